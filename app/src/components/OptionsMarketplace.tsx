@@ -26,12 +26,10 @@ export default function OptionsMarketplace({ client }: OptionsMarketplaceProps)
 
     setLoading(true);
     try {
-      const timestamp = new BN(Date.now());
       const expiration = new BN(Date.now() + parseInt(expirationDays) * 24 * 60 * 60 * 1000);
       
       // This would need actual mint addresses and token accounts
       const tx = await client.writeOption({
-        timestamp,
         optionType: optionType === 'call' ? { call: {} } : { put: {} },
         strikePrice: new BN(parseFloat(strikePrice) * 1_000_000),
         expirationTimestamp: expiration,
@@ -14,25 +14,21 @@ export type SolanaOptionsDex = {
   },
   "instructions": [
     {
-      "name": "buyOption",
+      "name": "acceptProtocolAuthority",
       "docs": [
-        "Buy an option contract"
+        "Accept a pending protocol authority handover (signed by the new authority)"
       ],
       "discriminator": [
-        242,
-        253,
-        221,
-        183,
-        67,
-        244,
-        140,
-        119
+        237,
+        122,
+        6,
+        39,
+        53,
+        202,
+        141,
+        113
       ],
       "accounts": [
-        {
-          "name": "optionContract",
-          "writable": true
-        },
         {
           "name": "protocolState",
           "writable": true,
@@ -61,299 +57,175 @@ export type SolanaOptionsDex = {
           }
         },
         {
-          "name": "buyerPosition",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  98,
-                  117,
-                  121,
-                  101,
-                  114,
-                  95,
-                  112,
-                  111,
-                  115,
-                  105,
-                  116,
-                  105,
-                  111,
-                  110
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "buyer"
-              },
-              {
-                "kind": "account",
-                "path": "optionContract"
-              }
-            ]
-          }
-        },
-        {
-          "name": "buyerQuoteAccount",
-          "writable": true
-        },
-        {
-          "name": "writerQuoteAccount",
-          "writable": true
-        },
-        {
-          "name": "protocolFeeAccount",
-          "writable": true
-        },
-        {
-          "name": "buyer",
-          "writable": true,
+          "name": "pendingAuthority",
           "signer": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "contractsToBuy",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "claimExpiredOption",
+      "name": "acceptRfqQuote",
       "docs": [
-        "Claim expired options (liquidation)"
+        "Accept a quote: writes a new option contract and settles the premium atomically"
       ],
       "discriminator": [
-        138,
-        45,
-        136,
-        218,
-        130,
-        8,
-        140,
-        129
+        40,
+        89,
+        201,
+        232,
+        141,
+        150,
+        142,
+        69
       ],
       "accounts": [
         {
-          "name": "optionContract",
+          "name": "rfq",
           "writable": true
         },
         {
-          "name": "collateralVault",
+          "name": "rfqQuote",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
+                  114,
+                  102,
+                  113,
+                  95,
+                  113,
+                  117,
                   111,
-                  108,
-                  108,
-                  97,
                   116,
-                  101,
-                  114,
-                  97,
-                  108
+                  101
                 ]
               },
               {
                 "kind": "account",
-                "path": "optionContract"
+                "path": "rfq"
+              },
+              {
+                "kind": "account",
+                "path": "rfq_quote.maker",
+                "account": "rfqQuote"
               }
             ]
           }
         },
         {
-          "name": "quoteCollateralVault",
+          "name": "rfqEscrow",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  114,
+                  102,
                   113,
-                  117,
-                  111,
-                  116,
-                  101,
                   95,
-                  99,
-                  111,
-                  108,
-                  108,
-                  97,
-                  116,
                   101,
+                  115,
+                  99,
                   114,
-                  97,
-                  108
+                  111,
+                  119
                 ]
               },
               {
                 "kind": "account",
-                "path": "optionContract"
+                "path": "rfqQuote"
               }
             ]
           }
         },
         {
-          "name": "writerTokenAccount",
+          "name": "maker",
           "writable": true
         },
         {
-          "name": "writerQuoteAccount",
-          "writable": true
+          "name": "writer"
         },
         {
-          "name": "writer",
-          "signer": true
+          "name": "buyer"
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "createOptionsMarket",
-      "docs": [
-        "Create a new options market for a specific underlying token"
-      ],
-      "discriminator": [
-        91,
-        147,
-        134,
-        225,
-        99,
-        65,
-        16,
-        44
-      ],
-      "accounts": [
-        {
-          "name": "optionsMarket",
+          "name": "writerAccount",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  112,
-                  116,
+                  119,
+                  114,
                   105,
-                  111,
-                  110,
-                  115,
+                  116,
+                  101,
+                  114,
                   95,
-                  109,
                   97,
-                  114,
-                  107,
-                  101,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
                   116
                 ]
               },
               {
-                "kind": "arg",
-                "path": "marketId"
+                "kind": "account",
+                "path": "writer"
               }
             ]
           }
         },
-        {
-          "name": "underlyingMint"
-        },
-        {
-          "name": "quoteMint"
-        },
-        {
-          "name": "authority",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "marketId",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "exerciseOption",
-      "docs": [
-        "Exercise an option contract"
-      ],
-      "discriminator": [
-        231,
-        98,
-        131,
-        183,
-        245,
-        93,
-        122,
-        48
-      ],
-      "accounts": [
         {
           "name": "optionContract",
-          "writable": true
-        },
-        {
-          "name": "buyerPosition",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  98,
-                  117,
-                  121,
-                  101,
-                  114,
-                  95,
-                  112,
                   111,
-                  115,
-                  105,
+                  112,
                   116,
                   105,
                   111,
-                  110
+                  110,
+                  95,
+                  99,
+                  111,
+                  110,
+                  116,
+                  114,
+                  97,
+                  99,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "buyer"
+                "path": "writer"
               },
               {
                 "kind": "account",
-                "path": "optionContract"
+                "path": "writer_account.next_nonce",
+                "account": "writerAccount"
               }
             ]
           }
         },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
         {
           "name": "protocolState",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -379,13 +251,25 @@ export type SolanaOptionsDex = {
           }
         },
         {
-          "name": "collateralVault",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
+          "name": "underlyingMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "collateralMint",
+          "docs": [
+            "Underlying mint for calls, quote mint for puts"
+          ]
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
                   99,
                   111,
                   108,
@@ -406,29 +290,24 @@ export type SolanaOptionsDex = {
           }
         },
         {
-          "name": "quoteCollateralVault",
+          "name": "optionMint",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  113,
-                  117,
                   111,
+                  112,
                   116,
-                  101,
-                  95,
-                  99,
+                  105,
                   111,
-                  108,
-                  108,
-                  97,
-                  116,
-                  101,
-                  114,
-                  97,
-                  108
+                  110,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
                 ]
               },
               {
@@ -439,248 +318,336 @@ export type SolanaOptionsDex = {
           }
         },
         {
-          "name": "buyerTokenAccount",
-          "writable": true
-        },
-        {
-          "name": "buyerQuoteAccount",
-          "writable": true
-        },
-        {
-          "name": "writerTokenAccount",
-          "writable": true
-        },
-        {
-          "name": "writerQuoteAccount",
-          "writable": true
-        },
-        {
-          "name": "protocolFeeAccount",
-          "writable": true
-        },
-        {
-          "name": "buyer",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "initializeProtocol",
-      "docs": [
-        "Initialize the global protocol state"
-      ],
-      "discriminator": [
-        188,
-        233,
-        252,
-        106,
-        134,
-        146,
-        202,
-        91
-      ],
-      "accounts": [
-        {
-          "name": "protocolState",
+          "name": "writerMint",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  112,
+                  119,
                   114,
-                  111,
+                  105,
                   116,
-                  111,
-                  99,
-                  111,
-                  108,
+                  101,
+                  114,
                   95,
-                  115,
-                  116,
-                  97,
-                  116,
-                  101
+                  109,
+                  105,
+                  110,
+                  116
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
               }
             ]
           }
         },
         {
-          "name": "authority",
+          "name": "writerClaimTokenAccount",
           "writable": true,
-          "signer": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "protocolFeeRate",
-          "type": "u64"
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "writer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "writerMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
         },
         {
-          "name": "settlementFeeRate",
-          "type": "u64"
+          "name": "buyerOptionTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "buyer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
         },
         {
-          "name": "liquidationFeeRate",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "writeOption",
-      "docs": [
-        "Write (sell) a new option contract"
-      ],
-      "discriminator": [
-        96,
-        144,
-        104,
-        51,
-        39,
-        132,
-        235,
-        38
-      ],
-      "accounts": [
-        {
-          "name": "optionContract",
+          "name": "buyerPosition",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
+                  98,
+                  117,
+                  121,
+                  101,
+                  114,
+                  95,
                   112,
+                  111,
+                  115,
+                  105,
                   116,
                   105,
                   111,
-                  110,
-                  95,
-                  99,
-                  111,
-                  110,
-                  116,
-                  114,
-                  97,
-                  99,
-                  116
+                  110
                 ]
               },
               {
                 "kind": "account",
-                "path": "writer"
+                "path": "buyer"
               },
               {
                 "kind": "account",
-                "path": "underlyingMint"
-              },
-              {
-                "kind": "arg",
-                "path": "timestampSeed"
+                "path": "optionContract"
               }
             ]
           }
         },
         {
-          "name": "optionsMarket",
+          "name": "requesterCollateralAccount",
+          "docs": [
+            "Only needed when the requester writes"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "requesterQuoteAccount",
           "writable": true
         },
         {
-          "name": "underlyingMint"
+          "name": "makerQuoteAccount",
+          "writable": true
         },
         {
-          "name": "quoteMint"
+          "name": "makerTokenAccount",
+          "docs": [
+            "Receives what is left in the escrow; the maker's account for the escrowed mint"
+          ],
+          "writable": true
         },
         {
-          "name": "collateralVault",
+          "name": "feeVault",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  99,
-                  111,
-                  108,
-                  108,
-                  97,
-                  116,
+                  102,
                   101,
-                  114,
+                  101,
+                  95,
+                  118,
                   97,
-                  108
+                  117,
+                  108,
+                  116
                 ]
               },
               {
                 "kind": "account",
-                "path": "optionContract"
+                "path": "quoteMint"
               }
             ]
           }
         },
         {
-          "name": "quoteCollateralVault",
+          "name": "feeStats",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  113,
-                  117,
-                  111,
-                  116,
+                  102,
+                  101,
                   101,
                   95,
-                  99,
-                  111,
-                  108,
-                  108,
-                  97,
+                  115,
                   116,
-                  101,
-                  114,
                   97,
-                  108
+                  116,
+                  115
                 ]
               },
               {
                 "kind": "account",
-                "path": "optionContract"
+                "path": "quoteMint"
               }
             ]
           }
         },
         {
-          "name": "writerTokenAccount",
-          "writable": true
-        },
-        {
-          "name": "writerQuoteAccount",
-          "writable": true
-        },
-        {
-          "name": "writer",
-          "writable": true,
-          "signer": true
+          "name": "requester",
+          "writable": true,
+          "signer": true
         },
         {
           "name": "tokenProgram",
           "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
@@ -692,182 +659,7310 @@ export type SolanaOptionsDex = {
       ],
       "args": [
         {
-          "name": "timestampSeed",
-          "type": "i64"
+          "name": "limitPremium",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "buyFromPool",
+      "docs": [
+        "Buy options from the pool, which writes a new contract at its model price"
+      ],
+      "discriminator": [
+        118,
+        108,
+        243,
+        198,
+        19,
+        184,
+        41,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionsMarket"
+              }
+            ]
+          }
         },
         {
-          "name": "optionType",
-          "type": {
-            "defined": {
-              "name": "optionType"
-            }
+          "name": "poolUnderlyingVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  117,
+                  110,
+                  100,
+                  101,
+                  114,
+                  108,
+                  121,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
           }
         },
         {
-          "name": "strikePrice",
-          "type": "u64"
+          "name": "poolQuoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
         },
         {
-          "name": "expirationTimestamp",
-          "type": "i64"
+          "name": "writerAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  114,
+                  105,
+                  116,
+                  101,
+                  114,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "optionContract",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  99,
+                  111,
+                  110,
+                  116,
+                  114,
+                  97,
+                  99,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "writer_account.next_nonce",
+                "account": "writerAccount"
+              }
+            ]
+          }
         },
         {
-          "name": "premiumPerContract",
-          "type": "u64"
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "buyerPosition",
-      "discriminator": [
-        232,
-        163,
-        167,
-        95,
-        170,
-        210,
-        214,
-        83
-      ]
-    },
-    {
-      "name": "optionContract",
-      "discriminator": [
-        196,
-        220,
-        72,
-        61,
-        245,
-        42,
-        68,
-        234
-      ]
-    },
-    {
-      "name": "optionsMarket",
-      "discriminator": [
-        67,
-        30,
-        90,
-        36,
-        130,
-        219,
-        166,
-        8
-      ]
-    },
-    {
-      "name": "protocolState",
-      "discriminator": [
-        33,
-        51,
-        173,
-        134,
-        35,
-        140,
-        195,
-        248
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "expirationInPast",
-      "msg": "Expiration timestamp is in the past"
-    },
-    {
-      "code": 6001,
-      "name": "invalidAmount",
-      "msg": "Invalid amount"
-    },
-    {
-      "code": 6002,
-      "name": "invalidPremium",
-      "msg": "Invalid premium"
-    },
-    {
-      "code": 6003,
-      "name": "optionExpired",
-      "msg": "Option has expired"
-    },
-    {
-      "code": 6004,
-      "name": "optionAlreadyExercised",
-      "msg": "Option already exercised"
-    },
-    {
-      "code": 6005,
-      "name": "positionAlreadyExercised",
-      "msg": "Position already exercised"
-    },
-    {
-      "code": 6006,
-      "name": "insufficientContracts",
-      "msg": "Insufficient contracts available"
-    },
-    {
-      "code": 6007,
-      "name": "noContractsOwned",
-      "msg": "No contracts owned"
-    },
-    {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "underlyingMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "collateralMint",
+          "docs": [
+            "Underlying mint for calls, quote mint for puts"
+          ]
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  114,
+                  105,
+                  116,
+                  101,
+                  114,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolWriterTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "writerMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "buyerOptionTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "buyer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "buyerPosition",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  121,
+                  101,
+                  114,
+                  95,
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "buyer"
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "feeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "quoteMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "quoteMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "optionType",
+          "type": {
+            "defined": {
+              "name": "optionType"
+            }
+          }
+        },
+        {
+          "name": "strikePrice",
+          "type": "u64"
+        },
+        {
+          "name": "expirationTimestamp",
+          "type": "i64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "maxTotalCost",
+          "type": "u64"
+        },
+        {
+          "name": "validUntil",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "buyOption",
+      "docs": [
+        "Buy option contracts, opening or topping up the buyer position"
+      ],
+      "discriminator": [
+        242,
+        253,
+        221,
+        183,
+        67,
+        244,
+        140,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyerPosition",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  121,
+                  101,
+                  114,
+                  95,
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "buyer"
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "collateralVault",
+          "docs": [
+            "Receives put premiums"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "proceedsMint",
+          "docs": [
+            "Quote mint for calls, underlying mint for puts"
+          ]
+        },
+        {
+          "name": "proceedsVault",
+          "docs": [
+            "Receives call premiums; created by the first buy or exercise"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  99,
+                  101,
+                  101,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionMint",
+          "writable": true
+        },
+        {
+          "name": "buyerOptionTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "feeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "option_contract.quote_mint",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "option_contract.quote_mint",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "contractsToBuy",
+          "type": "u64"
+        },
+        {
+          "name": "maxPremium",
+          "type": "u64"
+        },
+        {
+          "name": "maxTotalCost",
+          "type": "u64"
+        },
+        {
+          "name": "validUntil",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "cancelOrder",
+      "docs": [
+        "Cancel a resting order and refund its escrow"
+      ],
+      "discriminator": [
+        95,
+        129,
+        237,
+        240,
+        8,
+        49,
+        223,
+        132
+      ],
+      "accounts": [
+        {
+          "name": "orderBook",
+          "writable": true
+        },
+        {
+          "name": "optionVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "quoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerOptionAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order_book.option_mint",
+                "account": "orderBook"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "ownerQuoteAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order_book.quote_mint",
+                "account": "orderBook"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "orderId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancelRfqQuote",
+      "docs": [
+        "Withdraw a quote and refund its escrow.",
+        "The maker can do this at any time; anyone can once the quote has expired or the RFQ is filled."
+      ],
+      "discriminator": [
+        146,
+        110,
+        21,
+        245,
+        12,
+        48,
+        77,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "rfq"
+        },
+        {
+          "name": "rfqQuote",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rfq"
+              },
+              {
+                "kind": "account",
+                "path": "rfq_quote.maker",
+                "account": "rfqQuote"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rfqEscrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113,
+                  95,
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rfqQuote"
+              }
+            ]
+          }
+        },
+        {
+          "name": "makerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelUnsold",
+      "docs": [
+        "Cancel unsold contracts before expiry and return their collateral to the writer"
+      ],
+      "discriminator": [
+        200,
+        104,
+        243,
+        142,
+        39,
+        96,
+        84,
+        65
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerMint",
+          "writable": true
+        },
+        {
+          "name": "writerClaimTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "writer",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "contractsToCancel",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "claimExpiredOption",
+      "docs": [
+        "Burn writer tokens for a pro-rata share of the collateral and proceeds left after expiry"
+      ],
+      "discriminator": [
+        138,
+        45,
+        136,
+        218,
+        130,
+        8,
+        140,
+        129
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "proceedsVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  99,
+                  101,
+                  101,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerMint",
+          "writable": true
+        },
+        {
+          "name": "writerClaimTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "writer",
+          "docs": [
+            "Holder of the writer tokens being redeemed"
+          ],
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "claimParkedFill",
+      "docs": [
+        "Collect the fills the crank parked because the maker's token account could not receive them"
+      ],
+      "discriminator": [
+        42,
+        150,
+        124,
+        71,
+        127,
+        103,
+        212,
+        157
+      ],
+      "accounts": [
+        {
+          "name": "orderBook",
+          "writable": true
+        },
+        {
+          "name": "optionVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "quoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "makerOptionAccount",
+          "docs": [
+            "Receives parked option tokens; required when any are owed"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "makerQuoteAccount",
+          "docs": [
+            "Receives parked quote; required when any is owed"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "maker",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeBuyerPosition",
+      "docs": [
+        "Close a buyer position that can no longer be exercised, returning the rent to whoever paid it"
+      ],
+      "discriminator": [
+        239,
+        121,
+        221,
+        225,
+        204,
+        96,
+        171,
+        212
+      ],
+      "accounts": [
+        {
+          "name": "buyerPosition",
+          "writable": true
+        },
+        {
+          "name": "optionContract"
+        },
+        {
+          "name": "buyer",
+          "signer": true
+        },
+        {
+          "name": "rentPayer",
+          "writable": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeOptionContract",
+      "docs": [
+        "Close a settled option contract and its empty vaults, returning the rent to whoever paid it.",
+        "The writer's emptied writer token account is closed too when passed. The option and writer",
+        "mints stay open: the SPL Token program cannot close a mint, so their rent is not recoverable."
+      ],
+      "discriminator": [
+        229,
+        23,
+        125,
+        202,
+        47,
+        181,
+        246,
+        27
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "proceedsVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  99,
+                  101,
+                  101,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writer",
+          "signer": true
+        },
+        {
+          "name": "rentPayer",
+          "writable": true
+        },
+        {
+          "name": "proceedsRentPayer",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "writerClaimTokenAccount",
+          "docs": [
+            "The writer's emptied writer token account, closed along with the contract when passed"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "consumeEvents",
+      "docs": [
+        "Pay out queued maker fills (permissionless crank).",
+        "`remaining_accounts` holds each maker's option and quote token accounts, in event order.",
+        "A fill whose maker account was closed or frozen is parked for `claim_parked_fill` instead."
+      ],
+      "discriminator": [
+        221,
+        145,
+        177,
+        52,
+        31,
+        47,
+        63,
+        201
+      ],
+      "accounts": [
+        {
+          "name": "orderBook",
+          "writable": true
+        },
+        {
+          "name": "optionVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "quoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "createOptionsMarket",
+      "docs": [
+        "Create a new options market for a specific underlying token"
+      ],
+      "discriminator": [
+        91,
+        147,
+        134,
+        225,
+        99,
+        65,
+        16,
+        44
+      ],
+      "accounts": [
+        {
+          "name": "optionsMarket",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115,
+                  95,
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "marketId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "underlyingMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "quoteMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "quoteMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "marketId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "createOrderBook",
+      "docs": [
+        "Create the order book and its escrow vaults for an option series"
+      ],
+      "discriminator": [
+        153,
+        114,
+        9,
+        51,
+        100,
+        68,
+        240,
+        197
+      ],
+      "accounts": [
+        {
+          "name": "orderBook",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionContract"
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "optionMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "optionVault",
+          "docs": [
+            "Escrow for asks and for option tokens owed to bid makers"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "quoteVault",
+          "docs": [
+            "Escrow for bids and for quote owed to ask makers"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createPool",
+      "docs": [
+        "Create the liquidity pool for a market. Market authority only."
+      ],
+      "discriminator": [
+        233,
+        146,
+        209,
+        142,
+        207,
+        104,
+        64,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionsMarket"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "underlyingMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "lpMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  112,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolUnderlyingVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  117,
+                  110,
+                  100,
+                  101,
+                  114,
+                  108,
+                  121,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolQuoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "volatilityBps",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "createRfq",
+      "docs": [
+        "Broadcast a request for quotes on a block of options"
+      ],
+      "discriminator": [
+        127,
+        84,
+        185,
+        131,
+        235,
+        154,
+        189,
+        2
+      ],
+      "accounts": [
+        {
+          "name": "rfq",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "requester"
+              },
+              {
+                "kind": "arg",
+                "path": "rfqId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "requester",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "rfqId",
+          "type": "u64"
+        },
+        {
+          "name": "side",
+          "type": {
+            "defined": {
+              "name": "side"
+            }
+          }
+        },
+        {
+          "name": "optionType",
+          "type": {
+            "defined": {
+              "name": "optionType"
+            }
+          }
+        },
+        {
+          "name": "strikePrice",
+          "type": "u64"
+        },
+        {
+          "name": "expirationTimestamp",
+          "type": "i64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "quoteDeadline",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "depositLiquidity",
+      "docs": [
+        "Deposit underlying and quote tokens into the pool for LP shares",
+        "Every open pool-written contract goes in `remaining_accounts`, sorted by address."
+      ],
+      "discriminator": [
+        245,
+        99,
+        59,
+        25,
+        151,
+        71,
+        233,
+        249
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.options_market",
+                "account": "liquidityPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  112,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolUnderlyingVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  117,
+                  110,
+                  100,
+                  101,
+                  114,
+                  108,
+                  121,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolQuoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "depositorUnderlyingAccount",
+          "writable": true
+        },
+        {
+          "name": "depositorQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "depositorLpAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "depositor"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "lpMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "depositor",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "underlyingAmount",
+          "type": "u64"
+        },
+        {
+          "name": "quoteAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minShares",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "exerciseOption",
+      "docs": [
+        "Exercise option tokens held by the signer, burning them"
+      ],
+      "discriminator": [
+        231,
+        98,
+        131,
+        183,
+        245,
+        93,
+        122,
+        48
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "buyerPosition",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  121,
+                  101,
+                  114,
+                  95,
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "buyer"
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionMint",
+          "writable": true
+        },
+        {
+          "name": "buyerOptionTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "buyerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "proceedsMint",
+          "docs": [
+            "Quote mint for calls, underlying mint for puts"
+          ]
+        },
+        {
+          "name": "proceedsVault",
+          "docs": [
+            "Receives the strike payment for calls, the underlying for puts; created on first use"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  99,
+                  101,
+                  101,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "option_contract.quote_mint",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "option_contract.quote_mint",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "docs": [
+            "Required when the contract was written by the market's pool"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyer",
+          "docs": [
+            "Holder of the option tokens being exercised"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "minReceived",
+          "type": "u64"
+        },
+        {
+          "name": "maxPaid",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "initializeProtocol",
+      "docs": [
+        "Initialize the global protocol state"
+      ],
+      "discriminator": [
+        188,
+        233,
+        252,
+        106,
+        134,
+        146,
+        202,
+        91
+      ],
+      "accounts": [
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "protocolFeeRate",
+          "type": "u64"
+        },
+        {
+          "name": "settlementFeeRate",
+          "type": "u64"
+        },
+        {
+          "name": "liquidationFeeRate",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "mintUnsoldOptions",
+      "docs": [
+        "Mint option tokens for unsold contracts to the writer, e.g. to offer them on the order book"
+      ],
+      "discriminator": [
+        120,
+        34,
+        92,
+        131,
+        236,
+        122,
+        147,
+        64
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionMint",
+          "writable": true
+        },
+        {
+          "name": "writerOptionTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writer",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "placeOrder",
+      "docs": [
+        "Place a limit order; the crossing part fills immediately, the rest rests on the book"
+      ],
+      "discriminator": [
+        51,
+        194,
+        155,
+        175,
+        109,
+        130,
+        96,
+        106
+      ],
+      "accounts": [
+        {
+          "name": "orderBook",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114,
+                  95,
+                  98,
+                  111,
+                  111,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionContract"
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "quoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  111,
+                  107,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "orderBook"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "option_contract.quote_mint",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "option_contract.quote_mint",
+                "account": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerOptionAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order_book.option_mint",
+                "account": "orderBook"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "ownerQuoteAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order_book.quote_mint",
+                "account": "orderBook"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": {
+              "name": "side"
+            }
+          }
+        },
+        {
+          "name": "price",
+          "type": "u64"
+        },
+        {
+          "name": "quantity",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "proposeProtocolAuthority",
+      "docs": [
+        "Propose a new protocol authority (first step of the handover)"
+      ],
+      "discriminator": [
+        196,
+        230,
+        103,
+        192,
+        225,
+        211,
+        253,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setMarketStatus",
+      "docs": [
+        "Activate or deactivate an options market (market authority only)"
+      ],
+      "discriminator": [
+        101,
+        175,
+        83,
+        107,
+        200,
+        141,
+        155,
+        182
+      ],
+      "accounts": [
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "isActive",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setPauseFlags",
+      "docs": [
+        "Set the protocol pause flags (authority only)"
+      ],
+      "discriminator": [
+        205,
+        167,
+        85,
+        237,
+        144,
+        202,
+        248,
+        175
+      ],
+      "accounts": [
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "pauseFlags",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setPoolReferencePrice",
+      "docs": [
+        "Update the spot price the pool prices and bands strikes against. Market authority only."
+      ],
+      "discriminator": [
+        81,
+        190,
+        243,
+        206,
+        65,
+        50,
+        88,
+        204
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionsMarket"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "authority",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "referencePrice",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setPoolVolatility",
+      "docs": [
+        "Update the volatility the pool prices options at. Market authority only."
+      ],
+      "discriminator": [
+        25,
+        78,
+        253,
+        230,
+        242,
+        40,
+        239,
+        153
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionsMarket"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "authority",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "volatilityBps",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "settleExpiredOption",
+      "docs": [
+        "Settle an expired contract on behalf of a writer token holder (callable by any keeper).",
+        "The keeper earns the liquidation fee on the returned collateral."
+      ],
+      "discriminator": [
+        27,
+        170,
+        130,
+        132,
+        220,
+        4,
+        251,
+        212
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "proceedsVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  99,
+                  101,
+                  101,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerMint",
+          "writable": true
+        },
+        {
+          "name": "writerClaimTokenAccount",
+          "docs": [
+            "Writer token account being settled; must have delegated to the option contract"
+          ],
+          "writable": true
+        },
+        {
+          "name": "writerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "keeperTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "keeperQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "keeper",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "settlePoolOption",
+      "docs": [
+        "Return the collateral of an expired pool-written contract to the pool and close it (permissionless)"
+      ],
+      "discriminator": [
+        206,
+        74,
+        29,
+        35,
+        146,
+        135,
+        222,
+        1
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionsMarket"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "proceedsVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  99,
+                  101,
+                  101,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerMint",
+          "writable": true
+        },
+        {
+          "name": "poolWriterTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "writerMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "poolUnderlyingVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  117,
+                  110,
+                  100,
+                  101,
+                  114,
+                  108,
+                  121,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolQuoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rentPayer",
+          "writable": true
+        },
+        {
+          "name": "proceedsRentPayer",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "startDutchAuction",
+      "docs": [
+        "List the unsold contracts in a descending-price (Dutch) auction"
+      ],
+      "discriminator": [
+        51,
+        18,
+        177,
+        159,
+        48,
+        184,
+        54,
+        247
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "writer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "startPremium",
+          "type": "u64"
+        },
+        {
+          "name": "floorPremium",
+          "type": "u64"
+        },
+        {
+          "name": "duration",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "submitRfqQuote",
+      "docs": [
+        "Quote an RFQ, escrowing the maker's side of the trade"
+      ],
+      "discriminator": [
+        227,
+        124,
+        174,
+        46,
+        203,
+        73,
+        20,
+        185
+      ],
+      "accounts": [
+        {
+          "name": "rfq"
+        },
+        {
+          "name": "rfqQuote",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rfq"
+              },
+              {
+                "kind": "account",
+                "path": "maker"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "escrowMint",
+          "docs": [
+            "Collateral mint when the requester buys, quote mint when they write"
+          ]
+        },
+        {
+          "name": "rfqEscrow",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  102,
+                  113,
+                  95,
+                  101,
+                  115,
+                  99,
+                  114,
+                  111,
+                  119
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rfqQuote"
+              }
+            ]
+          }
+        },
+        {
+          "name": "makerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "maker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "premiumPerContract",
+          "type": "u64"
+        },
+        {
+          "name": "validUntil",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "updatePremium",
+      "docs": [
+        "Reprice the unsold inventory of an option contract"
+      ],
+      "discriminator": [
+        167,
+        79,
+        22,
+        230,
+        193,
+        245,
+        77,
+        42
+      ],
+      "accounts": [
+        {
+          "name": "optionContract",
+          "writable": true
+        },
+        {
+          "name": "writer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "premiumPerContract",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateProtocolConfig",
+      "docs": [
+        "Update the protocol fee rates (authority only)"
+      ],
+      "discriminator": [
+        197,
+        97,
+        123,
+        54,
+        221,
+        168,
+        11,
+        135
+      ],
+      "accounts": [
+        {
+          "name": "protocolState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "protocolFeeRate",
+          "type": "u64"
+        },
+        {
+          "name": "settlementFeeRate",
+          "type": "u64"
+        },
+        {
+          "name": "liquidationFeeRate",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawLiquidity",
+      "docs": [
+        "Burn LP shares for a pro-rata share of the pool, paid out of its free liquidity.",
+        "Every open pool-written contract goes in `remaining_accounts`, sorted by address."
+      ],
+      "discriminator": [
+        149,
+        158,
+        33,
+        185,
+        47,
+        243,
+        253,
+        31
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.options_market",
+                "account": "liquidityPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket"
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  112,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolUnderlyingVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  117,
+                  110,
+                  100,
+                  101,
+                  114,
+                  108,
+                  121,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolQuoteVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  113,
+                  117,
+                  111,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "depositorUnderlyingAccount",
+          "writable": true
+        },
+        {
+          "name": "depositorQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "depositorLpAccount",
+          "writable": true
+        },
+        {
+          "name": "depositor",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "shares",
+          "type": "u64"
+        },
+        {
+          "name": "minUnderlying",
+          "type": "u64"
+        },
+        {
+          "name": "minQuote",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawProtocolFees",
+      "docs": [
+        "Sweep collected protocol fees for one mint to a treasury account (authority only)"
+      ],
+      "discriminator": [
+        11,
+        68,
+        165,
+        98,
+        18,
+        208,
+        134,
+        73
+      ],
+      "accounts": [
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "fee_stats.mint",
+                "account": "feeStats"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  101,
+                  101,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "fee_stats.mint",
+                "account": "feeStats"
+              }
+            ]
+          }
+        },
+        {
+          "name": "treasury",
+          "writable": true
+        },
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "writeOption",
+      "docs": [
+        "Write (sell) a new option contract"
+      ],
+      "discriminator": [
+        96,
+        144,
+        104,
+        51,
+        39,
+        132,
+        235,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "writerAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  114,
+                  105,
+                  116,
+                  101,
+                  114,
+                  95,
+                  97,
+                  99,
+                  99,
+                  111,
+                  117,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "writer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionContract",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  99,
+                  111,
+                  110,
+                  116,
+                  114,
+                  97,
+                  99,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "writer"
+              },
+              {
+                "kind": "account",
+                "path": "writer_account.next_nonce",
+                "account": "writerAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionsMarket",
+          "writable": true
+        },
+        {
+          "name": "protocolState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  116,
+                  111,
+                  99,
+                  111,
+                  108,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "underlyingMint"
+        },
+        {
+          "name": "quoteMint"
+        },
+        {
+          "name": "collateralMint",
+          "docs": [
+            "Underlying mint for calls, quote mint for puts"
+          ]
+        },
+        {
+          "name": "collateralVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  108,
+                  108,
+                  97,
+                  116,
+                  101,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "optionMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  112,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  114,
+                  105,
+                  116,
+                  101,
+                  114,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "optionContract"
+              }
+            ]
+          }
+        },
+        {
+          "name": "writerClaimTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "writer"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "writerMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "writerTokenAccount",
+          "writable": true
+        },
+        {
+          "name": "writerQuoteAccount",
+          "writable": true
+        },
+        {
+          "name": "writer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "optionType",
+          "type": {
+            "defined": {
+              "name": "optionType"
+            }
+          }
+        },
+        {
+          "name": "strikePrice",
+          "type": "u64"
+        },
+        {
+          "name": "expirationTimestamp",
+          "type": "i64"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "premiumPerContract",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "buyerPosition",
+      "discriminator": [
+        232,
+        163,
+        167,
+        95,
+        170,
+        210,
+        214,
+        83
+      ]
+    },
+    {
+      "name": "feeStats",
+      "discriminator": [
+        178,
+        157,
+        11,
+        16,
+        173,
+        135,
+        166,
+        11
+      ]
+    },
+    {
+      "name": "liquidityPool",
+      "discriminator": [
+        66,
+        38,
+        17,
+        64,
+        188,
+        80,
+        68,
+        129
+      ]
+    },
+    {
+      "name": "optionContract",
+      "discriminator": [
+        196,
+        220,
+        72,
+        61,
+        245,
+        42,
+        68,
+        234
+      ]
+    },
+    {
+      "name": "optionsMarket",
+      "discriminator": [
+        67,
+        30,
+        90,
+        36,
+        130,
+        219,
+        166,
+        8
+      ]
+    },
+    {
+      "name": "protocolState",
+      "discriminator": [
+        33,
+        51,
+        173,
+        134,
+        35,
+        140,
+        195,
+        248
+      ]
+    },
+    {
+      "name": "rfq",
+      "discriminator": [
+        106,
+        19,
+        109,
+        78,
+        169,
+        13,
+        234,
+        58
+      ]
+    },
+    {
+      "name": "rfqQuote",
+      "discriminator": [
+        145,
+        52,
+        8,
+        29,
+        156,
+        146,
+        29,
+        195
+      ]
+    },
+    {
+      "name": "writerAccount",
+      "discriminator": [
+        77,
+        195,
+        76,
+        106,
+        167,
+        216,
+        42,
+        205
+      ]
+    },
+    {
+      "name": "orderBook",
+      "discriminator": [
+        55,
+        230,
+        125,
+        218,
+        149,
+        39,
+        65,
+        248
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "buyerPositionClosed",
+      "discriminator": [
+        181,
+        96,
+        231,
+        18,
+        209,
+        248,
+        145,
+        107
+      ]
+    },
+    {
+      "name": "dutchAuctionStarted",
+      "discriminator": [
+        50,
+        17,
+        130,
+        238,
+        165,
+        23,
+        153,
+        107
+      ]
+    },
+    {
+      "name": "eventsConsumed",
+      "discriminator": [
+        215,
+        189,
+        44,
+        221,
+        117,
+        199,
+        17,
+        252
+      ]
+    },
+    {
+      "name": "expiredOptionSettled",
+      "discriminator": [
+        114,
+        10,
+        221,
+        53,
+        156,
+        62,
+        145,
+        178
+      ]
+    },
+    {
+      "name": "liquidityDeposited",
+      "discriminator": [
+        218,
+        155,
+        74,
+        193,
+        59,
+        66,
+        94,
+        122
+      ]
+    },
+    {
+      "name": "liquidityWithdrawn",
+      "discriminator": [
+        240,
+        120,
+        73,
+        139,
+        154,
+        31,
+        218,
+        68
+      ]
+    },
+    {
+      "name": "marketCreated",
+      "discriminator": [
+        88,
+        184,
+        130,
+        231,
+        226,
+        84,
+        6,
+        58
+      ]
+    },
+    {
+      "name": "marketStatusChanged",
+      "discriminator": [
+        53,
+        72,
+        160,
+        209,
+        15,
+        222,
+        46,
+        157
+      ]
+    },
+    {
+      "name": "optionContractClosed",
+      "discriminator": [
+        87,
+        158,
+        24,
+        233,
+        21,
+        204,
+        120,
+        99
+      ]
+    },
+    {
+      "name": "optionExercised",
+      "discriminator": [
+        34,
+        100,
+        89,
+        14,
+        247,
+        159,
+        22,
+        97
+      ]
+    },
+    {
+      "name": "optionExpiredClaimed",
+      "discriminator": [
+        243,
+        211,
+        157,
+        64,
+        39,
+        235,
+        75,
+        173
+      ]
+    },
+    {
+      "name": "optionPurchased",
+      "discriminator": [
+        9,
+        175,
+        211,
+        168,
+        31,
+        202,
+        39,
+        191
+      ]
+    },
+    {
+      "name": "optionWritten",
+      "discriminator": [
+        216,
+        89,
+        143,
+        186,
+        129,
+        212,
+        10,
+        147
+      ]
+    },
+    {
+      "name": "orderBookCreated",
+      "discriminator": [
+        94,
+        35,
+        16,
+        200,
+        201,
+        134,
+        157,
+        238
+      ]
+    },
+    {
+      "name": "orderCancelled",
+      "discriminator": [
+        108,
+        56,
+        128,
+        68,
+        168,
+        113,
+        168,
+        239
+      ]
+    },
+    {
+      "name": "orderPlaced",
+      "discriminator": [
+        96,
+        130,
+        204,
+        234,
+        169,
+        219,
+        216,
+        227
+      ]
+    },
+    {
+      "name": "parkedFillClaimed",
+      "discriminator": [
+        148,
+        206,
+        134,
+        153,
+        35,
+        79,
+        246,
+        105
+      ]
+    },
+    {
+      "name": "pauseFlagsSet",
+      "discriminator": [
+        67,
+        234,
+        75,
+        170,
+        161,
+        26,
+        210,
+        36
+      ]
+    },
+    {
+      "name": "poolCreated",
+      "discriminator": [
+        202,
+        44,
+        41,
+        88,
+        104,
+        220,
+        157,
+        82
+      ]
+    },
+    {
+      "name": "poolOptionSettled",
+      "discriminator": [
+        17,
+        76,
+        27,
+        52,
+        204,
+        108,
+        30,
+        151
+      ]
+    },
+    {
+      "name": "poolOptionSold",
+      "discriminator": [
+        149,
+        3,
+        209,
+        132,
+        124,
+        169,
+        178,
+        158
+      ]
+    },
+    {
+      "name": "poolReferencePriceUpdated",
+      "discriminator": [
+        223,
+        75,
+        145,
+        144,
+        28,
+        177,
+        48,
+        154
+      ]
+    },
+    {
+      "name": "poolVolatilityUpdated",
+      "discriminator": [
+        111,
+        165,
+        186,
+        46,
+        189,
+        112,
+        43,
+        110
+      ]
+    },
+    {
+      "name": "premiumUpdated",
+      "discriminator": [
+        97,
+        63,
+        51,
+        110,
+        100,
+        42,
+        186,
+        152
+      ]
+    },
+    {
+      "name": "protocolAuthorityAccepted",
+      "discriminator": [
+        14,
+        90,
+        138,
+        158,
+        76,
+        49,
+        104,
+        97
+      ]
+    },
+    {
+      "name": "protocolAuthorityProposed",
+      "discriminator": [
+        73,
+        229,
+        220,
+        91,
+        40,
+        178,
+        53,
+        17
+      ]
+    },
+    {
+      "name": "protocolConfigUpdated",
+      "discriminator": [
+        20,
+        99,
+        32,
+        237,
+        111,
+        86,
+        195,
+        199
+      ]
+    },
+    {
+      "name": "protocolFeesWithdrawn",
+      "discriminator": [
+        202,
+        213,
+        134,
+        216,
+        108,
+        14,
+        84,
+        99
+      ]
+    },
+    {
+      "name": "protocolInitialized",
+      "discriminator": [
+        173,
+        122,
+        168,
+        254,
+        9,
+        118,
+        76,
+        132
+      ]
+    },
+    {
+      "name": "rfqCreated",
+      "discriminator": [
+        193,
+        254,
+        226,
+        174,
+        40,
+        151,
+        195,
+        165
+      ]
+    },
+    {
+      "name": "rfqQuoteAccepted",
+      "discriminator": [
+        77,
+        33,
+        56,
+        205,
+        153,
+        159,
+        220,
+        93
+      ]
+    },
+    {
+      "name": "rfqQuoteCancelled",
+      "discriminator": [
+        139,
+        156,
+        56,
+        228,
+        222,
+        206,
+        91,
+        158
+      ]
+    },
+    {
+      "name": "rfqQuoteSubmitted",
+      "discriminator": [
+        146,
+        209,
+        237,
+        166,
+        247,
+        174,
+        122,
+        123
+      ]
+    },
+    {
+      "name": "unsoldCancelled",
+      "discriminator": [
+        22,
+        49,
+        22,
+        30,
+        78,
+        233,
+        151,
+        143
+      ]
+    },
+    {
+      "name": "unsoldOptionsMinted",
+      "discriminator": [
+        171,
+        7,
+        234,
+        177,
+        75,
+        217,
+        119,
+        123
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "expirationInPast",
+      "msg": "Expiration timestamp is in the past"
+    },
+    {
+      "code": 6001,
+      "name": "invalidAmount",
+      "msg": "Invalid amount"
+    },
+    {
+      "code": 6002,
+      "name": "invalidPremium",
+      "msg": "Invalid premium"
+    },
+    {
+      "code": 6003,
+      "name": "optionExpired",
+      "msg": "Option has expired"
+    },
+    {
+      "code": 6004,
+      "name": "optionAlreadyExercised",
+      "msg": "Option already exercised"
+    },
+    {
+      "code": 6005,
+      "name": "positionAlreadyExercised",
+      "msg": "Position already exercised"
+    },
+    {
+      "code": 6006,
+      "name": "insufficientContracts",
+      "msg": "Insufficient contracts available"
+    },
+    {
+      "code": 6007,
+      "name": "noContractsOwned",
+      "msg": "No contracts owned"
+    },
+    {
       "code": 6008,
       "name": "mathOverflow",
       "msg": "Math overflow"
     },
     {
-      "code": 6009,
-      "name": "optionNotExpired",
-      "msg": "Option not expired yet"
+      "code": 6009,
+      "name": "optionNotExpired",
+      "msg": "Option not expired yet"
+    },
+    {
+      "code": 6010,
+      "name": "optionAlreadyClaimed",
+      "msg": "Option already claimed"
+    },
+    {
+      "code": 6011,
+      "name": "invalidOwner",
+      "msg": "Invalid account owner"
+    },
+    {
+      "code": 6012,
+      "name": "unauthorizedWriter",
+      "msg": "Unauthorized writer"
+    },
+    {
+      "code": 6013,
+      "name": "feeRateTooHigh",
+      "msg": "Fee rate exceeds maximum"
+    },
+    {
+      "code": 6014,
+      "name": "unauthorizedAuthority",
+      "msg": "Unauthorized protocol authority"
+    },
+    {
+      "code": 6015,
+      "name": "noPendingAuthority",
+      "msg": "No pending authority handover"
+    },
+    {
+      "code": 6016,
+      "name": "invalidPauseFlags",
+      "msg": "Invalid pause flags"
+    },
+    {
+      "code": 6017,
+      "name": "writingPaused",
+      "msg": "Writing options is paused"
+    },
+    {
+      "code": 6018,
+      "name": "buyingPaused",
+      "msg": "Buying options is paused"
+    },
+    {
+      "code": 6019,
+      "name": "exercisingPaused",
+      "msg": "Exercising options is paused"
+    },
+    {
+      "code": 6020,
+      "name": "claimingPaused",
+      "msg": "Claiming expired options is paused"
+    },
+    {
+      "code": 6021,
+      "name": "withdrawOnly",
+      "msg": "Protocol is in withdraw-only mode"
+    },
+    {
+      "code": 6022,
+      "name": "marketInactive",
+      "msg": "Options market is inactive"
+    },
+    {
+      "code": 6023,
+      "name": "unauthorizedMarketAuthority",
+      "msg": "Unauthorized market authority"
+    },
+    {
+      "code": 6024,
+      "name": "marketMismatch",
+      "msg": "Options market does not match option contract"
+    },
+    {
+      "code": 6025,
+      "name": "invalidMint",
+      "msg": "Token mint does not match"
+    },
+    {
+      "code": 6026,
+      "name": "exerciseExceedsPosition",
+      "msg": "Exercise amount exceeds position"
+    },
+    {
+      "code": 6027,
+      "name": "nothingToSettle",
+      "msg": "Nothing to settle"
+    },
+    {
+      "code": 6028,
+      "name": "premiumAboveLimit",
+      "msg": "Premium per contract is above the buyer's limit"
+    },
+    {
+      "code": 6029,
+      "name": "totalCostAboveLimit",
+      "msg": "Total cost is above the buyer's limit"
+    },
+    {
+      "code": 6030,
+      "name": "deadlineExceeded",
+      "msg": "Transaction deadline has passed"
+    },
+    {
+      "code": 6031,
+      "name": "receivedBelowMinimum",
+      "msg": "Amount received is below the minimum"
+    },
+    {
+      "code": 6032,
+      "name": "contractNotFinal",
+      "msg": "Option contract is not final yet"
+    },
+    {
+      "code": 6033,
+      "name": "vaultNotEmpty",
+      "msg": "Collateral vault is not empty"
+    },
+    {
+      "code": 6034,
+      "name": "positionStillOpen",
+      "msg": "Position can still be exercised"
+    },
+    {
+      "code": 6035,
+      "name": "contractMismatch",
+      "msg": "Option contract does not match"
+    },
+    {
+      "code": 6036,
+      "name": "orderBookFull",
+      "msg": "Order book side is full"
+    },
+    {
+      "code": 6037,
+      "name": "eventQueueFull",
+      "msg": "Order book event queue is full; crank it first"
+    },
+    {
+      "code": 6038,
+      "name": "orderNotFound",
+      "msg": "Order not found"
+    },
+    {
+      "code": 6039,
+      "name": "noEventsToConsume",
+      "msg": "No order book events to consume"
+    },
+    {
+      "code": 6040,
+      "name": "invalidMakerAccount",
+      "msg": "Maker token account does not match the event"
+    },
+    {
+      "code": 6041,
+      "name": "invalidDeadline",
+      "msg": "Invalid deadline"
+    },
+    {
+      "code": 6042,
+      "name": "rfqFilled",
+      "msg": "RFQ has already been filled"
+    },
+    {
+      "code": 6043,
+      "name": "premiumBelowLimit",
+      "msg": "Premium is below the requester's limit"
+    },
+    {
+      "code": 6044,
+      "name": "quoteStillActive",
+      "msg": "Quote is still active"
+    },
+    {
+      "code": 6045,
+      "name": "collateralAccountRequired",
+      "msg": "Collateral token account is required"
+    },
+    {
+      "code": 6046,
+      "name": "invalidVolatility",
+      "msg": "Volatility must be between 1 and MAX_VOLATILITY_BPS basis points"
+    },
+    {
+      "code": 6047,
+      "name": "insufficientLiquidity",
+      "msg": "Not enough free liquidity in the pool"
+    },
+    {
+      "code": 6048,
+      "name": "invalidAuction",
+      "msg": "Auction start premium must be above the floor and the duration positive"
+    },
+    {
+      "code": 6049,
+      "name": "paymentAboveLimit",
+      "msg": "Amount paid is above the limit"
+    },
+    {
+      "code": 6050,
+      "name": "staleReferencePrice",
+      "msg": "Pool reference price is missing or stale"
+    },
+    {
+      "code": 6051,
+      "name": "strikeOutOfRange",
+      "msg": "Strike is too far from the pool reference price"
+    },
+    {
+      "code": 6052,
+      "name": "poolRequired",
+      "msg": "Pool account is required for pool-written contracts"
+    },
+    {
+      "code": 6053,
+      "name": "proceedsRentPayerRequired",
+      "msg": "Proceeds vault rent payer account is required"
+    },
+    {
+      "code": 6054,
+      "name": "tooManyOpenContracts",
+      "msg": "Pool has too many open contracts; settle expired ones first"
+    },
+    {
+      "code": 6055,
+      "name": "openContractsMismatch",
+      "msg": "Every open pool contract must be passed once, sorted by address"
+    },
+    {
+      "code": 6056,
+      "name": "depositEarnsNoShares",
+      "msg": "One side of the deposit would earn no LP shares"
+    },
+    {
+      "code": 6057,
+      "name": "expiryTooSoon",
+      "msg": "Expiry is sooner than the pool's minimum time to expiry"
+    },
+    {
+      "code": 6058,
+      "name": "writerTokensOutstanding",
+      "msg": "Writer token account still holds writer tokens"
+    },
+    {
+      "code": 6059,
+      "name": "parkedFillsFull",
+      "msg": "Too many makers already have parked fills"
+    },
+    {
+      "code": 6060,
+      "name": "noParkedFill",
+      "msg": "No parked fill for this maker"
+    }
+  ],
+  "types": [
+    {
+      "name": "buyerPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "contractsOwned",
+            "type": "u64"
+          },
+          {
+            "name": "contractsExercised",
+            "type": "u64"
+          },
+          {
+            "name": "premiumPaid",
+            "type": "u64"
+          },
+          {
+            "name": "isExercised",
+            "type": "bool"
+          },
+          {
+            "name": "rentPayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "buyerPositionClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "buyerPosition",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "dutchAuctionStarted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "writer",
+            "type": "pubkey"
+          },
+          {
+            "name": "startPremium",
+            "type": "u64"
+          },
+          {
+            "name": "floorPremium",
+            "type": "u64"
+          },
+          {
+            "name": "startTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "duration",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "eventsConsumed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderBook",
+            "type": "pubkey"
+          },
+          {
+            "name": "count",
+            "type": "u8"
+          },
+          {
+            "name": "parked",
+            "type": "u8"
+          },
+          {
+            "name": "remaining",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "expiredOptionSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "holder",
+            "type": "pubkey"
+          },
+          {
+            "name": "keeper",
+            "type": "pubkey"
+          },
+          {
+            "name": "writerTokensBurned",
+            "type": "u64"
+          },
+          {
+            "name": "collateralAmount",
+            "type": "u64"
+          },
+          {
+            "name": "proceedsAmount",
+            "type": "u64"
+          },
+          {
+            "name": "keeperFee",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeStats",
+      "docs": [
+        "Per-mint breakdown of protocol fees; the tokens sit in the matching `fee_vault` PDA"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalFeesCollected",
+            "type": "u64"
+          },
+          {
+            "name": "totalFeesWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityDeposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "depositor",
+            "type": "pubkey"
+          },
+          {
+            "name": "underlyingAmount",
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "type": "u64"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityPool",
+      "docs": [
+        "Per-market pool that writes options on demand out of LP collateral"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "volatilityBps",
+            "type": "u64"
+          },
+          {
+            "name": "lockedUnderlying",
+            "type": "u64"
+          },
+          {
+            "name": "lockedQuote",
+            "type": "u64"
+          },
+          {
+            "name": "openContracts",
+            "type": "u64"
+          },
+          {
+            "name": "referencePrice",
+            "type": "u64"
+          },
+          {
+            "name": "referencePriceUpdatedAt",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "depositor",
+            "type": "pubkey"
+          },
+          {
+            "name": "shares",
+            "type": "u64"
+          },
+          {
+            "name": "underlyingAmount",
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "marketCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "marketId",
+            "type": "u64"
+          },
+          {
+            "name": "underlyingMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "underlyingDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "quoteDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "marketStatusChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "marketId",
+            "type": "u64"
+          },
+          {
+            "name": "isActive",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionContract",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "writer",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "underlyingMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionType",
+            "type": {
+              "defined": {
+                "name": "optionType"
+              }
+            }
+          },
+          {
+            "name": "strikePrice",
+            "type": "u64"
+          },
+          {
+            "name": "expirationTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "premiumPerContract",
+            "type": "u64"
+          },
+          {
+            "name": "contractsSold",
+            "type": "u64"
+          },
+          {
+            "name": "contractsExercised",
+            "type": "u64"
+          },
+          {
+            "name": "isExercised",
+            "type": "bool"
+          },
+          {
+            "name": "isExpired",
+            "type": "bool"
+          },
+          {
+            "name": "creationTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "optionMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "writerMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          },
+          {
+            "name": "auctionStartPremium",
+            "type": "u64"
+          },
+          {
+            "name": "auctionStartTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "auctionDuration",
+            "type": "i64"
+          },
+          {
+            "name": "rentPayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "heldPremium",
+            "type": "u64"
+          },
+          {
+            "name": "proceedsRentPayer",
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionContractClosed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "writer",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionExercised",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "holder",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionType",
+            "type": {
+              "defined": {
+                "name": "optionType"
+              }
+            }
+          },
+          {
+            "name": "contracts",
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "type": "u64"
+          },
+          {
+            "name": "settlementFee",
+            "type": "u64"
+          },
+          {
+            "name": "contractsExercised",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionExpiredClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "holder",
+            "type": "pubkey"
+          },
+          {
+            "name": "writerTokensBurned",
+            "type": "u64"
+          },
+          {
+            "name": "collateralAmount",
+            "type": "u64"
+          },
+          {
+            "name": "proceedsAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionPurchased",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "contracts",
+            "type": "u64"
+          },
+          {
+            "name": "premiumPerContract",
+            "type": "u64"
+          },
+          {
+            "name": "totalPremium",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "contractsSold",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "call"
+          },
+          {
+            "name": "put"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionWritten",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "writer",
+            "type": "pubkey"
+          },
+          {
+            "name": "nonce",
+            "type": "u64"
+          },
+          {
+            "name": "optionType",
+            "type": {
+              "defined": {
+                "name": "optionType"
+              }
+            }
+          },
+          {
+            "name": "strikePrice",
+            "type": "u64"
+          },
+          {
+            "name": "expirationTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "premiumPerContract",
+            "type": "u64"
+          },
+          {
+            "name": "collateralAmount",
+            "type": "u64"
+          },
+          {
+            "name": "optionMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "writerMint",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "optionsMarket",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketId",
+            "type": "u64"
+          },
+          {
+            "name": "underlyingMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "underlyingDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "quoteDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "underlyingScale",
+            "type": "u64"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalOptionsWritten",
+            "type": "u64"
+          },
+          {
+            "name": "totalVolume",
+            "type": "u64"
+          },
+          {
+            "name": "openInterestWritten",
+            "type": "u64"
+          },
+          {
+            "name": "openInterestSold",
+            "type": "u64"
+          },
+          {
+            "name": "totalContractsExercised",
+            "type": "u64"
+          },
+          {
+            "name": "totalContractsExpired",
+            "type": "u64"
+          },
+          {
+            "name": "isActive",
+            "type": "bool"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderBookCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderBook",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderBook",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "orderId",
+            "type": "u64"
+          },
+          {
+            "name": "side",
+            "type": {
+              "defined": {
+                "name": "side"
+              }
+            }
+          },
+          {
+            "name": "remainingQuantity",
+            "type": "u64"
+          },
+          {
+            "name": "refund",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderPlaced",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderBook",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": {
+              "defined": {
+                "name": "side"
+              }
+            }
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "filledQuantity",
+            "type": "u64"
+          },
+          {
+            "name": "notional",
+            "type": "u64"
+          },
+          {
+            "name": "fees",
+            "type": "u64"
+          },
+          {
+            "name": "restingOrderId",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "restingQuantity",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "parkedFillClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderBook",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionAmount",
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pauseFlagsSet",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "pauseFlags",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "lpMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "volatilityBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolOptionSettled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "collateralReturned",
+            "type": "u64"
+          },
+          {
+            "name": "proceedsReturned",
+            "type": "u64"
+          },
+          {
+            "name": "underlyingReleased",
+            "type": "u64"
+          },
+          {
+            "name": "quoteReleased",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolOptionSold",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionType",
+            "type": {
+              "defined": {
+                "name": "optionType"
+              }
+            }
+          },
+          {
+            "name": "strikePrice",
+            "type": "u64"
+          },
+          {
+            "name": "expirationTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "premiumPerContract",
+            "type": "u64"
+          },
+          {
+            "name": "totalPremium",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "collateralAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolReferencePriceUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "referencePrice",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolVolatilityUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "volatilityBps",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "optionAlreadyClaimed",
-      "msg": "Option already claimed"
+      "name": "premiumUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "writer",
+            "type": "pubkey"
+          },
+          {
+            "name": "oldPremium",
+            "type": "u64"
+          },
+          {
+            "name": "newPremium",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "invalidOwner",
-      "msg": "Invalid account owner"
+      "name": "protocolAuthorityAccepted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "previousAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolAuthorityProposed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolConfigUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "protocolFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "settlementFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "liquidationFeeRate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolFeesWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "treasury",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "totalFeesWithdrawn",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolInitialized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "protocolFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "settlementFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "liquidationFeeRate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "protocolState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "pauseFlags",
+            "type": "u8"
+          },
+          {
+            "name": "protocolFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "settlementFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "liquidationFeeRate",
+            "type": "u64"
+          },
+          {
+            "name": "totalVolume",
+            "type": "u64"
+          },
+          {
+            "name": "totalFeesCollected",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rfq",
+      "docs": [
+        "A request for quotes on a block of options; the accepted quote becomes a new `OptionContract`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "requester",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "rfqId",
+            "type": "u64"
+          },
+          {
+            "name": "side",
+            "type": {
+              "defined": {
+                "name": "side"
+              }
+            }
+          },
+          {
+            "name": "optionType",
+            "type": {
+              "defined": {
+                "name": "optionType"
+              }
+            }
+          },
+          {
+            "name": "strikePrice",
+            "type": "u64"
+          },
+          {
+            "name": "expirationTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "quoteDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "isFilled",
+            "type": "bool"
+          },
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rfqCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rfq",
+            "type": "pubkey"
+          },
+          {
+            "name": "requester",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionsMarket",
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "type": {
+              "defined": {
+                "name": "side"
+              }
+            }
+          },
+          {
+            "name": "optionType",
+            "type": {
+              "defined": {
+                "name": "optionType"
+              }
+            }
+          },
+          {
+            "name": "strikePrice",
+            "type": "u64"
+          },
+          {
+            "name": "expirationTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "quoteDeadline",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "unauthorizedWriter",
-      "msg": "Unauthorized writer"
-    }
-  ],
-  "types": [
-    {
-      "name": "buyerPosition",
+      "name": "rfqQuote",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "buyer",
+            "name": "rfq",
             "type": "pubkey"
           },
           {
-            "name": "optionContract",
+            "name": "maker",
             "type": "pubkey"
           },
           {
-            "name": "contractsOwned",
+            "name": "premiumPerContract",
             "type": "u64"
           },
           {
-            "name": "premiumPaid",
-            "type": "u64"
+            "name": "validUntil",
+            "type": "i64"
           },
           {
-            "name": "isExercised",
-            "type": "bool"
+            "name": "escrowAmount",
+            "type": "u64"
           },
           {
             "name": "bump",
@@ -877,115 +7972,177 @@ export type SolanaOptionsDex = {
       }
     },
     {
-      "name": "optionContract",
+      "name": "rfqQuoteAccepted",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "writer",
+            "name": "rfq",
             "type": "pubkey"
           },
           {
-            "name": "underlyingMint",
+            "name": "rfqQuote",
             "type": "pubkey"
           },
           {
-            "name": "quoteMint",
+            "name": "optionContract",
             "type": "pubkey"
           },
           {
-            "name": "optionType",
-            "type": {
-              "defined": {
-                "name": "optionType"
-              }
-            }
+            "name": "writer",
+            "type": "pubkey"
           },
           {
-            "name": "strikePrice",
+            "name": "buyer",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "expirationTimestamp",
-            "type": "i64"
+            "name": "premiumPerContract",
+            "type": "u64"
           },
           {
-            "name": "amount",
+            "name": "totalPremium",
             "type": "u64"
           },
           {
-            "name": "premiumPerContract",
+            "name": "protocolFee",
             "type": "u64"
           },
           {
-            "name": "contractsSold",
+            "name": "collateralAmount",
             "type": "u64"
           },
           {
-            "name": "isExercised",
-            "type": "bool"
+            "name": "escrowRefund",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rfqQuoteCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rfq",
+            "type": "pubkey"
           },
           {
-            "name": "isExpired",
-            "type": "bool"
+            "name": "rfqQuote",
+            "type": "pubkey"
           },
           {
-            "name": "creationTimestamp",
-            "type": "i64"
+            "name": "maker",
+            "type": "pubkey"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "refund",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "optionType",
+      "name": "rfqQuoteSubmitted",
       "type": {
-        "kind": "enum",
-        "variants": [
+        "kind": "struct",
+        "fields": [
           {
-            "name": "call"
+            "name": "rfq",
+            "type": "pubkey"
           },
           {
-            "name": "put"
+            "name": "rfqQuote",
+            "type": "pubkey"
+          },
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "premiumPerContract",
+            "type": "u64"
+          },
+          {
+            "name": "validUntil",
+            "type": "i64"
+          },
+          {
+            "name": "escrowAmount",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "optionsMarket",
+      "name": "unsoldCancelled",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "marketId",
-            "type": "u64"
+            "name": "optionContract",
+            "type": "pubkey"
           },
           {
-            "name": "underlyingMint",
+            "name": "writer",
             "type": "pubkey"
           },
           {
-            "name": "quoteMint",
+            "name": "contractsCancelled",
+            "type": "u64"
+          },
+          {
+            "name": "collateralReturned",
+            "type": "u64"
+          },
+          {
+            "name": "remainingAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "unsoldOptionsMinted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
             "type": "pubkey"
           },
           {
-            "name": "authority",
+            "name": "writer",
             "type": "pubkey"
           },
           {
-            "name": "totalOptionsWritten",
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "totalVolume",
+            "name": "contractsSold",
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "writerAccount",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "writer",
+            "type": "pubkey"
           },
           {
-            "name": "isActive",
-            "type": "bool"
+            "name": "nextNonce",
+            "type": "u64"
           },
           {
             "name": "bump",
@@ -995,40 +8152,174 @@ export type SolanaOptionsDex = {
       }
     },
     {
-      "name": "protocolState",
+      "name": "fillEvent",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "authority",
+            "name": "maker",
             "type": "pubkey"
           },
           {
-            "name": "protocolFeeRate",
+            "name": "orderId",
             "type": "u64"
           },
           {
-            "name": "settlementFeeRate",
+            "name": "makerSide",
+            "type": {
+              "defined": {
+                "name": "side"
+              }
+            }
+          },
+          {
+            "name": "quantity",
             "type": "u64"
           },
           {
-            "name": "liquidationFeeRate",
+            "name": "quoteAmount",
             "type": "u64"
           },
           {
-            "name": "totalVolume",
+            "name": "quoteRefund",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "order",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderId",
             "type": "u64"
           },
           {
-            "name": "totalFeesCollected",
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "quantity",
+            "type": "u64"
+          },
+          {
+            "name": "lockedQuote",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderBook",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "optionContract",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "quoteMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "underlyingScale",
+            "type": "u64"
+          },
+          {
+            "name": "nextOrderId",
             "type": "u64"
           },
+          {
+            "name": "bids",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "order"
+                }
+              }
+            }
+          },
+          {
+            "name": "asks",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "order"
+                }
+              }
+            }
+          },
+          {
+            "name": "events",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "fillEvent"
+                }
+              }
+            }
+          },
+          {
+            "name": "parked",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "parkedFill"
+                }
+              }
+            }
+          },
           {
             "name": "bump",
             "type": "u8"
           }
         ]
       }
+    },
+    {
+      "name": "parkedFill",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "maker",
+            "type": "pubkey"
+          },
+          {
+            "name": "optionAmount",
+            "type": "u64"
+          },
+          {
+            "name": "quoteAmount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "side",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "bid"
+          },
+          {
+            "name": "ask"
+          }
+        ]
+      }
     }
   ]
 };
@@ -48,23 +48,37 @@ export class OptionsDeXClient {
     );
   }
 
+  // Get Writer Account PDA (tracks the writer's next contract nonce)
+  getWriterAccountPDA(writer: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('writer_account'), writer.toBuffer()],
+      PROGRAM_ID
+    );
+  }
+
   // Get Option Contract PDA
-  getOptionContractPDA(
-    writer: PublicKey,
-    underlyingMint: PublicKey,
-    timestamp: BN
-  ): [PublicKey, number] {
+  getOptionContractPDA(writer: PublicKey, nonce: BN): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [
         Buffer.from('option_contract'),
         writer.toBuffer(),
-        underlyingMint.toBuffer(),
-        timestamp.toArrayLike(Buffer, 'le', 8)
+        nonce.toArrayLike(Buffer, 'le', 8)
       ],
       PROGRAM_ID
     );
   }
 
+  // Get the Option Contract PDA the writer's next write will create
+  async getNextOptionContractPDA(writer: PublicKey): Promise<PublicKey> {
+    if (!this.program) {
+      throw new Error('Program not deployed');
+    }
+    const [writerAccount] = this.getWriterAccountPDA(writer);
+    const writerData = await this.program.account.writerAccount.fetchNullable(writerAccount);
+    const nonce = writerData ? (writerData.nextNonce as BN) : new BN(0);
+    return this.getOptionContractPDA(writer, nonce)[0];
+  }
+
   // Get Buyer Position PDA
   getBuyerPositionPDA(
     buyer: PublicKey,
@@ -88,10 +102,34 @@ export class OptionsDeXClient {
     );
   }
 
-  // Get Quote Collateral Vault PDA
-  getQuoteCollateralVaultPDA(optionContract: PublicKey): [PublicKey, number] {
+  // Get Proceeds Vault PDA (call premiums and exercise payments)
+  getProceedsVaultPDA(optionContract: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('proceeds'), optionContract.toBuffer()],
+      PROGRAM_ID
+    );
+  }
+
+  // Get Option Mint PDA
+  getOptionMintPDA(optionContract: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('option_mint'), optionContract.toBuffer()],
+      PROGRAM_ID
+    );
+  }
+
+  // Get Fee Vault PDA (one per quote mint)
+  getFeeVaultPDA(quoteMint: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from('quote_collateral'), optionContract.toBuffer()],
+      [Buffer.from('fee_vault'), quoteMint.toBuffer()],
+      PROGRAM_ID
+    );
+  }
+
+  // Get Fee Stats PDA (one per quote mint)
+  getFeeStatsPDA(quoteMint: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('fee_stats'), quoteMint.toBuffer()],
       PROGRAM_ID
     );
   }
@@ -144,15 +182,22 @@ export class OptionsDeXClient {
     }
 
     const [optionsMarket] = this.getOptionsMarketPDA(marketId);
+    const [protocolState] = this.getProtocolStatePDA();
+    const [feeVault] = this.getFeeVaultPDA(quoteMint);
+    const [feeStats] = this.getFeeStatsPDA(quoteMint);
 
     try {
       const tx = await this.program.methods
         .createOptionsMarket(marketId)
-        .accounts({
+        .accountsPartial({
           optionsMarket,
           underlyingMint,
           quoteMint,
+          protocolState,
+          feeVault,
+          feeStats,
           authority: this.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -166,7 +211,6 @@ export class OptionsDeXClient {
 
   // Write Option - Ready for deployment
   async writeOption(params: {
-    timestamp: BN;
     optionType: { call: {} } | { put: {} };
     strikePrice: BN;
     expirationTimestamp: BN;
@@ -186,31 +230,36 @@ export class OptionsDeXClient {
       throw new Error('🚀 Ready to deploy! Deploy the program to devnet first:\n\nanchor deploy --provider.cluster devnet\n\nThen update the PROGRAM_ID in src/utils/program.ts');
     }
 
-    const [optionContract] = this.getOptionContractPDA(
-      this.wallet.publicKey,
-      params.underlyingMint,
-      params.timestamp
-    );
+    const optionContract = await this.getNextOptionContractPDA(this.wallet.publicKey);
     const [collateralVault] = this.getCollateralVaultPDA(optionContract);
-    const [quoteCollateralVault] = this.getQuoteCollateralVaultPDA(optionContract);
+    const [optionMint] = this.getOptionMintPDA(optionContract);
+    const [writerMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from('writer_mint'), optionContract.toBuffer()],
+      PROGRAM_ID
+    );
+    const isCall = 'call' in params.optionType;
 
     try {
       const tx = await this.program.methods
         .writeOption(
-          params.timestamp,
           params.optionType,
           params.strikePrice,
           params.expirationTimestamp,
           params.amount,
           params.premiumPerContract
         )
-        .accounts({
+        .accountsPartial({
+          writerAccount: this.getWriterAccountPDA(this.wallet.publicKey)[0],
           optionContract,
           optionsMarket: params.optionsMarket,
+          protocolState: this.getProtocolStatePDA()[0],
           underlyingMint: params.underlyingMint,
           quoteMint: params.quoteMint,
+          collateralMint: isCall ? params.underlyingMint : params.quoteMint,
           collateralVault,
-          quoteCollateralVault,
+          optionMint,
+          writerMint,
+          writerClaimTokenAccount: await getAssociatedTokenAddress(writerMint, this.wallet.publicKey),
           writerTokenAccount: params.writerTokenAccount,
           writerQuoteAccount: params.writerQuoteAccount,
           writer: this.wallet.publicKey,
@@ -230,10 +279,15 @@ export class OptionsDeXClient {
   // Buy Option - Ready for deployment
   async buyOption(params: {
     contractsToBuy: BN;
+    maxPremium: BN;
+    maxTotalCost: BN;
+    validUntil: BN;
     optionContract: PublicKey;
+    optionsMarket: PublicKey;
+    quoteMint: PublicKey;
+    proceedsMint: PublicKey; // quote mint for calls, underlying mint for puts
     buyerQuoteAccount: PublicKey;
-    writerQuoteAccount: PublicKey;
-    protocolFeeAccount: PublicKey;
+    buyerOptionTokenAccount: PublicKey;
   }) {
     if (!this.wallet?.publicKey) {
       throw new Error('Wallet not connected');
@@ -248,17 +302,32 @@ export class OptionsDeXClient {
       this.wallet.publicKey,
       params.optionContract
     );
+    const [collateralVault] = this.getCollateralVaultPDA(params.optionContract);
+    const [proceedsVault] = this.getProceedsVaultPDA(params.optionContract);
+    const [feeVault] = this.getFeeVaultPDA(params.quoteMint);
+    const [feeStats] = this.getFeeStatsPDA(params.quoteMint);
 
     try {
       const tx = await this.program.methods
-        .buyOption(params.contractsToBuy)
-        .accounts({
+        .buyOption(
+          params.contractsToBuy,
+          params.maxPremium,
+          params.maxTotalCost,
+          params.validUntil
+        )
+        .accountsPartial({
           optionContract: params.optionContract,
+          optionsMarket: params.optionsMarket,
           protocolState,
           buyerPosition,
           buyerQuoteAccount: params.buyerQuoteAccount,
-          writerQuoteAccount: params.writerQuoteAccount,
-          protocolFeeAccount: params.protocolFeeAccount,
+          collateralVault,
+          proceedsMint: params.proceedsMint,
+          proceedsVault,
+          optionMint: this.getOptionMintPDA(params.optionContract)[0],
+          buyerOptionTokenAccount: params.buyerOptionTokenAccount,
+          feeVault,
+          feeStats,
           buyer: this.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -274,12 +343,18 @@ export class OptionsDeXClient {
 
   // Exercise Option - Ready for deployment
   async exerciseOption(params: {
+    amount: BN;
+    minReceived: BN;
+    maxPaid: BN;
     optionContract: PublicKey;
+    optionsMarket: PublicKey;
+    quoteMint: PublicKey;
+    proceedsMint: PublicKey; // quote mint for calls, underlying mint for puts
     buyerTokenAccount: PublicKey;
     buyerQuoteAccount: PublicKey;
-    writerTokenAccount: PublicKey;
-    writerQuoteAccount: PublicKey;
-    protocolFeeAccount: PublicKey;
+    buyerOptionTokenAccount: PublicKey;
+    buyerPosition?: PublicKey | null;
+    pool?: PublicKey | null; // required for contracts written by the market's pool
   }) {
     if (!this.wallet?.publicKey) {
       throw new Error('Wallet not connected');
@@ -289,30 +364,33 @@ export class OptionsDeXClient {
       throw new Error('🚀 Ready to deploy! Deploy the program to devnet first:\n\nanchor deploy --provider.cluster devnet\n\nThen update the PROGRAM_ID in src/utils/program.ts');
     }
 
-    const [buyerPosition] = this.getBuyerPositionPDA(
-      this.wallet.publicKey,
-      params.optionContract
-    );
     const [protocolState] = this.getProtocolStatePDA();
     const [collateralVault] = this.getCollateralVaultPDA(params.optionContract);
-    const [quoteCollateralVault] = this.getQuoteCollateralVaultPDA(params.optionContract);
+    const [proceedsVault] = this.getProceedsVaultPDA(params.optionContract);
+    const [feeVault] = this.getFeeVaultPDA(params.quoteMint);
+    const [feeStats] = this.getFeeStatsPDA(params.quoteMint);
 
     try {
       const tx = await this.program.methods
-        .exerciseOption()
-        .accounts({
+        .exerciseOption(params.amount, params.minReceived, params.maxPaid)
+        .accountsPartial({
           optionContract: params.optionContract,
-          buyerPosition,
+          optionsMarket: params.optionsMarket,
+          buyerPosition: params.buyerPosition ?? null,
+          optionMint: this.getOptionMintPDA(params.optionContract)[0],
+          buyerOptionTokenAccount: params.buyerOptionTokenAccount,
           protocolState,
           collateralVault,
-          quoteCollateralVault,
           buyerTokenAccount: params.buyerTokenAccount,
           buyerQuoteAccount: params.buyerQuoteAccount,
-          writerTokenAccount: params.writerTokenAccount,
-          writerQuoteAccount: params.writerQuoteAccount,
-          protocolFeeAccount: params.protocolFeeAccount,
+          proceedsMint: params.proceedsMint,
+          proceedsVault,
+          feeVault,
+          feeStats,
+          pool: params.pool ?? null,
           buyer: this.wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
@@ -13,6 +13,7 @@ import {
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
   createAssociatedTokenAccountInstruction,
+  createAssociatedTokenAccountIdempotentInstruction,
   getAccount,
   TokenAccount
 } from "@solana/spl-token";
@@ -25,6 +26,7 @@ export enum OptionType {
 
 export interface OptionContractData {
   writer: PublicKey;
+  optionsMarket: PublicKey;
   underlyingMint: PublicKey;
   quoteMint: PublicKey;
   optionType: OptionType;
@@ -116,14 +118,22 @@ export class SolanaOptionsClient {
     quoteMint: PublicKey
   ): Promise<string> {
     const [optionsMarket] = this.getOptionsMarketPDA(marketId);
+    const [protocolState] = this.getProtocolStatePDA();
+    // The first market for a quote mint creates its fee vault
+    const [feeVault] = this.getFeeVaultPDA(quoteMint);
+    const [feeStats] = this.getFeeStatsPDA(quoteMint);
     
     const tx = await this.program.methods
       .createOptionsMarket(new BN(marketId))
-      .accounts({
+      .accountsPartial({
         optionsMarket,
         underlyingMint,
         quoteMint,
+        protocolState,
+        feeVault,
+        feeStats,
         authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([authority])
@@ -145,14 +155,14 @@ export class SolanaOptionsClient {
     premiumPerContract: number
   ): Promise<{ txSignature: string; optionContract: PublicKey }> {
     const [optionsMarket] = this.getOptionsMarketPDA(marketId);
-    const [optionContract] = this.getOptionContractPDA(
-      writer.publicKey,
-      underlyingMint,
-      Math.floor(Date.now() / 1000)
-    );
+    const [protocolState] = this.getProtocolStatePDA();
+    const [writerAccount] = this.getWriterAccountPDA(writer.publicKey);
+    const optionContract = await this.getNextOptionContractPDA(writer.publicKey);
     
     const [collateralVault] = this.getCollateralVaultPDA(optionContract);
-    const [quoteCollateralVault] = this.getQuoteCollateralVaultPDA(optionContract);
+    const [optionMint] = this.getOptionMintPDA(optionContract);
+    const [writerMint] = this.getWriterMintPDA(optionContract);
+    const isCall = optionType === OptionType.Call;
     
     // Get or create writer's token accounts
     const writerUnderlyingAccount = await getAssociatedTokenAddress(
@@ -164,21 +174,31 @@ export class SolanaOptionsClient {
       writer.publicKey
     );
     
+    const writerClaimTokenAccount = await getAssociatedTokenAddress(
+      writerMint,
+      writer.publicKey
+    );
+    
     const tx = await this.program.methods
       .writeOption(
-        optionType === OptionType.Call ? { call: {} } : { put: {} },
+        isCall ? { call: {} } : { put: {} },
         new BN(strikePrice),
         new BN(expirationTimestamp),
         new BN(amount),
         new BN(premiumPerContract)
       )
-      .accounts({
+      .accountsPartial({
+        writerAccount,
         optionContract,
         optionsMarket,
+        protocolState,
         underlyingMint,
         quoteMint,
+        collateralMint: isCall ? underlyingMint : quoteMint,
         collateralVault,
-        quoteCollateralVault,
+        optionMint,
+        writerMint,
+        writerClaimTokenAccount,
         writerTokenAccount: writerUnderlyingAccount,
         writerQuoteAccount,
         writer: writer.publicKey,
@@ -192,110 +212,131 @@ export class SolanaOptionsClient {
     return { txSignature: tx, optionContract };
   }
   
-  // Buy an option contract
+  // Buy an option contract. The limits guard against a repriced or auctioned premium.
   async buyOption(
     buyer: Keypair,
     optionContract: PublicKey,
     contractsToBuy: number,
-    quoteMint: PublicKey
+    maxPremium: number, // per whole underlying token
+    maxTotalCost: number, // premium plus protocol fee
+    validUntil: number = Math.floor(Date.now() / 1000) + 60
   ): Promise<{ txSignature: string; buyerPosition: PublicKey }> {
     const [protocolState] = this.getProtocolStatePDA();
     const [buyerPosition] = this.getBuyerPositionPDA(buyer.publicKey, optionContract);
+    const [collateralVault] = this.getCollateralVaultPDA(optionContract);
+    const [proceedsVault] = this.getProceedsVaultPDA(optionContract);
+    const [optionMint] = this.getOptionMintPDA(optionContract);
+    
+    const optionData = await this.program.account.optionContract.fetch(optionContract);
+    const [feeVault] = this.getFeeVaultPDA(optionData.quoteMint);
+    const [feeStats] = this.getFeeStatsPDA(optionData.quoteMint);
     
     // Get buyer's token accounts
     const buyerQuoteAccount = await getAssociatedTokenAddress(
-      quoteMint,
+      optionData.quoteMint,
       buyer.publicKey
     );
-    
-    // Get option contract data to find writer
-    const optionData = await this.getOptionContract(optionContract);
-    const writerQuoteAccount = await getAssociatedTokenAddress(
-      quoteMint,
-      optionData.writer
-    );
-    
-    // Get protocol fee account
-    const protocolStateData = await this.getProtocolState();
-    const protocolFeeAccount = await getAssociatedTokenAddress(
-      quoteMint,
-      protocolStateData.authority
+    const buyerOptionTokenAccount = await getAssociatedTokenAddress(
+      optionMint,
+      buyer.publicKey
     );
     
     const tx = await this.program.methods
-      .buyOption(new BN(contractsToBuy))
-      .accounts({
+      .buyOption(
+        new BN(contractsToBuy),
+        new BN(maxPremium),
+        new BN(maxTotalCost),
+        new BN(validUntil)
+      )
+      .accountsPartial({
         optionContract,
+        optionsMarket: optionData.optionsMarket,
         protocolState,
         buyerPosition,
         buyerQuoteAccount,
-        writerQuoteAccount,
-        protocolFeeAccount,
+        collateralVault,
+        // The proceeds vault is created by the first buy or exercise
+        proceedsMint: optionData.optionType.call ? optionData.quoteMint : optionData.underlyingMint,
+        proceedsVault,
+        optionMint,
+        buyerOptionTokenAccount,
+        feeVault,
+        feeStats,
         buyer: buyer.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
+      .preInstructions([
+        createAssociatedTokenAccountIdempotentInstruction(
+          buyer.publicKey,
+          buyerOptionTokenAccount,
+          buyer.publicKey,
+          optionMint
+        ),
+      ])
       .signers([buyer])
       .rpc();
     
     return { txSignature: tx, buyerPosition };
   }
   
-  // Exercise an option
+  // Exercise option tokens held by the buyer
   async exerciseOption(
     buyer: Keypair,
     optionContract: PublicKey,
-    underlyingMint: PublicKey,
-    quoteMint: PublicKey
+    amount: number,
+    minReceived: number = 0,
+    maxPaid: number = Number.MAX_SAFE_INTEGER // strike payment plus settlement fee, calls only
   ): Promise<string> {
     const [protocolState] = this.getProtocolStatePDA();
     const [buyerPosition] = this.getBuyerPositionPDA(buyer.publicKey, optionContract);
     const [collateralVault] = this.getCollateralVaultPDA(optionContract);
-    const [quoteCollateralVault] = this.getQuoteCollateralVaultPDA(optionContract);
+    const [proceedsVault] = this.getProceedsVaultPDA(optionContract);
+    const [optionMint] = this.getOptionMintPDA(optionContract);
+    
+    const optionData = await this.program.account.optionContract.fetch(optionContract);
+    const marketData = await this.program.account.optionsMarket.fetch(optionData.optionsMarket);
+    const [feeVault] = this.getFeeVaultPDA(optionData.quoteMint);
+    const [feeStats] = this.getFeeStatsPDA(optionData.quoteMint);
     
     // Get token accounts
     const buyerUnderlyingAccount = await getAssociatedTokenAddress(
-      underlyingMint,
+      optionData.underlyingMint,
       buyer.publicKey
     );
     const buyerQuoteAccount = await getAssociatedTokenAddress(
-      quoteMint,
+      optionData.quoteMint,
       buyer.publicKey
     );
-    
-    // Get option contract data to find writer
-    const optionData = await this.getOptionContract(optionContract);
-    const writerUnderlyingAccount = await getAssociatedTokenAddress(
-      underlyingMint,
-      optionData.writer
-    );
-    const writerQuoteAccount = await getAssociatedTokenAddress(
-      quoteMint,
-      optionData.writer
+    const buyerOptionTokenAccount = await getAssociatedTokenAddress(
+      optionMint,
+      buyer.publicKey
     );
     
-    // Get protocol fee account
-    const protocolStateData = await this.getProtocolState();
-    const protocolFeeAccount = await getAssociatedTokenAddress(
-      quoteMint,
-      protocolStateData.authority
-    );
+    // The position is optional; holders who bought on the book or by transfer have none
+    const hasPosition = (await this.provider.connection.getAccountInfo(buyerPosition)) !== null;
     
     const tx = await this.program.methods
-      .exerciseOption()
-      .accounts({
+      .exerciseOption(new BN(amount), new BN(minReceived), new BN(maxPaid))
+      .accountsPartial({
         optionContract,
-        buyerPosition,
+        optionsMarket: optionData.optionsMarket,
+        buyerPosition: hasPosition ? buyerPosition : null,
+        optionMint,
+        buyerOptionTokenAccount,
         protocolState,
         collateralVault,
-        quoteCollateralVault,
         buyerTokenAccount: buyerUnderlyingAccount,
         buyerQuoteAccount,
-        writerTokenAccount: writerUnderlyingAccount,
-        writerQuoteAccount,
-        protocolFeeAccount,
+        proceedsMint: optionData.optionType.call ? optionData.quoteMint : optionData.underlyingMint,
+        proceedsVault,
+        feeVault,
+        feeStats,
+        // Contracts written by the market's pool also update its locked totals
+        pool: optionData.writer.equals(marketData.pool) ? marketData.pool : null,
         buyer: buyer.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([buyer])
       .rpc();
@@ -303,31 +344,41 @@ export class SolanaOptionsClient {
     return tx;
   }
   
-  // Claim expired option (for writers)
+  // Redeem writer tokens after expiry for a share of the collateral and proceeds
   async claimExpiredOption(
     writer: Keypair,
     optionContract: PublicKey,
-    underlyingMint: PublicKey,
-    quoteMint: PublicKey
+    amount: number
   ): Promise<string> {
     const [collateralVault] = this.getCollateralVaultPDA(optionContract);
-    const [quoteCollateralVault] = this.getQuoteCollateralVaultPDA(optionContract);
+    const [proceedsVault] = this.getProceedsVaultPDA(optionContract);
+    const [writerMint] = this.getWriterMintPDA(optionContract);
+    const [protocolState] = this.getProtocolStatePDA();
     
+    const optionData = await this.program.account.optionContract.fetch(optionContract);
+    const writerClaimTokenAccount = await getAssociatedTokenAddress(
+      writerMint,
+      writer.publicKey
+    );
     const writerUnderlyingAccount = await getAssociatedTokenAddress(
-      underlyingMint,
+      optionData.underlyingMint,
       writer.publicKey
     );
     const writerQuoteAccount = await getAssociatedTokenAddress(
-      quoteMint,
+      optionData.quoteMint,
       writer.publicKey
     );
     
     const tx = await this.program.methods
-      .claimExpiredOption()
-      .accounts({
+      .claimExpiredOption(new BN(amount))
+      .accountsPartial({
         optionContract,
+        optionsMarket: optionData.optionsMarket,
+        protocolState,
         collateralVault,
-        quoteCollateralVault,
+        proceedsVault,
+        writerMint,
+        writerClaimTokenAccount,
         writerTokenAccount: writerUnderlyingAccount,
         writerQuoteAccount,
         writer: writer.publicKey,
@@ -410,22 +461,33 @@ export class SolanaOptionsClient {
     );
   }
   
-  getOptionContractPDA(
-    writer: PublicKey,
-    underlyingMint: PublicKey,
-    timestamp: number
-  ): [PublicKey, number] {
+  getWriterAccountPDA(writer: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("writer_account"), writer.toBuffer()],
+      this.program.programId
+    );
+  }
+  
+  // Contracts are seeded by the writer's nonce, so their addresses never collide
+  getOptionContractPDA(writer: PublicKey, nonce: BN): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [
         Buffer.from("option_contract"),
         writer.toBuffer(),
-        underlyingMint.toBuffer(),
-        new BN(timestamp).toArrayLike(Buffer, "le", 8)
+        nonce.toArrayLike(Buffer, "le", 8)
       ],
       this.program.programId
     );
   }
   
+  // The contract the writer's next write_option will create
+  async getNextOptionContractPDA(writer: PublicKey): Promise<PublicKey> {
+    const [writerAccount] = this.getWriterAccountPDA(writer);
+    const writerData = await this.program.account.writerAccount.fetchNullable(writerAccount);
+    const nonce = writerData ? writerData.nextNonce : new BN(0);
+    return this.getOptionContractPDA(writer, nonce)[0];
+  }
+  
   getBuyerPositionPDA(buyer: PublicKey, optionContract: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [
@@ -444,9 +506,39 @@ export class SolanaOptionsClient {
     );
   }
   
-  getQuoteCollateralVaultPDA(optionContract: PublicKey): [PublicKey, number] {
+  // Holds call premiums and exercise payments until writer token holders redeem them
+  getProceedsVaultPDA(optionContract: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proceeds"), optionContract.toBuffer()],
+      this.program.programId
+    );
+  }
+  
+  getOptionMintPDA(optionContract: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("option_mint"), optionContract.toBuffer()],
+      this.program.programId
+    );
+  }
+  
+  getWriterMintPDA(optionContract: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("writer_mint"), optionContract.toBuffer()],
+      this.program.programId
+    );
+  }
+  
+  // Protocol fees are held and tracked per quote mint
+  getFeeVaultPDA(quoteMint: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), quoteMint.toBuffer()],
+      this.program.programId
+    );
+  }
+  
+  getFeeStatsPDA(quoteMint: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from("quote_collateral"), optionContract.toBuffer()],
+      [Buffer.from("fee_stats"), quoteMint.toBuffer()],
       this.program.programId
     );
   }
@@ -1,9 +1,40 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Approve, Burn, CloseAccount, Mint, MintTo, Token, TokenAccount, Transfer};
 use std::mem::size_of;
 
+pub mod math;
+pub mod orderbook;
+
+use math::Rounding;
+use orderbook::{OrderBook, Side};
+
 declare_id!("E1TXVekuewkrgWspyhUToYeZzucutnEqyVG9eFf8WTKq");
 
+/// Upper bound for any protocol fee rate, in basis points (10%)
+pub const MAX_FEE_RATE: u64 = 1_000;
+/// Upper bound for a pool's volatility parameter, in basis points (1000%)
+pub const MAX_VOLATILITY_BPS: u64 = 100_000;
+/// Oldest a pool's reference price may be when it quotes, in seconds
+pub const MAX_REFERENCE_PRICE_AGE: i64 = 60;
+/// Shortest expiry a pool sells, in seconds. Longer than the reference price may lag, so a
+/// buyer cannot buy an option whose payoff a move since the last update already decides.
+pub const MIN_POOL_TIME_TO_EXPIRY: i64 = 2 * MAX_REFERENCE_PRICE_AGE;
+/// Widest a pool strike may sit from the reference price, in basis points (20%)
+pub const MAX_STRIKE_DEVIATION_BPS: u64 = 2_000;
+/// Most unsettled contracts a pool may have written; LP deposits and withdrawals pass them all
+pub const MAX_OPEN_POOL_CONTRACTS: u64 = 16;
+
+// Pause flags stored in `ProtocolState.pause_flags`
+pub const PAUSE_WRITE: u8 = 1 << 0;
+pub const PAUSE_BUY: u8 = 1 << 1;
+pub const PAUSE_EXERCISE: u8 = 1 << 2;
+pub const PAUSE_CLAIM: u8 = 1 << 3;
+/// Blocks new risk (writing and buying) while exercise and claims keep working
+pub const PAUSE_WITHDRAW_ONLY: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 = PAUSE_WRITE | PAUSE_BUY | PAUSE_EXERCISE | PAUSE_CLAIM | PAUSE_WITHDRAW_ONLY;
+
 #[program]
 pub mod solana_options_dex {
     use super::*;
@@ -15,8 +46,12 @@ pub mod solana_options_dex {
         settlement_fee_rate: u64,
         liquidation_fee_rate: u64,
     ) -> Result<()> {
+        validate_fee_rates(protocol_fee_rate, settlement_fee_rate, liquidation_fee_rate)?;
+
         let protocol_state = &mut ctx.accounts.protocol_state;
         protocol_state.authority = ctx.accounts.authority.key();
+        protocol_state.pending_authority = Pubkey::default();
+        protocol_state.pause_flags = 0;
         protocol_state.protocol_fee_rate = protocol_fee_rate;
         protocol_state.settlement_fee_rate = settlement_fee_rate;
         protocol_state.liquidation_fee_rate = liquidation_fee_rate;
@@ -24,6 +59,12 @@ pub mod solana_options_dex {
         protocol_state.total_fees_collected = 0;
         protocol_state.bump = ctx.bumps.protocol_state;
 
+        emit!(ProtocolInitialized {
+            authority: protocol_state.authority,
+            protocol_fee_rate,
+            settlement_fee_rate,
+            liquidation_fee_rate,
+        });
         msg!("Protocol initialized with fee rates: {}%, {}%, {}%", 
              protocol_fee_rate as f64 / 100.0,
              settlement_fee_rate as f64 / 100.0,
@@ -32,6 +73,113 @@ pub mod solana_options_dex {
         Ok(())
     }
 
+    /// Update the protocol fee rates (authority only)
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        protocol_fee_rate: u64,
+        settlement_fee_rate: u64,
+        liquidation_fee_rate: u64,
+    ) -> Result<()> {
+        validate_fee_rates(protocol_fee_rate, settlement_fee_rate, liquidation_fee_rate)?;
+
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.protocol_fee_rate = protocol_fee_rate;
+        protocol_state.settlement_fee_rate = settlement_fee_rate;
+        protocol_state.liquidation_fee_rate = liquidation_fee_rate;
+
+        emit!(ProtocolConfigUpdated {
+            authority: protocol_state.authority,
+            protocol_fee_rate,
+            settlement_fee_rate,
+            liquidation_fee_rate,
+        });
+        msg!("Protocol fee rates updated: {}%, {}%, {}%",
+             protocol_fee_rate as f64 / 100.0,
+             settlement_fee_rate as f64 / 100.0,
+             liquidation_fee_rate as f64 / 100.0
+        );
+        Ok(())
+    }
+
+    /// Propose a new protocol authority (first step of the handover)
+    pub fn propose_protocol_authority(
+        ctx: Context<UpdateProtocolConfig>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.pending_authority = new_authority;
+
+        emit!(ProtocolAuthorityProposed {
+            authority: protocol_state.authority,
+            pending_authority: new_authority,
+        });
+        msg!("Protocol authority handover proposed to: {}", new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending protocol authority handover (signed by the new authority)
+    pub fn accept_protocol_authority(ctx: Context<AcceptProtocolAuthority>) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let previous_authority = protocol_state.authority;
+        protocol_state.authority = ctx.accounts.pending_authority.key();
+        protocol_state.pending_authority = Pubkey::default();
+
+        emit!(ProtocolAuthorityAccepted {
+            previous_authority,
+            authority: protocol_state.authority,
+        });
+        msg!("Protocol authority transferred to: {}", protocol_state.authority);
+        Ok(())
+    }
+
+    /// Set the protocol pause flags (authority only)
+    pub fn set_pause_flags(ctx: Context<UpdateProtocolConfig>, pause_flags: u8) -> Result<()> {
+        require!(pause_flags & !PAUSE_ALL == 0, OptionsError::InvalidPauseFlags);
+
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.pause_flags = pause_flags;
+
+        emit!(PauseFlagsSet {
+            authority: protocol_state.authority,
+            pause_flags,
+        });
+        msg!("Protocol pause flags set to: {:#07b}", pause_flags);
+        Ok(())
+    }
+
+    /// Sweep collected protocol fees for one mint to a treasury account (authority only)
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, OptionsError::InvalidAmount);
+
+        let seeds = &[b"protocol_state".as_ref(), &[ctx.accounts.protocol_state.bump]];
+        let signer = &[&seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.protocol_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, amount)?;
+
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        fee_stats.total_fees_withdrawn = fee_stats.total_fees_withdrawn
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        emit!(ProtocolFeesWithdrawn {
+            mint: fee_stats.mint,
+            treasury: ctx.accounts.treasury.key(),
+            amount,
+            total_fees_withdrawn: fee_stats.total_fees_withdrawn,
+        });
+        msg!("Withdrew {} protocol fees of mint {}", amount, fee_stats.mint);
+        Ok(())
+    }
+
     /// Create a new options market for a specific underlying token
     pub fn create_options_market(
         ctx: Context<CreateOptionsMarket>,
@@ -41,86 +189,189 @@ pub mod solana_options_dex {
         options_market.market_id = market_id;
         options_market.underlying_mint = ctx.accounts.underlying_mint.key();
         options_market.quote_mint = ctx.accounts.quote_mint.key();
+        options_market.underlying_decimals = ctx.accounts.underlying_mint.decimals;
+        options_market.quote_decimals = ctx.accounts.quote_mint.decimals;
+        options_market.underlying_scale = math::decimal_scale(ctx.accounts.underlying_mint.decimals)?;
         options_market.authority = ctx.accounts.authority.key();
         options_market.total_options_written = 0;
         options_market.total_volume = 0;
+        options_market.open_interest_written = 0;
+        options_market.open_interest_sold = 0;
+        options_market.total_contracts_exercised = 0;
+        options_market.total_contracts_expired = 0;
         options_market.is_active = true;
+        options_market.pool = Pubkey::default();
         options_market.bump = ctx.bumps.options_market;
 
+        // The first market for a quote mint sets up its fee vault
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        if fee_stats.mint == Pubkey::default() {
+            fee_stats.mint = ctx.accounts.quote_mint.key();
+            fee_stats.total_fees_collected = 0;
+            fee_stats.total_fees_withdrawn = 0;
+            fee_stats.bump = ctx.bumps.fee_stats;
+        }
+
+        emit!(MarketCreated {
+            options_market: options_market.key(),
+            market_id,
+            underlying_mint: options_market.underlying_mint,
+            quote_mint: options_market.quote_mint,
+            underlying_decimals: options_market.underlying_decimals,
+            quote_decimals: options_market.quote_decimals,
+            authority: options_market.authority,
+        });
         msg!("Options market created for underlying: {}", options_market.underlying_mint);
         Ok(())
     }
 
+    /// Activate or deactivate an options market (market authority only)
+    pub fn set_market_status(ctx: Context<SetMarketStatus>, is_active: bool) -> Result<()> {
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.is_active = is_active;
+
+        emit!(MarketStatusChanged {
+            options_market: options_market.key(),
+            market_id: options_market.market_id,
+            is_active,
+        });
+        msg!("Options market {} is now {}",
+             options_market.market_id,
+             if is_active { "active" } else { "inactive" }
+        );
+        Ok(())
+    }
+
     /// Write (sell) a new option contract
     pub fn write_option(
         ctx: Context<WriteOption>,
-        _timestamp_seed: i64,
         option_type: OptionType,
         strike_price: u64,
         expiration_timestamp: i64,
         amount: u64,
         premium_per_contract: u64,
     ) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_WRITE)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
+
         let clock = Clock::get()?;
         require!(expiration_timestamp > clock.unix_timestamp, OptionsError::ExpirationInPast);
         require!(amount > 0, OptionsError::InvalidAmount);
         require!(premium_per_contract > 0, OptionsError::InvalidPremium);
 
+        // The contract is seeded by the writer's next nonce
+        let writer_account = &mut ctx.accounts.writer_account;
+        if writer_account.writer == Pubkey::default() {
+            writer_account.writer = ctx.accounts.writer.key();
+            writer_account.bump = ctx.bumps.writer_account;
+        }
+        let nonce = writer_account.next_nonce;
+        writer_account.next_nonce = nonce
+            .checked_add(1)
+            .ok_or(OptionsError::MathOverflow)?;
+
         let option_contract = &mut ctx.accounts.option_contract;
         option_contract.writer = ctx.accounts.writer.key();
+        option_contract.options_market = ctx.accounts.options_market.key();
         option_contract.underlying_mint = ctx.accounts.underlying_mint.key();
         option_contract.quote_mint = ctx.accounts.quote_mint.key();
-        option_contract.option_type = option_type.clone();
+        option_contract.option_type = option_type;
         option_contract.strike_price = strike_price;
         option_contract.expiration_timestamp = expiration_timestamp;
         option_contract.amount = amount;
         option_contract.premium_per_contract = premium_per_contract;
         option_contract.contracts_sold = 0;
+        option_contract.contracts_exercised = 0;
         option_contract.is_exercised = false;
         option_contract.is_expired = false;
         option_contract.creation_timestamp = clock.unix_timestamp;
+        option_contract.option_mint = ctx.accounts.option_mint.key();
+        option_contract.writer_mint = ctx.accounts.writer_mint.key();
+        option_contract.nonce = nonce;
+        option_contract.auction_start_premium = 0;
+        option_contract.auction_start_timestamp = 0;
+        option_contract.auction_duration = 0;
+        option_contract.rent_payer = ctx.accounts.writer.key();
+        option_contract.held_premium = 0;
+        option_contract.proceeds_rent_payer = Pubkey::default();
         option_contract.bump = ctx.bumps.option_contract;
 
-        // Transfer collateral based on option type
-        match option_type {
-            OptionType::Call => {
-                // For calls, lock underlying tokens as collateral
-                let transfer_ctx = CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.writer_token_account.to_account_info(),
-                        to: ctx.accounts.collateral_vault.to_account_info(),
-                        authority: ctx.accounts.writer.to_account_info(),
-                    },
-                );
-                token::transfer(transfer_ctx, amount)?;
-            }
-            OptionType::Put => {
-                // For puts, lock quote tokens as collateral (strike_price * amount / 1_000_000)
-                let collateral_amount = strike_price
-                    .checked_mul(amount)
-                    .ok_or(OptionsError::MathOverflow)?
-                    .checked_div(1_000_000) // Normalize decimal scaling
-                    .ok_or(OptionsError::MathOverflow)?;
-                
-                let transfer_ctx = CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.writer_quote_account.to_account_info(),
-                        to: ctx.accounts.quote_collateral_vault.to_account_info(),
-                        authority: ctx.accounts.writer.to_account_info(),
-                    },
-                );
-                token::transfer(transfer_ctx, collateral_amount)?;
-            }
-        }
+        // Lock collateral: underlying tokens for calls, quote tokens for puts
+        let collateral_amount = option_type.collateral_amount(
+            strike_price,
+            amount,
+            ctx.accounts.options_market.underlying_scale,
+        )?;
+        let collateral_source = match option_type {
+            OptionType::Call => ctx.accounts.writer_token_account.to_account_info(),
+            OptionType::Put => ctx.accounts.writer_quote_account.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: collateral_source,
+                to: ctx.accounts.collateral_vault.to_account_info(),
+                authority: ctx.accounts.writer.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, collateral_amount)?;
+
+        // Mint one writer token per contract written, signed by the option contract PDA
+        let writer_key = ctx.accounts.writer.key();
+        let nonce_bytes = nonce.to_le_bytes();
+        let seeds = &[
+            b"option_contract".as_ref(),
+            writer_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.option_contract],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                to: ctx.accounts.writer_claim_token_account.to_account_info(),
+                authority: ctx.accounts.option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, amount)?;
+
+        // Let the contract burn the writer's tokens so a keeper can settle on their behalf
+        let approve_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.writer_claim_token_account.to_account_info(),
+                delegate: ctx.accounts.option_contract.to_account_info(),
+                authority: ctx.accounts.writer.to_account_info(),
+            },
+        );
+        token::approve(approve_ctx, amount)?;
 
         // Update market stats
         let options_market = &mut ctx.accounts.options_market;
         options_market.total_options_written = options_market.total_options_written
             .checked_add(amount)
             .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_written = options_market.open_interest_written
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
 
+        emit!(OptionWritten {
+            option_contract: ctx.accounts.option_contract.key(),
+            options_market: options_market.key(),
+            writer: writer_key,
+            nonce,
+            option_type,
+            strike_price,
+            expiration_timestamp,
+            amount,
+            premium_per_contract,
+            collateral_amount,
+            option_mint: ctx.accounts.option_mint.key(),
+            writer_mint: ctx.accounts.writer_mint.key(),
+        });
         msg!("Option written: {} {} contracts at strike {} expiring at {}", 
              amount, 
              match option_type { OptionType::Call => "CALL", OptionType::Put => "PUT" },
@@ -130,80 +381,182 @@ pub mod solana_options_dex {
         Ok(())
     }
 
-    /// Buy an option contract
+    /// Reprice the unsold inventory of an option contract
+    pub fn update_premium(ctx: Context<UpdatePremium>, premium_per_contract: u64) -> Result<()> {
+        require!(premium_per_contract > 0, OptionsError::InvalidPremium);
+
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp < option_contract.expiration_timestamp, OptionsError::OptionExpired);
+
+        // A flat price replaces any running auction
+        let old_premium = option_contract.premium_per_contract;
+        option_contract.premium_per_contract = premium_per_contract;
+        option_contract.auction_duration = 0;
+
+        emit!(PremiumUpdated {
+            option_contract: option_contract.key(),
+            writer: option_contract.writer,
+            old_premium,
+            new_premium: premium_per_contract,
+        });
+        msg!("Premium updated from {} to {}", old_premium, premium_per_contract);
+        Ok(())
+    }
+
+    /// List the unsold contracts in a descending-price (Dutch) auction
+    pub fn start_dutch_auction(
+        ctx: Context<StartDutchAuction>,
+        start_premium: u64,
+        floor_premium: u64,
+        duration: i64,
+    ) -> Result<()> {
+        require!(floor_premium > 0, OptionsError::InvalidPremium);
+        require!(start_premium > floor_premium && duration > 0, OptionsError::InvalidAuction);
+
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp < option_contract.expiration_timestamp, OptionsError::OptionExpired);
+
+        // The floor is the flat premium the contract falls back to once the auction ends
+        option_contract.premium_per_contract = floor_premium;
+        option_contract.auction_start_premium = start_premium;
+        option_contract.auction_start_timestamp = clock.unix_timestamp;
+        option_contract.auction_duration = duration;
+
+        emit!(DutchAuctionStarted {
+            option_contract: option_contract.key(),
+            writer: option_contract.writer,
+            start_premium,
+            floor_premium,
+            start_timestamp: clock.unix_timestamp,
+            duration,
+        });
+        msg!("Dutch auction started from {} down to {} over {} seconds", start_premium, floor_premium, duration);
+        Ok(())
+    }
+
+    /// Buy option contracts, opening or topping up the buyer position
     pub fn buy_option(
         ctx: Context<BuyOption>,
         contracts_to_buy: u64,
+        max_premium: u64,
+        max_total_cost: u64,
+        valid_until: i64,
     ) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_BUY)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
         require!(contracts_to_buy > 0, OptionsError::InvalidAmount);
-        
+
         let option_contract = &mut ctx.accounts.option_contract;
         let clock = Clock::get()?;
         
+        require!(clock.unix_timestamp <= valid_until, OptionsError::DeadlineExceeded);
         require!(clock.unix_timestamp < option_contract.expiration_timestamp, OptionsError::OptionExpired);
         require!(!option_contract.is_exercised, OptionsError::OptionAlreadyExercised);
+        // Protect the buyer from a reprice landing just before their purchase
+        let premium_per_contract = option_contract.current_premium(clock.unix_timestamp)?;
+        require!(premium_per_contract <= max_premium, OptionsError::PremiumAboveLimit);
         
         let available_contracts = option_contract.amount
             .checked_sub(option_contract.contracts_sold)
             .ok_or(OptionsError::InsufficientContracts)?;
         require!(contracts_to_buy <= available_contracts, OptionsError::InsufficientContracts);
+        option_contract.record_proceeds_rent_payer(ctx.accounts.buyer.key());
 
-        // Calculate total premium (normalize for decimal scaling)
-        let total_premium = option_contract.premium_per_contract
-            .checked_mul(contracts_to_buy)
-            .ok_or(OptionsError::MathOverflow)?
-            .checked_div(1_000_000) // Normalize decimal scaling
-            .ok_or(OptionsError::MathOverflow)?;
-
-        // Calculate protocol fee
-        let protocol_state = &ctx.accounts.protocol_state;
-        let protocol_fee = total_premium
-            .checked_mul(protocol_state.protocol_fee_rate)
-            .ok_or(OptionsError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(OptionsError::MathOverflow)?;
+        // The protocol fee is taken out of the premium, so the premium is the buyer's total cost
+        let (total_premium, protocol_fee) = ctx.accounts.protocol_state.premium_with_fee(
+            premium_per_contract,
+            contracts_to_buy,
+            ctx.accounts.options_market.underlying_scale,
+        )?;
+        require!(total_premium <= max_total_cost, OptionsError::TotalCostAboveLimit);
 
         let net_premium = total_premium
             .checked_sub(protocol_fee)
             .ok_or(OptionsError::MathOverflow)?;
 
-        // Transfer premium from buyer to writer (minus protocol fee)
+        // The premium (minus protocol fee) belongs to whoever holds the writer tokens, so it is
+        // kept in the contract's quote-side vault until they are redeemed
+        let premium_vault = match option_contract.option_type {
+            OptionType::Call => ctx.accounts.proceeds_vault.to_account_info(),
+            OptionType::Put => ctx.accounts.collateral_vault.to_account_info(),
+        };
         let transfer_to_writer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.buyer_quote_account.to_account_info(),
-                to: ctx.accounts.writer_quote_account.to_account_info(),
+                to: premium_vault,
                 authority: ctx.accounts.buyer.to_account_info(),
             },
         );
         token::transfer(transfer_to_writer_ctx, net_premium)?;
+        if option_contract.option_type == OptionType::Put {
+            // Tracked so settlement can tell the premium apart from the collateral
+            option_contract.held_premium = option_contract.held_premium
+                .checked_add(net_premium)
+                .ok_or(OptionsError::MathOverflow)?;
+        }
 
-        // Transfer protocol fee to fee collector
+        // Transfer protocol fee to the fee vault
         if protocol_fee > 0 {
             let transfer_fee_ctx = CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
                     from: ctx.accounts.buyer_quote_account.to_account_info(),
-                    to: ctx.accounts.protocol_fee_account.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
                     authority: ctx.accounts.buyer.to_account_info(),
                 },
             );
             token::transfer(transfer_fee_ctx, protocol_fee)?;
         }
 
+        // Mint option tokens to the buyer, signed by the option contract PDA
+        let nonce = option_contract.nonce.to_le_bytes();
+        let seeds = &[
+            b"option_contract".as_ref(),
+            option_contract.writer.as_ref(),
+            nonce.as_ref(),
+            &[option_contract.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.option_mint.to_account_info(),
+                to: ctx.accounts.buyer_option_token_account.to_account_info(),
+                authority: option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, contracts_to_buy)?;
+
         // Update option contract first
         option_contract.contracts_sold = option_contract.contracts_sold
             .checked_add(contracts_to_buy)
             .ok_or(OptionsError::MathOverflow)?;
 
-        // Create buyer position
+        // Create the buyer position on first purchase, top it up on repeat purchases
         let buyer_position = &mut ctx.accounts.buyer_position;
-        buyer_position.buyer = ctx.accounts.buyer.key();
-        buyer_position.option_contract = option_contract.key();
-        buyer_position.contracts_owned = contracts_to_buy;
-        buyer_position.premium_paid = total_premium;
+        if buyer_position.buyer == Pubkey::default() {
+            buyer_position.buyer = ctx.accounts.buyer.key();
+            buyer_position.option_contract = option_contract.key();
+            buyer_position.contracts_owned = 0;
+            buyer_position.contracts_exercised = 0;
+            buyer_position.premium_paid = 0;
+            buyer_position.rent_payer = ctx.accounts.buyer.key();
+            buyer_position.bump = ctx.bumps.buyer_position;
+        }
+        buyer_position.contracts_owned = buyer_position.contracts_owned
+            .checked_add(contracts_to_buy)
+            .ok_or(OptionsError::MathOverflow)?;
+        buyer_position.premium_paid = buyer_position.premium_paid
+            .checked_add(total_premium)
+            .ok_or(OptionsError::MathOverflow)?;
         buyer_position.is_exercised = false;
-        buyer_position.bump = ctx.bumps.buyer_position;
 
         // Update protocol stats (Note: we need to get protocol_state as mutable)
         let protocol_state = &mut ctx.accounts.protocol_state;
@@ -213,40 +566,119 @@ pub mod solana_options_dex {
         protocol_state.total_fees_collected = protocol_state.total_fees_collected
             .checked_add(protocol_fee)
             .ok_or(OptionsError::MathOverflow)?;
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        fee_stats.total_fees_collected = fee_stats.total_fees_collected
+            .checked_add(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // Update market stats
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.total_volume = options_market.total_volume
+            .checked_add(total_premium)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_sold = options_market.open_interest_sold
+            .checked_add(contracts_to_buy)
+            .ok_or(OptionsError::MathOverflow)?;
 
+        emit!(OptionPurchased {
+            option_contract: option_contract.key(),
+            options_market: options_market.key(),
+            buyer: ctx.accounts.buyer.key(),
+            contracts: contracts_to_buy,
+            premium_per_contract,
+            total_premium,
+            protocol_fee,
+            contracts_sold: option_contract.contracts_sold,
+        });
         msg!("Option purchased: {} contracts for {} total premium", contracts_to_buy, total_premium);
         msg!("Protocol stats updated - Volume: {}, Fees: {}", protocol_state.total_volume, protocol_state.total_fees_collected);
         Ok(())
     }
 
-    /// Exercise an option contract
-    pub fn exercise_option(ctx: Context<ExerciseOption>) -> Result<()> {
-        let buyer_position = &mut ctx.accounts.buyer_position;
+    /// Exercise option tokens held by the signer, burning them
+    pub fn exercise_option(
+        ctx: Context<ExerciseOption>,
+        amount: u64,
+        min_received: u64,
+        max_paid: u64,
+    ) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_EXERCISE)?;
+
         let option_contract = &mut ctx.accounts.option_contract;
         let clock = Clock::get()?;
 
-        require!(!buyer_position.is_exercised, OptionsError::PositionAlreadyExercised);
         require!(clock.unix_timestamp <= option_contract.expiration_timestamp, OptionsError::OptionExpired);
-        require!(buyer_position.contracts_owned > 0, OptionsError::NoContractsOwned);
+        require!(amount > 0, OptionsError::InvalidAmount);
+        option_contract.record_proceeds_rent_payer(ctx.accounts.buyer.key());
+
+        // The original buyer can pass their position to keep it in sync
+        if let Some(buyer_position) = ctx.accounts.buyer_position.as_mut() {
+            require!(!buyer_position.is_exercised, OptionsError::PositionAlreadyExercised);
+            require!(buyer_position.contracts_owned > 0, OptionsError::NoContractsOwned);
+            require!(amount <= buyer_position.contracts_owned, OptionsError::ExerciseExceedsPosition);
 
-        let contracts_to_exercise = buyer_position.contracts_owned;
+            buyer_position.contracts_owned = buyer_position.contracts_owned
+                .checked_sub(amount)
+                .ok_or(OptionsError::MathOverflow)?;
+            buyer_position.contracts_exercised = buyer_position.contracts_exercised
+                .checked_add(amount)
+                .ok_or(OptionsError::MathOverflow)?;
+            buyer_position.is_exercised = buyer_position.contracts_owned == 0;
+        }
+
+        let contracts_to_exercise = amount;
         let option_contract_key = option_contract.key();
 
-        match option_contract.option_type {
+        // Settlement fee is charged in quote tokens on the strike value (rounded down)
+        let settlement_value = math::quote_amount(
+            option_contract.strike_price,
+            contracts_to_exercise,
+            ctx.accounts.options_market.underlying_scale,
+            Rounding::Down,
+        )?;
+        let settlement_fee = math::fee_amount(settlement_value, ctx.accounts.protocol_state.settlement_fee_rate)?;
+
+        // Calls pay out underlying; puts pay out the strike value, net of the fee
+        let amount_received = match option_contract.option_type {
+            OptionType::Call => contracts_to_exercise,
+            OptionType::Put => settlement_value
+                .checked_sub(settlement_fee)
+                .ok_or(OptionsError::MathOverflow)?,
+        };
+        require!(amount_received >= min_received, OptionsError::ReceivedBelowMinimum);
+
+        // Burn the exercised option tokens from the holder
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.option_mint.to_account_info(),
+                from: ctx.accounts.buyer_option_token_account.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, contracts_to_exercise)?;
+
+        let quote_amount = match option_contract.option_type {
             OptionType::Call => {
-                // For calls: buyer pays strike price, receives underlying tokens
-                let payment_amount = option_contract.strike_price
-                    .checked_mul(contracts_to_exercise)
-                    .ok_or(OptionsError::MathOverflow)?
-                    .checked_div(1_000_000) // Normalize decimal scaling
+                // For calls: buyer pays strike price (rounded up), receives underlying tokens
+                let payment_amount = math::quote_amount(
+                    option_contract.strike_price,
+                    contracts_to_exercise,
+                    ctx.accounts.options_market.underlying_scale,
+                    Rounding::Up,
+                )?;
+                // Protect the holder from paying more than they signed for, fee included
+                let total_paid = payment_amount
+                    .checked_add(settlement_fee)
                     .ok_or(OptionsError::MathOverflow)?;
+                require!(total_paid <= max_paid, OptionsError::PaymentAboveLimit);
 
-                // Transfer payment from buyer to writer
+                // Transfer payment from buyer to the proceeds vault
                 let transfer_payment_ctx = CpiContext::new(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
                         from: ctx.accounts.buyer_quote_account.to_account_info(),
-                        to: ctx.accounts.writer_quote_account.to_account_info(),
+                        to: ctx.accounts.proceeds_vault.to_account_info(),
                         authority: ctx.accounts.buyer.to_account_info(),
                     },
                 );
@@ -270,21 +702,16 @@ pub mod solana_options_dex {
                     signer,
                 );
                 token::transfer(transfer_underlying_ctx, contracts_to_exercise)?;
+                payment_amount
             }
             OptionType::Put => {
-                // For puts: buyer provides underlying tokens, receives quote tokens
-                let quote_amount = option_contract.strike_price
-                    .checked_mul(contracts_to_exercise)
-                    .ok_or(OptionsError::MathOverflow)?
-                    .checked_div(1_000_000) // Normalize decimal scaling
-                    .ok_or(OptionsError::MathOverflow)?;
-
-                // Transfer underlying tokens from buyer to writer
+                // For puts: buyer provides underlying tokens, receives quote tokens (rounded down)
+                // Transfer underlying tokens from buyer to the proceeds vault
                 let transfer_underlying_ctx = CpiContext::new(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
                         from: ctx.accounts.buyer_token_account.to_account_info(),
-                        to: ctx.accounts.writer_token_account.to_account_info(),
+                        to: ctx.accounts.proceeds_vault.to_account_info(),
                         authority: ctx.accounts.buyer.to_account_info(),
                     },
                 );
@@ -292,284 +719,4056 @@ pub mod solana_options_dex {
 
                 // Transfer quote tokens from collateral vault to buyer
                 let seeds = &[
-                    b"quote_collateral",
+                    b"collateral",
                     option_contract_key.as_ref(),
-                    &[ctx.bumps.quote_collateral_vault],
+                    &[ctx.bumps.collateral_vault],
                 ];
                 let signer = &[&seeds[..]];
 
                 let transfer_quote_ctx = CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
-                        from: ctx.accounts.quote_collateral_vault.to_account_info(),
+                        from: ctx.accounts.collateral_vault.to_account_info(),
                         to: ctx.accounts.buyer_quote_account.to_account_info(),
-                        authority: ctx.accounts.quote_collateral_vault.to_account_info(),
+                        authority: ctx.accounts.collateral_vault.to_account_info(),
                     },
                     signer,
                 );
-                token::transfer(transfer_quote_ctx, quote_amount)?;
+                token::transfer(transfer_quote_ctx, settlement_value)?;
+                settlement_value
             }
-        }
-
-        // Calculate and collect settlement fee
-        let protocol_state = &ctx.accounts.protocol_state;
-        let settlement_value = match option_contract.option_type {
-            OptionType::Call => option_contract.strike_price.checked_mul(contracts_to_exercise).ok_or(OptionsError::MathOverflow)?.checked_div(1_000_000).ok_or(OptionsError::MathOverflow)?,
-            OptionType::Put => option_contract.strike_price.checked_mul(contracts_to_exercise).ok_or(OptionsError::MathOverflow)?.checked_div(1_000_000).ok_or(OptionsError::MathOverflow)?,
         };
 
-        let settlement_fee = settlement_value
-            .checked_mul(protocol_state.settlement_fee_rate)
-            .ok_or(OptionsError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(OptionsError::MathOverflow)?;
-
+        // Collect settlement fee
         if settlement_fee > 0 {
             let transfer_fee_ctx = CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
                     from: ctx.accounts.buyer_quote_account.to_account_info(),
-                    to: ctx.accounts.protocol_fee_account.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
                     authority: ctx.accounts.buyer.to_account_info(),
                 },
             );
             token::transfer(transfer_fee_ctx, settlement_fee)?;
         }
 
-        // Mark position as exercised
-        buyer_position.is_exercised = true;
-        
-        // Update option contract
-        option_contract.is_exercised = true;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.total_fees_collected = protocol_state.total_fees_collected
+            .checked_add(settlement_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        fee_stats.total_fees_collected = fee_stats.total_fees_collected
+            .checked_add(settlement_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // Update option contract; it is fully exercised once every written contract is
+        option_contract.contracts_exercised = option_contract.contracts_exercised
+            .checked_add(contracts_to_exercise)
+            .ok_or(OptionsError::MathOverflow)?;
+        option_contract.is_exercised = option_contract.contracts_exercised == option_contract.amount;
+
+        // Update market stats
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.open_interest_written = options_market.open_interest_written
+            .checked_sub(contracts_to_exercise)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_sold = options_market.open_interest_sold
+            .checked_sub(contracts_to_exercise)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // A pool-written contract's vaults count toward the pool's assets, so keep its totals in step
+        if option_contract.writer == options_market.pool {
+            let pool = ctx.accounts.pool.as_mut().ok_or(OptionsError::PoolRequired)?;
+            pool.record_exercise(option_contract.option_type, contracts_to_exercise, quote_amount)?;
+        }
+
+        options_market.total_contracts_exercised = options_market.total_contracts_exercised
+            .checked_add(contracts_to_exercise)
+            .ok_or(OptionsError::MathOverflow)?;
 
+        emit!(OptionExercised {
+            option_contract: option_contract_key,
+            options_market: options_market.key(),
+            holder: ctx.accounts.buyer.key(),
+            option_type: option_contract.option_type,
+            contracts: contracts_to_exercise,
+            quote_amount,
+            settlement_fee,
+            contracts_exercised: option_contract.contracts_exercised,
+        });
         msg!("Option exercised: {} contracts", contracts_to_exercise);
         Ok(())
     }
 
-    /// Claim expired options (liquidation)
-    pub fn claim_expired_option(ctx: Context<ClaimExpiredOption>) -> Result<()> {
+    /// Burn writer tokens for a pro-rata share of the collateral and proceeds left after expiry
+    pub fn claim_expired_option(ctx: Context<ClaimExpiredOption>, amount: u64) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_CLAIM)?;
+
         let option_contract = &mut ctx.accounts.option_contract;
         let clock = Clock::get()?;
 
         require!(clock.unix_timestamp > option_contract.expiration_timestamp, OptionsError::OptionNotExpired);
-        require!(!option_contract.is_exercised, OptionsError::OptionAlreadyExercised);
-        require!(!option_contract.is_expired, OptionsError::OptionAlreadyClaimed);
+        require!(amount > 0, OptionsError::InvalidAmount);
+        require!(amount <= ctx.accounts.writer_mint.supply, OptionsError::InvalidAmount);
+
+        // The first claim after expiry settles the contract
+        option_contract.record_expiry(&mut ctx.accounts.options_market)?;
 
         let option_contract_key = option_contract.key();
-        
-        // Return collateral to writer
-        let remaining_contracts = option_contract.amount
-            .checked_sub(option_contract.contracts_sold)
+        let writer_token_supply = ctx.accounts.writer_mint.supply;
+
+        // Nothing can be exercised after expiry, so both vaults are shared among writer token holders
+        let collateral_amount = math::pro_rata_share(
+            ctx.accounts.collateral_vault.amount,
+            amount,
+            writer_token_supply,
+        )?;
+        let proceeds_amount = math::pro_rata_share(
+            lazy_vault_amount(&ctx.accounts.proceeds_vault)?,
+            amount,
+            writer_token_supply,
+        )?;
+        let premium_share = math::pro_rata_share(option_contract.held_premium, amount, writer_token_supply)?;
+        option_contract.held_premium = option_contract.held_premium
+            .checked_sub(premium_share)
             .ok_or(OptionsError::MathOverflow)?;
 
-        match option_contract.option_type {
-            OptionType::Call => {
-                // Return unsold underlying tokens to writer
-                if remaining_contracts > 0 {
-                    let seeds = &[
-                        b"collateral",
-                        option_contract_key.as_ref(),
-                        &[ctx.bumps.collateral_vault],
-                    ];
-                    let signer = &[&seeds[..]];
+        // Calls hold underlying collateral and quote proceeds, puts the other way round
+        let (collateral_destination, proceeds_destination) = match option_contract.option_type {
+            OptionType::Call => (
+                ctx.accounts.writer_token_account.to_account_info(),
+                ctx.accounts.writer_quote_account.to_account_info(),
+            ),
+            OptionType::Put => (
+                ctx.accounts.writer_quote_account.to_account_info(),
+                ctx.accounts.writer_token_account.to_account_info(),
+            ),
+        };
 
-                    let transfer_ctx = CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.collateral_vault.to_account_info(),
-                            to: ctx.accounts.writer_token_account.to_account_info(),
-                            authority: ctx.accounts.collateral_vault.to_account_info(),
-                        },
-                        signer,
-                    );
-                    token::transfer(transfer_ctx, remaining_contracts)?;
-                }
-            }
-            OptionType::Put => {
-                // Return unsold quote token collateral to writer
-                if remaining_contracts > 0 {
-                    let collateral_amount = option_contract.strike_price
-                        .checked_mul(remaining_contracts)
-                        .ok_or(OptionsError::MathOverflow)?
-                        .checked_div(1_000_000) // Normalize decimal scaling
-                        .ok_or(OptionsError::MathOverflow)?;
-
-                    let seeds = &[
-                        b"quote_collateral",
-                        option_contract_key.as_ref(),
-                        &[ctx.bumps.quote_collateral_vault],
-                    ];
-                    let signer = &[&seeds[..]];
+        if collateral_amount > 0 {
+            let seeds = &[
+                b"collateral",
+                option_contract_key.as_ref(),
+                &[ctx.bumps.collateral_vault],
+            ];
+            let signer = &[&seeds[..]];
 
-                    let transfer_ctx = CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.quote_collateral_vault.to_account_info(),
-                            to: ctx.accounts.writer_quote_account.to_account_info(),
-                            authority: ctx.accounts.quote_collateral_vault.to_account_info(),
-                        },
-                        signer,
-                    );
-                    token::transfer(transfer_ctx, collateral_amount)?;
-                }
-            }
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: collateral_destination,
+                    authority: ctx.accounts.collateral_vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, collateral_amount)?;
+        }
+
+        if proceeds_amount > 0 {
+            let seeds = &[
+                b"proceeds",
+                option_contract_key.as_ref(),
+                &[ctx.bumps.proceeds_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.proceeds_vault.to_account_info(),
+                    to: proceeds_destination,
+                    authority: ctx.accounts.proceeds_vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, proceeds_amount)?;
         }
 
-        // Mark as expired
-        option_contract.is_expired = true;
+        // Burn the redeemed writer tokens
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                from: ctx.accounts.writer_claim_token_account.to_account_info(),
+                authority: ctx.accounts.writer.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, amount)?;
 
-        msg!("Expired option claimed, collateral returned to writer");
+        emit!(OptionExpiredClaimed {
+            option_contract: option_contract_key,
+            options_market: option_contract.options_market,
+            holder: ctx.accounts.writer.key(),
+            writer_tokens_burned: amount,
+            collateral_amount,
+            proceeds_amount,
+        });
+        msg!("Expired option claimed: {} writer tokens redeemed", amount);
         Ok(())
     }
-}
 
-// Data structures
-#[account]
-pub struct ProtocolState {
-    pub authority: Pubkey,
-    pub protocol_fee_rate: u64,    // basis points
-    pub settlement_fee_rate: u64,  // basis points
-    pub liquidation_fee_rate: u64, // basis points
-    pub total_volume: u64,
-    pub total_fees_collected: u64,
-    pub bump: u8,
-}
+    /// Cancel unsold contracts before expiry and return their collateral to the writer
+    pub fn cancel_unsold(ctx: Context<CancelUnsold>, contracts_to_cancel: u64) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_CLAIM)?;
 
-#[account]
-pub struct OptionsMarket {
-    pub market_id: u64,
-    pub underlying_mint: Pubkey,
-    pub quote_mint: Pubkey,
-    pub authority: Pubkey,
-    pub total_options_written: u64,
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp < option_contract.expiration_timestamp, OptionsError::OptionExpired);
+        require!(contracts_to_cancel > 0, OptionsError::InvalidAmount);
+
+        let unsold_contracts = option_contract.amount
+            .checked_sub(option_contract.contracts_sold)
+            .ok_or(OptionsError::MathOverflow)?;
+        require!(contracts_to_cancel <= unsold_contracts, OptionsError::InsufficientContracts);
+
+        let remaining_amount = option_contract.amount - contracts_to_cancel;
+        let option_contract_key = option_contract.key();
+
+        let (collateral_amount, destination) = match option_contract.option_type {
+            OptionType::Call => (
+                contracts_to_cancel,
+                ctx.accounts.writer_token_account.to_account_info(),
+            ),
+            OptionType::Put => {
+                // Release the difference of the rounded-up requirements so the
+                // remaining contracts stay exactly as collateralized as at write time
+                let scale = ctx.accounts.options_market.underlying_scale;
+                let locked = math::quote_amount(option_contract.strike_price, option_contract.amount, scale, Rounding::Up)?;
+                let still_required = math::quote_amount(option_contract.strike_price, remaining_amount, scale, Rounding::Up)?;
+                let collateral_amount = locked
+                    .checked_sub(still_required)
+                    .ok_or(OptionsError::MathOverflow)?;
+                (collateral_amount, ctx.accounts.writer_quote_account.to_account_info())
+            }
+        };
+
+        if collateral_amount > 0 {
+            let seeds = &[
+                b"collateral",
+                option_contract_key.as_ref(),
+                &[ctx.bumps.collateral_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.collateral_vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, collateral_amount)?;
+        }
+
+        // Burn the matching writer tokens so supply keeps tracking the contract amount
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                from: ctx.accounts.writer_claim_token_account.to_account_info(),
+                authority: ctx.accounts.writer.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, contracts_to_cancel)?;
+
+        option_contract.amount = remaining_amount;
+
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.open_interest_written = options_market.open_interest_written
+            .checked_sub(contracts_to_cancel)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        emit!(UnsoldCancelled {
+            option_contract: option_contract_key,
+            writer: option_contract.writer,
+            contracts_cancelled: contracts_to_cancel,
+            collateral_returned: collateral_amount,
+            remaining_amount,
+        });
+        msg!("Cancelled {} unsold contracts, {} remaining", contracts_to_cancel, remaining_amount);
+        Ok(())
+    }
+
+    /// Close a settled option contract and its empty vaults, returning the rent to whoever paid it.
+    /// The writer's emptied writer token account is closed too when passed. The option and writer
+    /// mints stay open: the SPL Token program cannot close a mint, so their rent is not recoverable.
+    pub fn close_option_contract(ctx: Context<CloseOptionContract>) -> Result<()> {
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+
+        // Final once every remaining contract was exercised, or once it has expired
+        let expired = clock.unix_timestamp > option_contract.expiration_timestamp;
+        require!(
+            expired || option_contract.contracts_exercised == option_contract.amount,
+            OptionsError::ContractNotFinal
+        );
+        require!(
+            ctx.accounts.collateral_vault.amount == 0 && lazy_vault_amount(&ctx.accounts.proceeds_vault)? == 0,
+            OptionsError::VaultNotEmpty
+        );
+
+        // Make sure an unclaimed expiry still reaches the market stats
+        if expired {
+            option_contract.record_expiry(&mut ctx.accounts.options_market)?;
+        }
+
+        let option_contract_key = option_contract.key();
+
+        let seeds = &[
+            b"collateral",
+            option_contract_key.as_ref(),
+            &[ctx.bumps.collateral_vault],
+        ];
+        let signer = &[&seeds[..]];
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.collateral_vault.to_account_info(),
+                destination: ctx.accounts.rent_payer.to_account_info(),
+                authority: ctx.accounts.collateral_vault.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)?;
+
+        close_proceeds_vault(
+            &ctx.accounts.proceeds_vault,
+            ctx.accounts.proceeds_rent_payer.as_ref(),
+            option_contract_key,
+            ctx.bumps.proceeds_vault,
+            &ctx.accounts.token_program,
+        )?;
+
+        // The writer signs, so their own emptied writer token account can be closed alongside
+        if let Some(writer_claim_token_account) = &ctx.accounts.writer_claim_token_account {
+            let close_claim_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: writer_claim_token_account.to_account_info(),
+                    destination: ctx.accounts.rent_payer.to_account_info(),
+                    authority: ctx.accounts.writer.to_account_info(),
+                },
+            );
+            token::close_account(close_claim_ctx)?;
+        }
+
+        emit!(OptionContractClosed {
+            option_contract: option_contract_key,
+            writer: ctx.accounts.writer.key(),
+        });
+        msg!("Option contract {} closed", option_contract_key);
+        Ok(())
+    }
+
+    /// Close a buyer position that can no longer be exercised, returning the rent to whoever paid it
+    pub fn close_buyer_position(ctx: Context<CloseBuyerPosition>) -> Result<()> {
+        let buyer_position = &ctx.accounts.buyer_position;
+
+        // A position is final once fully exercised, once its contract expired, or once the contract was closed
+        let position_final = buyer_position.contracts_owned == 0 || {
+            let contract_info = &ctx.accounts.option_contract;
+            if contract_info.data_is_empty() {
+                true
+            } else {
+                require_keys_eq!(*contract_info.owner, crate::ID, OptionsError::ContractMismatch);
+                let option_contract = OptionContract::try_deserialize(&mut &contract_info.try_borrow_data()?[..])?;
+                Clock::get()?.unix_timestamp > option_contract.expiration_timestamp
+            }
+        };
+        require!(position_final, OptionsError::PositionStillOpen);
+
+        emit!(BuyerPositionClosed {
+            buyer_position: buyer_position.key(),
+            option_contract: buyer_position.option_contract,
+            buyer: buyer_position.buyer,
+        });
+        msg!("Buyer position closed for contract {}", buyer_position.option_contract);
+        Ok(())
+    }
+
+    /// Settle an expired contract on behalf of a writer token holder (callable by any keeper).
+    /// The keeper earns the liquidation fee on the returned collateral.
+    pub fn settle_expired_option(ctx: Context<SettleExpiredOption>) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_CLAIM)?;
+
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp > option_contract.expiration_timestamp, OptionsError::OptionNotExpired);
+
+        // Only the tokens the holder delegated to the contract can be settled
+        let claim_account = &ctx.accounts.writer_claim_token_account;
+        let amount = claim_account.amount.min(claim_account.delegated_amount);
+        require!(amount > 0, OptionsError::NothingToSettle);
+
+        option_contract.record_expiry(&mut ctx.accounts.options_market)?;
+
+        let option_contract_key = option_contract.key();
+        let (writer_destination, keeper_destination, proceeds_destination) = match option_contract.option_type {
+            OptionType::Call => (
+                ctx.accounts.writer_token_account.to_account_info(),
+                ctx.accounts.keeper_token_account.to_account_info(),
+                ctx.accounts.writer_quote_account.to_account_info(),
+            ),
+            OptionType::Put => (
+                ctx.accounts.writer_quote_account.to_account_info(),
+                ctx.accounts.keeper_quote_account.to_account_info(),
+                ctx.accounts.writer_token_account.to_account_info(),
+            ),
+        };
+
+        let vault = &ctx.accounts.collateral_vault;
+        let writer_token_supply = ctx.accounts.writer_mint.supply;
+        let collateral_amount = math::pro_rata_share(vault.amount, amount, writer_token_supply)?;
+        // A put's vault also holds the premium, which is passed on without the fee
+        let premium_share = math::pro_rata_share(option_contract.held_premium, amount, writer_token_supply)?;
+        option_contract.held_premium = option_contract.held_premium
+            .checked_sub(premium_share)
+            .ok_or(OptionsError::MathOverflow)?;
+        let keeper_fee = math::fee_amount(
+            collateral_amount.checked_sub(premium_share).ok_or(OptionsError::MathOverflow)?,
+            ctx.accounts.protocol_state.liquidation_fee_rate,
+        )?;
+        let writer_amount = collateral_amount
+            .checked_sub(keeper_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+        // Exercise proceeds are passed on in full too
+        let proceeds_amount = math::pro_rata_share(
+            lazy_vault_amount(&ctx.accounts.proceeds_vault)?,
+            amount,
+            writer_token_supply,
+        )?;
+
+        let seeds = &[
+            b"collateral",
+            option_contract_key.as_ref(),
+            &[ctx.bumps.collateral_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        if writer_amount > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault.to_account_info(),
+                    to: writer_destination,
+                    authority: vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, writer_amount)?;
+        }
+
+        if keeper_fee > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault.to_account_info(),
+                    to: keeper_destination,
+                    authority: vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, keeper_fee)?;
+        }
+
+        if proceeds_amount > 0 {
+            let seeds = &[
+                b"proceeds",
+                option_contract_key.as_ref(),
+                &[ctx.bumps.proceeds_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.proceeds_vault.to_account_info(),
+                    to: proceeds_destination,
+                    authority: ctx.accounts.proceeds_vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, proceeds_amount)?;
+        }
+
+        // Burn the settled writer tokens as their delegate
+        let nonce = option_contract.nonce.to_le_bytes();
+        let contract_seeds = &[
+            b"option_contract".as_ref(),
+            option_contract.writer.as_ref(),
+            nonce.as_ref(),
+            &[option_contract.bump],
+        ];
+        let contract_signer = &[&contract_seeds[..]];
+
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                from: ctx.accounts.writer_claim_token_account.to_account_info(),
+                authority: option_contract.to_account_info(),
+            },
+            contract_signer,
+        );
+        token::burn(burn_ctx, amount)?;
+
+        emit!(ExpiredOptionSettled {
+            option_contract: option_contract_key,
+            options_market: option_contract.options_market,
+            holder: ctx.accounts.writer_claim_token_account.owner,
+            keeper: ctx.accounts.keeper.key(),
+            writer_tokens_burned: amount,
+            collateral_amount,
+            proceeds_amount,
+            keeper_fee,
+        });
+        msg!("Expired option settled by keeper: {} writer tokens, {} collateral, {} keeper fee",
+             amount,
+             collateral_amount,
+             keeper_fee
+        );
+        Ok(())
+    }
+
+    /// Mint option tokens for unsold contracts to the writer, e.g. to offer them on the order book
+    pub fn mint_unsold_options(ctx: Context<MintUnsoldOptions>, amount: u64) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_WRITE)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
+        require!(amount > 0, OptionsError::InvalidAmount);
+
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+
+        require!(clock.unix_timestamp < option_contract.expiration_timestamp, OptionsError::OptionExpired);
+
+        let unsold_contracts = option_contract.amount
+            .checked_sub(option_contract.contracts_sold)
+            .ok_or(OptionsError::MathOverflow)?;
+        require!(amount <= unsold_contracts, OptionsError::InsufficientContracts);
+
+        let nonce = option_contract.nonce.to_le_bytes();
+        let seeds = &[
+            b"option_contract".as_ref(),
+            option_contract.writer.as_ref(),
+            nonce.as_ref(),
+            &[option_contract.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.option_mint.to_account_info(),
+                to: ctx.accounts.writer_option_token_account.to_account_info(),
+                authority: option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, amount)?;
+
+        // Minted contracts count as sold; the writer now holds the long side
+        option_contract.contracts_sold = option_contract.contracts_sold
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.open_interest_sold = options_market.open_interest_sold
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        emit!(UnsoldOptionsMinted {
+            option_contract: option_contract.key(),
+            writer: option_contract.writer,
+            amount,
+            contracts_sold: option_contract.contracts_sold,
+        });
+        msg!("Minted {} unsold option tokens to the writer", amount);
+        Ok(())
+    }
+
+    /// Create the order book and its escrow vaults for an option series
+    pub fn create_order_book(ctx: Context<CreateOrderBook>) -> Result<()> {
+        let order_book = &mut ctx.accounts.order_book;
+        order_book.option_contract = ctx.accounts.option_contract.key();
+        order_book.option_mint = ctx.accounts.option_mint.key();
+        order_book.quote_mint = ctx.accounts.quote_mint.key();
+        order_book.underlying_scale = ctx.accounts.options_market.underlying_scale;
+        order_book.next_order_id = 0;
+        order_book.bids = Vec::new();
+        order_book.asks = Vec::new();
+        order_book.events = Vec::new();
+        order_book.parked = Vec::new();
+        order_book.bump = ctx.bumps.order_book;
+
+        emit!(OrderBookCreated {
+            order_book: order_book.key(),
+            option_contract: order_book.option_contract,
+        });
+        msg!("Order book created for option contract {}", order_book.option_contract);
+        Ok(())
+    }
+
+    /// Place a limit order; the crossing part fills immediately, the rest rests on the book
+    pub fn place_order(ctx: Context<PlaceOrder>, side: Side, price: u64, quantity: u64) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_BUY)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp < ctx.accounts.option_contract.expiration_timestamp,
+            OptionsError::OptionExpired
+        );
+
+        let owner = ctx.accounts.owner.key();
+        let fee_rate = ctx.accounts.protocol_state.protocol_fee_rate;
+        let result = ctx.accounts.order_book.place_order(owner, side, price, quantity, fee_rate)?;
+
+        let option_contract_key = ctx.accounts.option_contract.key();
+        let seeds = &[
+            b"order_book".as_ref(),
+            option_contract_key.as_ref(),
+            &[ctx.accounts.order_book.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let net_notional = result.notional
+            .checked_sub(result.fees)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        match side {
+            Side::Bid => {
+                // Pay the ask makers (through the crank) and escrow the resting remainder
+                let quote_in = net_notional
+                    .checked_add(result.escrowed_quote)
+                    .ok_or(OptionsError::MathOverflow)?;
+                if quote_in > 0 {
+                    let transfer_ctx = CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.owner_quote_account.to_account_info(),
+                            to: ctx.accounts.quote_vault.to_account_info(),
+                            authority: ctx.accounts.owner.to_account_info(),
+                        },
+                    );
+                    token::transfer(transfer_ctx, quote_in)?;
+                }
+                if result.fees > 0 {
+                    let transfer_ctx = CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.owner_quote_account.to_account_info(),
+                            to: ctx.accounts.fee_vault.to_account_info(),
+                            authority: ctx.accounts.owner.to_account_info(),
+                        },
+                    );
+                    token::transfer(transfer_ctx, result.fees)?;
+                }
+                if result.filled_quantity > 0 {
+                    let transfer_ctx = CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.option_vault.to_account_info(),
+                            to: ctx.accounts.owner_option_account.to_account_info(),
+                            authority: ctx.accounts.order_book.to_account_info(),
+                        },
+                        signer,
+                    );
+                    token::transfer(transfer_ctx, result.filled_quantity)?;
+                }
+            }
+            Side::Ask => {
+                // Every option token offered goes into escrow: filled ones are owed to bid makers
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.owner_option_account.to_account_info(),
+                        to: ctx.accounts.option_vault.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, quantity)?;
+
+                // Bid makers escrowed the quote, so the seller is paid from the vault
+                if net_notional > 0 {
+                    let transfer_ctx = CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.quote_vault.to_account_info(),
+                            to: ctx.accounts.owner_quote_account.to_account_info(),
+                            authority: ctx.accounts.order_book.to_account_info(),
+                        },
+                        signer,
+                    );
+                    token::transfer(transfer_ctx, net_notional)?;
+                }
+                if result.fees > 0 {
+                    let transfer_ctx = CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.quote_vault.to_account_info(),
+                            to: ctx.accounts.fee_vault.to_account_info(),
+                            authority: ctx.accounts.order_book.to_account_info(),
+                        },
+                        signer,
+                    );
+                    token::transfer(transfer_ctx, result.fees)?;
+                }
+            }
+        }
+
+        // Update protocol stats
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.total_volume = protocol_state.total_volume
+            .checked_add(result.notional)
+            .ok_or(OptionsError::MathOverflow)?;
+        protocol_state.total_fees_collected = protocol_state.total_fees_collected
+            .checked_add(result.fees)
+            .ok_or(OptionsError::MathOverflow)?;
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        fee_stats.total_fees_collected = fee_stats.total_fees_collected
+            .checked_add(result.fees)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        emit!(OrderPlaced {
+            order_book: ctx.accounts.order_book.key(),
+            owner,
+            side,
+            price,
+            quantity,
+            filled_quantity: result.filled_quantity,
+            notional: result.notional,
+            fees: result.fees,
+            resting_order_id: result.resting_order_id,
+            resting_quantity: result.resting_quantity,
+        });
+        msg!("Order placed: {} filled, {} resting", result.filled_quantity, result.resting_quantity);
+        Ok(())
+    }
+
+    /// Cancel a resting order and refund its escrow
+    pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let (side, order) = ctx.accounts.order_book.cancel_order(owner, order_id)?;
+
+        let option_contract_key = ctx.accounts.order_book.option_contract;
+        let seeds = &[
+            b"order_book".as_ref(),
+            option_contract_key.as_ref(),
+            &[ctx.accounts.order_book.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let (from, to, refund) = match side {
+            Side::Bid => (
+                ctx.accounts.quote_vault.to_account_info(),
+                ctx.accounts.owner_quote_account.to_account_info(),
+                order.locked_quote,
+            ),
+            Side::Ask => (
+                ctx.accounts.option_vault.to_account_info(),
+                ctx.accounts.owner_option_account.to_account_info(),
+                order.quantity,
+            ),
+        };
+
+        if refund > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from,
+                    to,
+                    authority: ctx.accounts.order_book.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, refund)?;
+        }
+
+        emit!(OrderCancelled {
+            order_book: ctx.accounts.order_book.key(),
+            owner,
+            order_id,
+            side,
+            remaining_quantity: order.quantity,
+            refund,
+        });
+        msg!("Order {} cancelled, {} refunded", order_id, refund);
+        Ok(())
+    }
+
+    /// Pay out queued maker fills (permissionless crank).
+    /// `remaining_accounts` holds each maker's option and quote token accounts, in event order.
+    /// A fill whose maker account was closed or frozen is parked for `claim_parked_fill` instead.
+    pub fn consume_events<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+        limit: u8,
+    ) -> Result<()> {
+        let order_book = &ctx.accounts.order_book;
+        let count = (limit as usize).min(order_book.events.len());
+        require!(count > 0, OptionsError::NoEventsToConsume);
+        require!(ctx.remaining_accounts.len() >= count * 2, OptionsError::InvalidMakerAccount);
+
+        let events = order_book.events[..count].to_vec();
+        let seeds = &[
+            b"order_book".as_ref(),
+            order_book.option_contract.as_ref(),
+            &[order_book.bump],
+        ];
+        let signer = &[&seeds[..]];
+        let mut undeliverable = Vec::new();
+
+        for (event, maker_accounts) in events.iter().zip(ctx.remaining_accounts.chunks(2)) {
+            // Makers are paid to their associated token accounts
+            let maker_option_account = &maker_accounts[0];
+            let maker_quote_account = &maker_accounts[1];
+            require_keys_eq!(
+                maker_option_account.key(),
+                get_associated_token_address(&event.maker, &order_book.option_mint),
+                OptionsError::InvalidMakerAccount
+            );
+            require_keys_eq!(
+                maker_quote_account.key(),
+                get_associated_token_address(&event.maker, &order_book.quote_mint),
+                OptionsError::InvalidMakerAccount
+            );
+
+            let (option_amount, quote_amount) = match event.maker_side {
+                Side::Bid => (event.quantity, event.quote_refund),
+                Side::Ask => (0, event.quote_amount),
+            };
+
+            // Skip a maker who cannot receive the fill rather than stall every fill behind it
+            if (option_amount > 0 && !can_receive_tokens(maker_option_account)?)
+                || (quote_amount > 0 && !can_receive_tokens(maker_quote_account)?)
+            {
+                undeliverable.push((event.maker, option_amount, quote_amount));
+                continue;
+            }
+
+            if option_amount > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.option_vault.to_account_info(),
+                        to: maker_option_account.clone(),
+                        authority: order_book.to_account_info(),
+                    },
+                    signer,
+                );
+                token::transfer(transfer_ctx, option_amount)?;
+            }
+            if quote_amount > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.quote_vault.to_account_info(),
+                        to: maker_quote_account.clone(),
+                        authority: order_book.to_account_info(),
+                    },
+                    signer,
+                );
+                token::transfer(transfer_ctx, quote_amount)?;
+            }
+        }
+
+        let order_book = &mut ctx.accounts.order_book;
+        order_book.events.drain(..count);
+        for (maker, option_amount, quote_amount) in &undeliverable {
+            order_book.park_fill(*maker, *option_amount, *quote_amount)?;
+        }
+
+        emit!(EventsConsumed {
+            order_book: order_book.key(),
+            count: count as u8,
+            parked: undeliverable.len() as u8,
+            remaining: order_book.events.len() as u8,
+        });
+        msg!("Consumed {} order book events, {} parked", count, undeliverable.len());
+        Ok(())
+    }
+
+    /// Collect the fills the crank parked because the maker's token account could not receive them
+    pub fn claim_parked_fill(ctx: Context<ClaimParkedFill>) -> Result<()> {
+        let maker = ctx.accounts.maker.key();
+        let parked = ctx.accounts.order_book.take_parked_fill(maker)?;
+
+        let option_contract_key = ctx.accounts.order_book.option_contract;
+        let seeds = &[
+            b"order_book".as_ref(),
+            option_contract_key.as_ref(),
+            &[ctx.accounts.order_book.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        if parked.option_amount > 0 {
+            let maker_option_account = ctx.accounts.maker_option_account
+                .as_ref()
+                .ok_or(OptionsError::InvalidMakerAccount)?;
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.option_vault.to_account_info(),
+                    to: maker_option_account.to_account_info(),
+                    authority: ctx.accounts.order_book.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, parked.option_amount)?;
+        }
+        if parked.quote_amount > 0 {
+            let maker_quote_account = ctx.accounts.maker_quote_account
+                .as_ref()
+                .ok_or(OptionsError::InvalidMakerAccount)?;
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.quote_vault.to_account_info(),
+                    to: maker_quote_account.to_account_info(),
+                    authority: ctx.accounts.order_book.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, parked.quote_amount)?;
+        }
+
+        emit!(ParkedFillClaimed {
+            order_book: ctx.accounts.order_book.key(),
+            maker,
+            option_amount: parked.option_amount,
+            quote_amount: parked.quote_amount,
+        });
+        msg!("Parked fill claimed: {} options, {} quote", parked.option_amount, parked.quote_amount);
+        Ok(())
+    }
+
+    /// Broadcast a request for quotes on a block of options
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_rfq(
+        ctx: Context<CreateRfq>,
+        rfq_id: u64,
+        side: Side,
+        option_type: OptionType,
+        strike_price: u64,
+        expiration_timestamp: i64,
+        amount: u64,
+        quote_deadline: i64,
+    ) -> Result<()> {
+        // Bid: the requester buys the options, Ask: the requester writes them
+        let operation = match side {
+            Side::Bid => PAUSE_BUY,
+            Side::Ask => PAUSE_WRITE,
+        };
+        ctx.accounts.protocol_state.require_not_paused(operation)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
+
+        let clock = Clock::get()?;
+        require!(expiration_timestamp > clock.unix_timestamp, OptionsError::ExpirationInPast);
+        require!(amount > 0, OptionsError::InvalidAmount);
+        require!(
+            quote_deadline > clock.unix_timestamp && quote_deadline < expiration_timestamp,
+            OptionsError::InvalidDeadline
+        );
+
+        let rfq = &mut ctx.accounts.rfq;
+        rfq.requester = ctx.accounts.requester.key();
+        rfq.options_market = ctx.accounts.options_market.key();
+        rfq.rfq_id = rfq_id;
+        rfq.side = side;
+        rfq.option_type = option_type;
+        rfq.strike_price = strike_price;
+        rfq.expiration_timestamp = expiration_timestamp;
+        rfq.amount = amount;
+        rfq.quote_deadline = quote_deadline;
+        rfq.is_filled = false;
+        rfq.option_contract = Pubkey::default();
+        rfq.bump = ctx.bumps.rfq;
+
+        emit!(RfqCreated {
+            rfq: rfq.key(),
+            requester: rfq.requester,
+            options_market: rfq.options_market,
+            side,
+            option_type,
+            strike_price,
+            expiration_timestamp,
+            amount,
+            quote_deadline,
+        });
+        msg!("RFQ {} created for {} contracts at strike {}", rfq_id, amount, strike_price);
+        Ok(())
+    }
+
+    /// Quote an RFQ, escrowing the maker's side of the trade
+    pub fn submit_rfq_quote(
+        ctx: Context<SubmitRfqQuote>,
+        premium_per_contract: u64,
+        valid_until: i64,
+    ) -> Result<()> {
+        let rfq = &ctx.accounts.rfq;
+        let operation = match rfq.side {
+            Side::Bid => PAUSE_WRITE,
+            Side::Ask => PAUSE_BUY,
+        };
+        ctx.accounts.protocol_state.require_not_paused(operation)?;
+        require!(premium_per_contract > 0, OptionsError::InvalidPremium);
+
+        let clock = Clock::get()?;
+        require!(!rfq.is_filled, OptionsError::RfqFilled);
+        require!(clock.unix_timestamp <= rfq.quote_deadline, OptionsError::DeadlineExceeded);
+        require!(
+            valid_until >= clock.unix_timestamp && valid_until < rfq.expiration_timestamp,
+            OptionsError::InvalidDeadline
+        );
+
+        // A writing maker escrows the collateral, a buying maker escrows the premium
+        let underlying_scale = ctx.accounts.options_market.underlying_scale;
+        let escrow_amount = match rfq.side {
+            Side::Bid => rfq.option_type.collateral_amount(rfq.strike_price, rfq.amount, underlying_scale)?,
+            Side::Ask => ctx.accounts.protocol_state
+                .premium_with_fee(premium_per_contract, rfq.amount, underlying_scale)?
+                .0,
+        };
+
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.maker_token_account.to_account_info(),
+                to: ctx.accounts.rfq_escrow.to_account_info(),
+                authority: ctx.accounts.maker.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, escrow_amount)?;
+
+        let rfq_quote = &mut ctx.accounts.rfq_quote;
+        rfq_quote.rfq = rfq.key();
+        rfq_quote.maker = ctx.accounts.maker.key();
+        rfq_quote.premium_per_contract = premium_per_contract;
+        rfq_quote.valid_until = valid_until;
+        rfq_quote.escrow_amount = escrow_amount;
+        rfq_quote.bump = ctx.bumps.rfq_quote;
+
+        emit!(RfqQuoteSubmitted {
+            rfq: rfq.key(),
+            rfq_quote: rfq_quote.key(),
+            maker: rfq_quote.maker,
+            premium_per_contract,
+            valid_until,
+            escrow_amount,
+        });
+        msg!("RFQ quote submitted at {} per contract, {} escrowed", premium_per_contract, escrow_amount);
+        Ok(())
+    }
+
+    /// Accept a quote: writes a new option contract and settles the premium atomically
+    pub fn accept_rfq_quote(ctx: Context<AcceptRfqQuote>, limit_premium: u64) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_WRITE)?;
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_BUY)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
+
+        let clock = Clock::get()?;
+        let rfq = &ctx.accounts.rfq;
+        let rfq_quote = &ctx.accounts.rfq_quote;
+        require!(clock.unix_timestamp <= rfq_quote.valid_until, OptionsError::DeadlineExceeded);
+        require!(clock.unix_timestamp < rfq.expiration_timestamp, OptionsError::OptionExpired);
+        // Protect the requester from a quote replaced just before acceptance
+        match rfq.side {
+            Side::Bid => require!(
+                rfq_quote.premium_per_contract <= limit_premium,
+                OptionsError::PremiumAboveLimit
+            ),
+            Side::Ask => require!(
+                rfq_quote.premium_per_contract >= limit_premium,
+                OptionsError::PremiumBelowLimit
+            ),
+        }
+
+        let side = rfq.side;
+        let option_type = rfq.option_type;
+        let strike_price = rfq.strike_price;
+        let expiration_timestamp = rfq.expiration_timestamp;
+        let amount = rfq.amount;
+        let premium_per_contract = rfq_quote.premium_per_contract;
+        let maker = rfq_quote.maker;
+        let rfq_key = rfq.key();
+        let quote_bump = rfq_quote.bump;
+
+        let underlying_scale = ctx.accounts.options_market.underlying_scale;
+        let collateral_amount = option_type.collateral_amount(strike_price, amount, underlying_scale)?;
+        let (total_premium, protocol_fee) = ctx.accounts.protocol_state
+            .premium_with_fee(premium_per_contract, amount, underlying_scale)?;
+        let net_premium = total_premium
+            .checked_sub(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // The contract is seeded by the writer's next nonce
+        let writer_key = ctx.accounts.writer.key();
+        let writer_account = &mut ctx.accounts.writer_account;
+        if writer_account.writer == Pubkey::default() {
+            writer_account.writer = writer_key;
+            writer_account.bump = ctx.bumps.writer_account;
+        }
+        let nonce = writer_account.next_nonce;
+        writer_account.next_nonce = nonce
+            .checked_add(1)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // The whole block is written and sold in one go
+        let option_contract = &mut ctx.accounts.option_contract;
+        option_contract.writer = writer_key;
+        option_contract.options_market = ctx.accounts.options_market.key();
+        option_contract.underlying_mint = ctx.accounts.underlying_mint.key();
+        option_contract.quote_mint = ctx.accounts.quote_mint.key();
+        option_contract.option_type = option_type;
+        option_contract.strike_price = strike_price;
+        option_contract.expiration_timestamp = expiration_timestamp;
+        option_contract.amount = amount;
+        option_contract.premium_per_contract = premium_per_contract;
+        option_contract.contracts_sold = amount;
+        option_contract.contracts_exercised = 0;
+        option_contract.is_exercised = false;
+        option_contract.is_expired = false;
+        option_contract.creation_timestamp = clock.unix_timestamp;
+        option_contract.option_mint = ctx.accounts.option_mint.key();
+        option_contract.writer_mint = ctx.accounts.writer_mint.key();
+        option_contract.nonce = nonce;
+        option_contract.auction_start_premium = 0;
+        option_contract.auction_start_timestamp = 0;
+        option_contract.auction_duration = 0;
+        option_contract.rent_payer = ctx.accounts.requester.key();
+        option_contract.held_premium = 0;
+        option_contract.proceeds_rent_payer = Pubkey::default();
+        option_contract.bump = ctx.bumps.option_contract;
+
+        let quote_seeds = &[
+            b"rfq_quote".as_ref(),
+            rfq_key.as_ref(),
+            maker.as_ref(),
+            &[quote_bump],
+        ];
+        let quote_signer = &[&quote_seeds[..]];
+
+        match side {
+            Side::Bid => {
+                // The maker writes: collateral comes out of their escrow, the premium from the requester
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.rfq_escrow.to_account_info(),
+                        to: ctx.accounts.collateral_vault.to_account_info(),
+                        authority: ctx.accounts.rfq_quote.to_account_info(),
+                    },
+                    quote_signer,
+                );
+                token::transfer(transfer_ctx, collateral_amount)?;
+
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.requester_quote_account.to_account_info(),
+                        to: ctx.accounts.maker_quote_account.to_account_info(),
+                        authority: ctx.accounts.requester.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, net_premium)?;
+
+                if protocol_fee > 0 {
+                    let transfer_ctx = CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.requester_quote_account.to_account_info(),
+                            to: ctx.accounts.fee_vault.to_account_info(),
+                            authority: ctx.accounts.requester.to_account_info(),
+                        },
+                    );
+                    token::transfer(transfer_ctx, protocol_fee)?;
+                }
+            }
+            Side::Ask => {
+                // The requester writes: collateral comes from them, the premium out of the maker's escrow
+                let requester_collateral_account = ctx.accounts.requester_collateral_account
+                    .as_ref()
+                    .ok_or(OptionsError::CollateralAccountRequired)?;
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: requester_collateral_account.to_account_info(),
+                        to: ctx.accounts.collateral_vault.to_account_info(),
+                        authority: ctx.accounts.requester.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, collateral_amount)?;
+
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.rfq_escrow.to_account_info(),
+                        to: ctx.accounts.requester_quote_account.to_account_info(),
+                        authority: ctx.accounts.rfq_quote.to_account_info(),
+                    },
+                    quote_signer,
+                );
+                token::transfer(transfer_ctx, net_premium)?;
+
+                if protocol_fee > 0 {
+                    let transfer_ctx = CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.rfq_escrow.to_account_info(),
+                            to: ctx.accounts.fee_vault.to_account_info(),
+                            authority: ctx.accounts.rfq_quote.to_account_info(),
+                        },
+                        quote_signer,
+                    );
+                    token::transfer(transfer_ctx, protocol_fee)?;
+                }
+            }
+        }
+
+        // Mint the short side to the writer and the long side to the buyer
+        let nonce_bytes = nonce.to_le_bytes();
+        let seeds = &[
+            b"option_contract".as_ref(),
+            writer_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.option_contract],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                to: ctx.accounts.writer_claim_token_account.to_account_info(),
+                authority: ctx.accounts.option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, amount)?;
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.option_mint.to_account_info(),
+                to: ctx.accounts.buyer_option_token_account.to_account_info(),
+                authority: ctx.accounts.option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, amount)?;
+
+        // Only a signing writer can delegate their tokens for keeper settlement
+        if side == Side::Ask {
+            let approve_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.writer_claim_token_account.to_account_info(),
+                    delegate: ctx.accounts.option_contract.to_account_info(),
+                    authority: ctx.accounts.requester.to_account_info(),
+                },
+            );
+            token::approve(approve_ctx, amount)?;
+        }
+
+        // Anything the fill did not use (over-funding or a donation) goes back to the maker
+        ctx.accounts.rfq_escrow.reload()?;
+        let escrow_refund = ctx.accounts.rfq_escrow.amount;
+        if escrow_refund > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.rfq_escrow.to_account_info(),
+                    to: ctx.accounts.maker_token_account.to_account_info(),
+                    authority: ctx.accounts.rfq_quote.to_account_info(),
+                },
+                quote_signer,
+            );
+            token::transfer(transfer_ctx, escrow_refund)?;
+        }
+
+        // The escrow is spent; return its rent to the maker
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.rfq_escrow.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.rfq_quote.to_account_info(),
+            },
+            quote_signer,
+        );
+        token::close_account(close_ctx)?;
+
+        let buyer_position = &mut ctx.accounts.buyer_position;
+        buyer_position.buyer = ctx.accounts.buyer.key();
+        buyer_position.option_contract = ctx.accounts.option_contract.key();
+        buyer_position.contracts_owned = amount;
+        buyer_position.contracts_exercised = 0;
+        buyer_position.premium_paid = total_premium;
+        buyer_position.is_exercised = false;
+        buyer_position.rent_payer = ctx.accounts.requester.key();
+        buyer_position.bump = ctx.bumps.buyer_position;
+
+        // Update protocol stats
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.total_volume = protocol_state.total_volume
+            .checked_add(total_premium)
+            .ok_or(OptionsError::MathOverflow)?;
+        protocol_state.total_fees_collected = protocol_state.total_fees_collected
+            .checked_add(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        fee_stats.total_fees_collected = fee_stats.total_fees_collected
+            .checked_add(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // Update market stats
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.total_options_written = options_market.total_options_written
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_written = options_market.open_interest_written
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.total_volume = options_market.total_volume
+            .checked_add(total_premium)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_sold = options_market.open_interest_sold
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        let rfq = &mut ctx.accounts.rfq;
+        rfq.is_filled = true;
+        rfq.option_contract = ctx.accounts.option_contract.key();
+
+        emit!(RfqQuoteAccepted {
+            rfq: rfq_key,
+            rfq_quote: ctx.accounts.rfq_quote.key(),
+            option_contract: rfq.option_contract,
+            writer: writer_key,
+            buyer: ctx.accounts.buyer.key(),
+            amount,
+            premium_per_contract,
+            total_premium,
+            protocol_fee,
+            collateral_amount,
+            escrow_refund,
+        });
+        msg!("RFQ filled: {} contracts for {} total premium", amount, total_premium);
+        Ok(())
+    }
+
+    /// Withdraw a quote and refund its escrow.
+    /// The maker can do this at any time; anyone can once the quote has expired or the RFQ is filled.
+    pub fn cancel_rfq_quote(ctx: Context<CancelRfqQuote>) -> Result<()> {
+        let clock = Clock::get()?;
+        let rfq_quote = &ctx.accounts.rfq_quote;
+        require!(
+            ctx.accounts.authority.key() == rfq_quote.maker
+                || clock.unix_timestamp > rfq_quote.valid_until
+                || ctx.accounts.rfq.is_filled,
+            OptionsError::QuoteStillActive
+        );
+
+        let rfq_key = ctx.accounts.rfq.key();
+        let seeds = &[
+            b"rfq_quote".as_ref(),
+            rfq_key.as_ref(),
+            rfq_quote.maker.as_ref(),
+            &[rfq_quote.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let refund = ctx.accounts.rfq_escrow.amount;
+        if refund > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.rfq_escrow.to_account_info(),
+                    to: ctx.accounts.maker_token_account.to_account_info(),
+                    authority: ctx.accounts.rfq_quote.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, refund)?;
+        }
+
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.rfq_escrow.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.rfq_quote.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)?;
+
+        emit!(RfqQuoteCancelled {
+            rfq: rfq_key,
+            rfq_quote: rfq_quote.key(),
+            maker: rfq_quote.maker,
+            refund,
+        });
+        msg!("RFQ quote cancelled, {} refunded", refund);
+        Ok(())
+    }
+
+    /// Create the liquidity pool for a market. Market authority only.
+    pub fn create_pool(ctx: Context<CreatePool>, volatility_bps: u64) -> Result<()> {
+        require!(
+            volatility_bps > 0 && volatility_bps <= MAX_VOLATILITY_BPS,
+            OptionsError::InvalidVolatility
+        );
+
+        let pool = &mut ctx.accounts.pool;
+        pool.options_market = ctx.accounts.options_market.key();
+        pool.volatility_bps = volatility_bps;
+        pool.locked_underlying = 0;
+        pool.locked_quote = 0;
+        pool.open_contracts = 0;
+        pool.reference_price = 0;
+        pool.reference_price_updated_at = 0;
+        pool.bump = ctx.bumps.pool;
+        ctx.accounts.options_market.pool = pool.key();
+
+        emit!(PoolCreated {
+            pool: pool.key(),
+            options_market: pool.options_market,
+            lp_mint: ctx.accounts.lp_mint.key(),
+            volatility_bps,
+        });
+        msg!("Liquidity pool created for market {} at {} bps volatility", pool.options_market, volatility_bps);
+        Ok(())
+    }
+
+    /// Update the volatility the pool prices options at. Market authority only.
+    pub fn set_pool_volatility(ctx: Context<SetPoolVolatility>, volatility_bps: u64) -> Result<()> {
+        require!(
+            volatility_bps > 0 && volatility_bps <= MAX_VOLATILITY_BPS,
+            OptionsError::InvalidVolatility
+        );
+
+        let pool = &mut ctx.accounts.pool;
+        pool.volatility_bps = volatility_bps;
+
+        emit!(PoolVolatilityUpdated {
+            pool: pool.key(),
+            volatility_bps,
+        });
+        msg!("Pool volatility set to {} bps", volatility_bps);
+        Ok(())
+    }
+
+    /// Update the spot price the pool prices and bands strikes against. Market authority only.
+    pub fn set_pool_reference_price(ctx: Context<SetPoolVolatility>, reference_price: u64) -> Result<()> {
+        require!(reference_price > 0, OptionsError::InvalidAmount);
+
+        let clock = Clock::get()?;
+        let pool = &mut ctx.accounts.pool;
+        pool.reference_price = reference_price;
+        pool.reference_price_updated_at = clock.unix_timestamp;
+
+        emit!(PoolReferencePriceUpdated {
+            pool: pool.key(),
+            reference_price,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Pool reference price set to {}", reference_price);
+        Ok(())
+    }
+
+    /// Deposit underlying and quote tokens into the pool for LP shares
+    /// Every open pool-written contract goes in `remaining_accounts`, sorted by address.
+    pub fn deposit_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositLiquidity<'info>>,
+        underlying_amount: u64,
+        quote_amount: u64,
+        min_shares: u64,
+    ) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_WRITE)?;
+        require!(underlying_amount > 0 || quote_amount > 0, OptionsError::InvalidAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        let underlying_scale = ctx.accounts.options_market.underlying_scale;
+        let liability = ctx.accounts.pool.open_liability(
+            ctx.accounts.pool.key(),
+            ctx.remaining_accounts,
+            underlying_scale,
+            now,
+        )?;
+        let shares = ctx.accounts.pool.shares_for_deposit(
+            underlying_amount,
+            quote_amount,
+            ctx.accounts.pool_underlying_vault.amount,
+            ctx.accounts.pool_quote_vault.amount,
+            liability,
+            ctx.accounts.lp_mint.supply,
+            underlying_scale,
+            now,
+        )?;
+        require!(shares > 0, OptionsError::InvalidAmount);
+        require!(shares >= min_shares, OptionsError::ReceivedBelowMinimum);
+
+        for (from, to, amount) in [
+            (&ctx.accounts.depositor_underlying_account, &ctx.accounts.pool_underlying_vault, underlying_amount),
+            (&ctx.accounts.depositor_quote_account, &ctx.accounts.pool_quote_vault, quote_amount),
+        ] {
+            if amount > 0 {
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.depositor.to_account_info(),
+                    },
+                );
+                token::transfer(transfer_ctx, amount)?;
+            }
+        }
+
+        let options_market_key = ctx.accounts.pool.options_market;
+        let seeds = &[
+            b"pool".as_ref(),
+            options_market_key.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.depositor_lp_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, shares)?;
+
+        emit!(LiquidityDeposited {
+            pool: ctx.accounts.pool.key(),
+            depositor: ctx.accounts.depositor.key(),
+            underlying_amount,
+            quote_amount,
+            shares,
+        });
+        msg!("Deposited {} underlying and {} quote for {} LP shares", underlying_amount, quote_amount, shares);
+        Ok(())
+    }
+
+    /// Burn LP shares for a pro-rata share of the pool, paid out of its free liquidity.
+    /// Every open pool-written contract goes in `remaining_accounts`, sorted by address.
+    pub fn withdraw_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawLiquidity<'info>>,
+        shares: u64,
+        min_underlying: u64,
+        min_quote: u64,
+    ) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_CLAIM)?;
+        require!(shares > 0, OptionsError::InvalidAmount);
+
+        // What the open contracts owe at the reference price stays behind for their holders
+        let liability = ctx.accounts.pool.open_liability(
+            ctx.accounts.pool.key(),
+            ctx.remaining_accounts,
+            ctx.accounts.options_market.underlying_scale,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let free_underlying = ctx.accounts.pool_underlying_vault.amount;
+        let free_quote = ctx.accounts.pool_quote_vault.amount;
+        let (total_underlying, total_quote) = ctx.accounts.pool.total_assets(free_underlying, free_quote, liability)?;
+        let lp_supply = ctx.accounts.lp_mint.supply;
+
+        // Locked collateral counts toward the share value but cannot be paid out until it is settled
+        let underlying_amount = math::pro_rata_share(total_underlying, shares, lp_supply)?;
+        let quote_amount = math::pro_rata_share(total_quote, shares, lp_supply)?;
+        require!(
+            underlying_amount <= free_underlying && quote_amount <= free_quote,
+            OptionsError::InsufficientLiquidity
+        );
+        require!(
+            underlying_amount >= min_underlying && quote_amount >= min_quote,
+            OptionsError::ReceivedBelowMinimum
+        );
+
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.depositor_lp_account.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        );
+        token::burn(burn_ctx, shares)?;
+
+        let options_market_key = ctx.accounts.pool.options_market;
+        let seeds = &[
+            b"pool".as_ref(),
+            options_market_key.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        for (from, to, amount) in [
+            (&ctx.accounts.pool_underlying_vault, &ctx.accounts.depositor_underlying_account, underlying_amount),
+            (&ctx.accounts.pool_quote_vault, &ctx.accounts.depositor_quote_account, quote_amount),
+        ] {
+            if amount > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    signer,
+                );
+                token::transfer(transfer_ctx, amount)?;
+            }
+        }
+
+        emit!(LiquidityWithdrawn {
+            pool: ctx.accounts.pool.key(),
+            depositor: ctx.accounts.depositor.key(),
+            shares,
+            underlying_amount,
+            quote_amount,
+        });
+        msg!("Withdrew {} underlying and {} quote for {} LP shares", underlying_amount, quote_amount, shares);
+        Ok(())
+    }
+
+    /// Buy options from the pool, which writes a new contract at its model price
+    #[allow(clippy::too_many_arguments)]
+    pub fn buy_from_pool(
+        ctx: Context<BuyFromPool>,
+        option_type: OptionType,
+        strike_price: u64,
+        expiration_timestamp: i64,
+        amount: u64,
+        max_total_cost: u64,
+        valid_until: i64,
+    ) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_WRITE)?;
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_BUY)?;
+        require!(ctx.accounts.options_market.is_active, OptionsError::MarketInactive);
+        require!(amount > 0, OptionsError::InvalidAmount);
+
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp <= valid_until, OptionsError::DeadlineExceeded);
+        require!(expiration_timestamp > clock.unix_timestamp, OptionsError::ExpirationInPast);
+        require!(
+            expiration_timestamp - clock.unix_timestamp >= MIN_POOL_TIME_TO_EXPIRY,
+            OptionsError::ExpiryTooSoon
+        );
+        require!(
+            ctx.accounts.pool.open_contracts < MAX_OPEN_POOL_CONTRACTS,
+            OptionsError::TooManyOpenContracts
+        );
+
+        let seconds_to_expiry = (expiration_timestamp - clock.unix_timestamp) as u64;
+        let premium_per_contract = ctx.accounts.pool.premium_per_contract(
+            option_type,
+            strike_price,
+            seconds_to_expiry,
+            clock.unix_timestamp,
+        )?;
+        require!(premium_per_contract > 0, OptionsError::InvalidPremium);
+
+        let underlying_scale = ctx.accounts.options_market.underlying_scale;
+        let collateral_amount = option_type.collateral_amount(strike_price, amount, underlying_scale)?;
+        let (total_premium, protocol_fee) = ctx.accounts.protocol_state
+            .premium_with_fee(premium_per_contract, amount, underlying_scale)?;
+        require!(total_premium <= max_total_cost, OptionsError::TotalCostAboveLimit);
+        let net_premium = total_premium
+            .checked_sub(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // The pool is the writer; its contracts are seeded by its own nonce
+        let pool_key = ctx.accounts.pool.key();
+        let writer_account = &mut ctx.accounts.writer_account;
+        if writer_account.writer == Pubkey::default() {
+            writer_account.writer = pool_key;
+            writer_account.bump = ctx.bumps.writer_account;
+        }
+        let nonce = writer_account.next_nonce;
+        writer_account.next_nonce = nonce
+            .checked_add(1)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        let option_contract = &mut ctx.accounts.option_contract;
+        option_contract.writer = pool_key;
+        option_contract.options_market = ctx.accounts.options_market.key();
+        option_contract.underlying_mint = ctx.accounts.underlying_mint.key();
+        option_contract.quote_mint = ctx.accounts.quote_mint.key();
+        option_contract.option_type = option_type;
+        option_contract.strike_price = strike_price;
+        option_contract.expiration_timestamp = expiration_timestamp;
+        option_contract.amount = amount;
+        option_contract.premium_per_contract = premium_per_contract;
+        option_contract.contracts_sold = amount;
+        option_contract.contracts_exercised = 0;
+        option_contract.is_exercised = false;
+        option_contract.is_expired = false;
+        option_contract.creation_timestamp = clock.unix_timestamp;
+        option_contract.option_mint = ctx.accounts.option_mint.key();
+        option_contract.writer_mint = ctx.accounts.writer_mint.key();
+        option_contract.nonce = nonce;
+        option_contract.auction_start_premium = 0;
+        option_contract.auction_start_timestamp = 0;
+        option_contract.auction_duration = 0;
+        option_contract.rent_payer = ctx.accounts.buyer.key();
+        option_contract.held_premium = 0;
+        option_contract.proceeds_rent_payer = Pubkey::default();
+        option_contract.bump = ctx.bumps.option_contract;
+
+        // Lock collateral out of the pool's free liquidity
+        let (collateral_source, free_collateral) = match option_type {
+            OptionType::Call => (&ctx.accounts.pool_underlying_vault, ctx.accounts.pool_underlying_vault.amount),
+            OptionType::Put => (&ctx.accounts.pool_quote_vault, ctx.accounts.pool_quote_vault.amount),
+        };
+        require!(collateral_amount <= free_collateral, OptionsError::InsufficientLiquidity);
+
+        let options_market_key = ctx.accounts.options_market.key();
+        let pool_seeds = &[
+            b"pool".as_ref(),
+            options_market_key.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: collateral_source.to_account_info(),
+                to: ctx.accounts.collateral_vault.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_ctx, collateral_amount)?;
+
+        // The premium goes to the pool, the protocol fee to the fee vault
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_quote_account.to_account_info(),
+                to: ctx.accounts.pool_quote_vault.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        );
+        token::transfer(transfer_ctx, net_premium)?;
+
+        if protocol_fee > 0 {
+            let transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_quote_account.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            );
+            token::transfer(transfer_ctx, protocol_fee)?;
+        }
+
+        // Mint the short side to the pool and the long side to the buyer
+        let nonce_bytes = nonce.to_le_bytes();
+        let seeds = &[
+            b"option_contract".as_ref(),
+            pool_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.option_contract],
+        ];
+        let signer = &[&seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                to: ctx.accounts.pool_writer_token_account.to_account_info(),
+                authority: ctx.accounts.option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, amount)?;
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.option_mint.to_account_info(),
+                to: ctx.accounts.buyer_option_token_account.to_account_info(),
+                authority: ctx.accounts.option_contract.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(mint_ctx, amount)?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.open_contracts = pool.open_contracts
+            .checked_add(1)
+            .ok_or(OptionsError::MathOverflow)?;
+        match option_type {
+            OptionType::Call => {
+                pool.locked_underlying = pool.locked_underlying
+                    .checked_add(collateral_amount)
+                    .ok_or(OptionsError::MathOverflow)?;
+            }
+            OptionType::Put => {
+                pool.locked_quote = pool.locked_quote
+                    .checked_add(collateral_amount)
+                    .ok_or(OptionsError::MathOverflow)?;
+            }
+        }
+
+        let buyer_position = &mut ctx.accounts.buyer_position;
+        buyer_position.buyer = ctx.accounts.buyer.key();
+        buyer_position.option_contract = ctx.accounts.option_contract.key();
+        buyer_position.contracts_owned = amount;
+        buyer_position.contracts_exercised = 0;
+        buyer_position.premium_paid = total_premium;
+        buyer_position.is_exercised = false;
+        buyer_position.rent_payer = ctx.accounts.buyer.key();
+        buyer_position.bump = ctx.bumps.buyer_position;
+
+        // Update protocol stats
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.total_volume = protocol_state.total_volume
+            .checked_add(total_premium)
+            .ok_or(OptionsError::MathOverflow)?;
+        protocol_state.total_fees_collected = protocol_state.total_fees_collected
+            .checked_add(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+        let fee_stats = &mut ctx.accounts.fee_stats;
+        fee_stats.total_fees_collected = fee_stats.total_fees_collected
+            .checked_add(protocol_fee)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        // Update market stats
+        let options_market = &mut ctx.accounts.options_market;
+        options_market.total_options_written = options_market.total_options_written
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_written = options_market.open_interest_written
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.total_volume = options_market.total_volume
+            .checked_add(total_premium)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_sold = options_market.open_interest_sold
+            .checked_add(amount)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        emit!(PoolOptionSold {
+            pool: pool_key,
+            option_contract: ctx.accounts.option_contract.key(),
+            buyer: ctx.accounts.buyer.key(),
+            option_type,
+            strike_price,
+            expiration_timestamp,
+            amount,
+            premium_per_contract,
+            total_premium,
+            protocol_fee,
+            collateral_amount,
+        });
+        msg!("Pool sold {} contracts at {} per contract", amount, premium_per_contract);
+        Ok(())
+    }
+
+    /// Return the collateral of an expired pool-written contract to the pool and close it (permissionless)
+    pub fn settle_pool_option(ctx: Context<SettlePoolOption>) -> Result<()> {
+        ctx.accounts.protocol_state.require_not_paused(PAUSE_CLAIM)?;
+
+        let option_contract = &mut ctx.accounts.option_contract;
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp > option_contract.expiration_timestamp, OptionsError::OptionNotExpired);
+
+        // The pool holds every writer token of its contracts, so this runs once per contract
+        let writer_tokens = ctx.accounts.pool_writer_token_account.amount;
+        require!(writer_tokens > 0, OptionsError::NothingToSettle);
+
+        option_contract.record_expiry(&mut ctx.accounts.options_market)?;
+
+        let option_contract_key = option_contract.key();
+        let collateral_returned = ctx.accounts.collateral_vault.amount;
+        if collateral_returned > 0 {
+            let destination = match option_contract.option_type {
+                OptionType::Call => ctx.accounts.pool_underlying_vault.to_account_info(),
+                OptionType::Put => ctx.accounts.pool_quote_vault.to_account_info(),
+            };
+
+            let seeds = &[
+                b"collateral",
+                option_contract_key.as_ref(),
+                &[ctx.bumps.collateral_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.collateral_vault.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.collateral_vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, collateral_returned)?;
+        }
+
+        let proceeds_returned = lazy_vault_amount(&ctx.accounts.proceeds_vault)?;
+        if proceeds_returned > 0 {
+            let destination = match option_contract.option_type {
+                OptionType::Call => ctx.accounts.pool_quote_vault.to_account_info(),
+                OptionType::Put => ctx.accounts.pool_underlying_vault.to_account_info(),
+            };
+
+            let seeds = &[
+                b"proceeds",
+                option_contract_key.as_ref(),
+                &[ctx.bumps.proceeds_vault],
+            ];
+            let signer = &[&seeds[..]];
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.proceeds_vault.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.proceeds_vault.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(transfer_ctx, proceeds_returned)?;
+        }
+
+        let options_market_key = ctx.accounts.pool.options_market;
+        let pool_seeds = &[
+            b"pool".as_ref(),
+            options_market_key.as_ref(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.writer_mint.to_account_info(),
+                from: ctx.accounts.pool_writer_token_account.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::burn(burn_ctx, writer_tokens)?;
+
+        // Close the emptied token accounts; the contract itself is closed on exit.
+        // All of their rent goes back to the buyer who funded them.
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.pool_writer_token_account.to_account_info(),
+                destination: ctx.accounts.rent_payer.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::close_account(close_ctx)?;
+
+        let seeds = &[
+            b"collateral",
+            option_contract_key.as_ref(),
+            &[ctx.bumps.collateral_vault],
+        ];
+        let signer = &[&seeds[..]];
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.collateral_vault.to_account_info(),
+                destination: ctx.accounts.rent_payer.to_account_info(),
+                authority: ctx.accounts.collateral_vault.to_account_info(),
+            },
+            signer,
+        );
+        token::close_account(close_ctx)?;
+
+        close_proceeds_vault(
+            &ctx.accounts.proceeds_vault,
+            ctx.accounts.proceeds_rent_payer.as_ref(),
+            option_contract_key,
+            ctx.bumps.proceeds_vault,
+            &ctx.accounts.token_program,
+        )?;
+
+        // Both vaults now sit in the pool's free balances. Saturate so that tokens donated
+        // to a vault cannot push the locked totals below zero and block settlement.
+        let (underlying_released, quote_released) = match option_contract.option_type {
+            OptionType::Call => (collateral_returned, proceeds_returned),
+            OptionType::Put => (proceeds_returned, collateral_returned),
+        };
+        let pool = &mut ctx.accounts.pool;
+        pool.locked_underlying = pool.locked_underlying.saturating_sub(underlying_released);
+        pool.locked_quote = pool.locked_quote.saturating_sub(quote_released);
+        pool.open_contracts = pool.open_contracts
+            .checked_sub(1)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        emit!(PoolOptionSettled {
+            pool: pool.key(),
+            option_contract: option_contract_key,
+            collateral_returned,
+            proceeds_returned,
+            underlying_released,
+            quote_released,
+        });
+        msg!("Pool option settled: {} collateral and {} proceeds returned", collateral_returned, proceeds_returned);
+        Ok(())
+    }
+}
+
+/// Whether a maker's token account can take a transfer: it still exists and is not frozen
+fn can_receive_tokens(account: &AccountInfo) -> Result<bool> {
+    if account.data_is_empty() || *account.owner != token::ID {
+        return Ok(false);
+    }
+    let data = account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..]).is_ok_and(|token_account| !token_account.is_frozen()))
+}
+
+/// Balance of a proceeds vault, which holds nothing until it has been created
+fn lazy_vault_amount(vault: &AccountInfo) -> Result<u64> {
+    if vault.data_is_empty() {
+        return Ok(0);
+    }
+    let data = vault.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Close an emptied proceeds vault, if it was ever created, and refund whoever paid for it
+fn close_proceeds_vault<'info>(
+    proceeds_vault: &UncheckedAccount<'info>,
+    proceeds_rent_payer: Option<&UncheckedAccount<'info>>,
+    option_contract: Pubkey,
+    bump: u8,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    if proceeds_vault.data_is_empty() {
+        return Ok(());
+    }
+    let proceeds_rent_payer = proceeds_rent_payer.ok_or(OptionsError::ProceedsRentPayerRequired)?;
+
+    let seeds = &[b"proceeds", option_contract.as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+    let close_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: proceeds_vault.to_account_info(),
+            destination: proceeds_rent_payer.to_account_info(),
+            authority: proceeds_vault.to_account_info(),
+        },
+        signer,
+    );
+    token::close_account(close_ctx)
+}
+
+fn validate_fee_rates(
+    protocol_fee_rate: u64,
+    settlement_fee_rate: u64,
+    liquidation_fee_rate: u64,
+) -> Result<()> {
+    require!(protocol_fee_rate <= MAX_FEE_RATE, OptionsError::FeeRateTooHigh);
+    require!(settlement_fee_rate <= MAX_FEE_RATE, OptionsError::FeeRateTooHigh);
+    require!(liquidation_fee_rate <= MAX_FEE_RATE, OptionsError::FeeRateTooHigh);
+    Ok(())
+}
+
+// Data structures
+#[account]
+pub struct ProtocolState {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey, // Pubkey::default() when no handover is pending
+    pub pause_flags: u8,           // PAUSE_* bitmask
+    pub protocol_fee_rate: u64,    // basis points
+    pub settlement_fee_rate: u64,  // basis points
+    pub liquidation_fee_rate: u64, // basis points
     pub total_volume: u64,
+    pub total_fees_collected: u64,
+    pub bump: u8,
+}
+
+impl ProtocolState {
+    /// Fail with the matching error if `operation` (a single PAUSE_* flag) is paused
+    pub fn require_not_paused(&self, operation: u8) -> Result<()> {
+        if self.pause_flags & operation != 0 {
+            return Err(match operation {
+                PAUSE_WRITE => OptionsError::WritingPaused,
+                PAUSE_BUY => OptionsError::BuyingPaused,
+                PAUSE_EXERCISE => OptionsError::ExercisingPaused,
+                _ => OptionsError::ClaimingPaused,
+            }
+            .into());
+        }
+        if self.pause_flags & PAUSE_WITHDRAW_ONLY != 0 && operation & (PAUSE_WRITE | PAUSE_BUY) != 0 {
+            return err!(OptionsError::WithdrawOnly);
+        }
+        Ok(())
+    }
+
+    /// Total premium for `contracts`, rounded up in the writer's favour, and the protocol fee taken out of it
+    pub fn premium_with_fee(
+        &self,
+        premium_per_contract: u64,
+        contracts: u64,
+        underlying_scale: u64,
+    ) -> Result<(u64, u64)> {
+        let total_premium = math::quote_amount(premium_per_contract, contracts, underlying_scale, Rounding::Up)?;
+        let protocol_fee = math::fee_amount(total_premium, self.protocol_fee_rate)?;
+        Ok((total_premium, protocol_fee))
+    }
+}
+
+/// Per-mint breakdown of protocol fees; the tokens sit in the matching `fee_vault` PDA
+#[account]
+pub struct FeeStats {
+    pub mint: Pubkey,
+    pub total_fees_collected: u64,
+    pub total_fees_withdrawn: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct OptionsMarket {
+    pub market_id: u64,
+    pub underlying_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub underlying_decimals: u8,
+    pub quote_decimals: u8,
+    pub underlying_scale: u64,          // 10^underlying_decimals, base units per whole underlying token
+    pub authority: Pubkey,
+    pub total_options_written: u64,
+    pub total_volume: u64,              // premium volume in quote tokens
+    pub open_interest_written: u64,     // written contracts not yet exercised or expired
+    pub open_interest_sold: u64,        // sold contracts not yet exercised or expired
+    pub total_contracts_exercised: u64,
+    pub total_contracts_expired: u64,
     pub is_active: bool,
+    pub pool: Pubkey,                   // liquidity pool, once one is created
+    pub bump: u8,
+}
+
+#[account]
+pub struct WriterAccount {
+    pub writer: Pubkey,
+    pub next_nonce: u64,          // seed of the writer's next option contract
+    pub bump: u8,
+}
+
+#[account]
+pub struct OptionContract {
+    pub writer: Pubkey,
+    pub options_market: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub option_type: OptionType,
+    pub strike_price: u64,
+    pub expiration_timestamp: i64,
+    pub amount: u64,
+    pub premium_per_contract: u64, // flat premium, or the floor of a Dutch auction
+    pub contracts_sold: u64,
+    pub contracts_exercised: u64,
+    pub is_exercised: bool,       // every written contract has been exercised
+    pub is_expired: bool,
+    pub creation_timestamp: i64,
+    pub option_mint: Pubkey,      // SPL mint of the transferable long position
+    pub writer_mint: Pubkey,      // SPL mint of the transferable short position
+    pub nonce: u64,               // writer's sequence number, used as the PDA seed
+    pub auction_start_premium: u64,   // Dutch auction: premium at the start, falling to premium_per_contract
+    pub auction_start_timestamp: i64,
+    pub auction_duration: i64,        // seconds; 0 when sold at the flat premium_per_contract
+    pub rent_payer: Pubkey,       // funded the contract's accounts and gets the rent back on close
+    pub held_premium: u64,        // put premium kept in the collateral vault alongside the collateral
+    pub proceeds_rent_payer: Pubkey, // funded the proceeds vault on first use; default until it exists
+    pub bump: u8,
+}
+
+impl OptionContract {
+    /// Mint of the proceeds vault: quote for calls, underlying for puts
+    pub fn proceeds_mint(&self) -> Pubkey {
+        match self.option_type {
+            OptionType::Call => self.quote_mint,
+            OptionType::Put => self.underlying_mint,
+        }
+    }
+
+    /// Remember who paid for the proceeds vault when it is first created
+    pub fn record_proceeds_rent_payer(&mut self, payer: Pubkey) {
+        if self.proceeds_rent_payer == Pubkey::default() {
+            self.proceeds_rent_payer = payer;
+        }
+    }
+
+    /// Premium per contract at `now`: the flat premium, or the Dutch auction price falling to it
+    pub fn current_premium(&self, now: i64) -> Result<u64> {
+        if self.auction_duration == 0 {
+            return Ok(self.premium_per_contract);
+        }
+        let elapsed = now.saturating_sub(self.auction_start_timestamp).max(0) as u64;
+        math::linear_decay(
+            self.auction_start_premium,
+            self.premium_per_contract,
+            elapsed,
+            self.auction_duration as u64,
+        )
+    }
+
+    /// Record the expiry in the market stats; only the first call after expiry has any effect
+    pub fn record_expiry(&mut self, options_market: &mut OptionsMarket) -> Result<()> {
+        if self.is_expired {
+            return Ok(());
+        }
+
+        // Every contract that was not exercised (sold or unsold) releases its collateral
+        let unexercised_contracts = self.amount
+            .checked_sub(self.contracts_exercised)
+            .ok_or(OptionsError::MathOverflow)?;
+        let unexercised_sold = self.contracts_sold
+            .checked_sub(self.contracts_exercised)
+            .ok_or(OptionsError::MathOverflow)?;
+
+        self.is_expired = true;
+
+        options_market.open_interest_written = options_market.open_interest_written
+            .checked_sub(unexercised_contracts)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.open_interest_sold = options_market.open_interest_sold
+            .checked_sub(unexercised_sold)
+            .ok_or(OptionsError::MathOverflow)?;
+        options_market.total_contracts_expired = options_market.total_contracts_expired
+            .checked_add(unexercised_contracts)
+            .ok_or(OptionsError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct BuyerPosition {
+    pub buyer: Pubkey,
+    pub option_contract: Pubkey,
+    pub contracts_owned: u64,     // contracts not yet exercised
+    pub contracts_exercised: u64,
+    pub premium_paid: u64,        // total across purchases; cost basis = premium_paid / (owned + exercised)
+    pub is_exercised: bool,
+    pub rent_payer: Pubkey,       // funded the position and gets the rent back on close
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+impl OptionType {
+    /// Collateral locked for `amount` contracts: the underlying itself for calls,
+    /// the strike value in quote tokens (rounded up) for puts
+    pub fn collateral_amount(&self, strike_price: u64, amount: u64, underlying_scale: u64) -> Result<u64> {
+        match self {
+            OptionType::Call => Ok(amount),
+            OptionType::Put => math::quote_amount(strike_price, amount, underlying_scale, Rounding::Up),
+        }
+    }
+}
+
+/// Per-market pool that writes options on demand out of LP collateral
+#[account]
+pub struct LiquidityPool {
+    pub options_market: Pubkey,
+    pub volatility_bps: u64,      // annualized volatility the pool prices at
+    pub locked_underlying: u64,   // underlying held in unsettled pool-written contracts' vaults
+    pub locked_quote: u64,        // quote held in unsettled pool-written contracts' vaults
+    pub open_contracts: u64,      // pool-written contracts not yet settled
+    pub reference_price: u64,     // authority-set spot, quote per whole underlying token
+    pub reference_price_updated_at: i64,
+    pub bump: u8,
+}
+
+impl LiquidityPool {
+    /// Fail unless the reference price is set and no older than `MAX_REFERENCE_PRICE_AGE`
+    pub fn require_fresh_reference_price(&self, now: i64) -> Result<()> {
+        require!(
+            self.reference_price > 0
+                && now.saturating_sub(self.reference_price_updated_at) <= MAX_REFERENCE_PRICE_AGE,
+            OptionsError::StaleReferencePrice
+        );
+        Ok(())
+    }
+
+    /// What the pool owes on its open contracts if they were exercised at the reference
+    /// price, as (underlying, quote). `contracts` must be every open contract, sorted by
+    /// address; contracts past expiry owe nothing.
+    pub fn open_liability<'info>(
+        &self,
+        pool_key: Pubkey,
+        contracts: &'info [AccountInfo<'info>],
+        underlying_scale: u64,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        require!(contracts.len() as u64 == self.open_contracts, OptionsError::OpenContractsMismatch);
+        if contracts.is_empty() {
+            return Ok((0, 0));
+        }
+        self.require_fresh_reference_price(now)?;
+
+        let spot = self.reference_price;
+        let mut previous: Option<Pubkey> = None;
+        let (mut underlying_owed, mut quote_owed) = (0u64, 0u64);
+        for info in contracts {
+            // Strictly ascending addresses rule out passing one contract twice
+            if let Some(key) = previous {
+                require!(key < info.key(), OptionsError::OpenContractsMismatch);
+            }
+            previous = Some(info.key());
+
+            let contract = Account::<OptionContract>::try_from(info)?;
+            require_keys_eq!(contract.writer, pool_key, OptionsError::OpenContractsMismatch);
+            if now > contract.expiration_timestamp {
+                continue;
+            }
+
+            let outstanding = contract.amount
+                .checked_sub(contract.contracts_exercised)
+                .ok_or(OptionsError::MathOverflow)?;
+            match contract.option_type {
+                // A call pays out underlying worth (spot - strike) for each contract
+                OptionType::Call if spot > contract.strike_price => {
+                    let owed = math::mul_div(outstanding, spot - contract.strike_price, spot, Rounding::Up)?;
+                    underlying_owed = underlying_owed.checked_add(owed).ok_or(OptionsError::MathOverflow)?;
+                }
+                // A put pays out (strike - spot) in quote for each contract
+                OptionType::Put if contract.strike_price > spot => {
+                    let owed = math::quote_amount(contract.strike_price - spot, outstanding, underlying_scale, Rounding::Up)?;
+                    quote_owed = quote_owed.checked_add(owed).ok_or(OptionsError::MathOverflow)?;
+                }
+                _ => {}
+            }
+        }
+        Ok((underlying_owed, quote_owed))
+    }
+
+    /// Pool assets: the free vault balances plus what is held in its contracts' vaults, less
+    /// the `liability` owed on the open contracts (see `open_liability`)
+    pub fn total_assets(&self, free_underlying: u64, free_quote: u64, liability: (u64, u64)) -> Result<(u64, u64)> {
+        let total_underlying = free_underlying
+            .checked_add(self.locked_underlying)
+            .ok_or(OptionsError::MathOverflow)?
+            .saturating_sub(liability.0);
+        let total_quote = free_quote
+            .checked_add(self.locked_quote)
+            .ok_or(OptionsError::MathOverflow)?
+            .saturating_sub(liability.1);
+        Ok((total_underlying, total_quote))
+    }
+
+    /// LP shares for a deposit. The first deposit sets the share scale at its value in quote
+    /// tokens, pricing the underlying at the reference price. Later deposits get the smaller
+    /// of their two pro-rata shares, so an unbalanced excess accrues to all LPs; a side that
+    /// would earn no shares at all is refused rather than given away.
+    #[allow(clippy::too_many_arguments)]
+    pub fn shares_for_deposit(
+        &self,
+        underlying_amount: u64,
+        quote_amount: u64,
+        free_underlying: u64,
+        free_quote: u64,
+        liability: (u64, u64),
+        lp_supply: u64,
+        underlying_scale: u64,
+        now: i64,
+    ) -> Result<u64> {
+        let (total_underlying, total_quote) = self.total_assets(free_underlying, free_quote, liability)?;
+        if lp_supply == 0 || (total_underlying == 0 && total_quote == 0) {
+            let underlying_value = if underlying_amount > 0 {
+                self.require_fresh_reference_price(now)?;
+                math::quote_amount(self.reference_price, underlying_amount, underlying_scale, Rounding::Down)?
+            } else {
+                0
+            };
+            return underlying_value
+                .checked_add(quote_amount)
+                .ok_or_else(|| OptionsError::MathOverflow.into());
+        }
+
+        let mut shares = u64::MAX;
+        for (amount, total) in [(underlying_amount, total_underlying), (quote_amount, total_quote)] {
+            require!(amount == 0 || total > 0, OptionsError::DepositEarnsNoShares);
+            if total > 0 {
+                let side_shares = math::pro_rata_share(lp_supply, amount, total)?;
+                require!(amount == 0 || side_shares > 0, OptionsError::DepositEarnsNoShares);
+                shares = shares.min(side_shares);
+            }
+        }
+        Ok(shares)
+    }
+
+    /// Move an exercise between the locked totals: the collateral paid out of the contract
+    /// leaves one side, the payment now held in its proceeds vault joins the other
+    pub fn record_exercise(&mut self, option_type: OptionType, contracts: u64, quote_amount: u64) -> Result<()> {
+        let (underlying_out, quote_out, underlying_in, quote_in) = match option_type {
+            OptionType::Call => (contracts, 0, 0, quote_amount),
+            OptionType::Put => (0, quote_amount, contracts, 0),
+        };
+        self.locked_underlying = self.locked_underlying
+            .checked_sub(underlying_out)
+            .and_then(|locked| locked.checked_add(underlying_in))
+            .ok_or(OptionsError::MathOverflow)?;
+        self.locked_quote = self.locked_quote
+            .checked_sub(quote_out)
+            .and_then(|locked| locked.checked_add(quote_in))
+            .ok_or(OptionsError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Model premium per whole underlying token for a strike and time to expiry. Needs a
+    /// fresh reference price and a strike within `MAX_STRIKE_DEVIATION_BPS` of it.
+    pub fn premium_per_contract(
+        &self,
+        option_type: OptionType,
+        strike_price: u64,
+        seconds_to_expiry: u64,
+        now: i64,
+    ) -> Result<u64> {
+        self.require_fresh_reference_price(now)?;
+        let max_deviation = math::mul_div(
+            self.reference_price,
+            MAX_STRIKE_DEVIATION_BPS,
+            math::BPS_DENOMINATOR,
+            Rounding::Down,
+        )?;
+        require!(
+            strike_price.abs_diff(self.reference_price) <= max_deviation,
+            OptionsError::StrikeOutOfRange
+        );
+
+        math::model_premium(
+            option_type,
+            self.reference_price,
+            strike_price,
+            self.volatility_bps,
+            seconds_to_expiry,
+        )
+    }
+}
+
+/// A request for quotes on a block of options; the accepted quote becomes a new `OptionContract`
+#[account]
+pub struct Rfq {
+    pub requester: Pubkey,
+    pub options_market: Pubkey,
+    pub rfq_id: u64,
+    pub side: Side,               // Bid: the requester buys, Ask: the requester writes
+    pub option_type: OptionType,
+    pub strike_price: u64,
+    pub expiration_timestamp: i64,
+    pub amount: u64,
+    pub quote_deadline: i64,      // last moment quotes are accepted
+    pub is_filled: bool,
+    pub option_contract: Pubkey,  // contract written by the accepted quote
+    pub bump: u8,
+}
+
+impl Rfq {
+    /// Mint a maker escrows: the collateral if they write, the quote token if they buy
+    pub fn escrow_mint(&self, options_market: &OptionsMarket) -> Pubkey {
+        match (self.side, self.option_type) {
+            (Side::Bid, OptionType::Call) => options_market.underlying_mint,
+            _ => options_market.quote_mint,
+        }
+    }
+}
+
+#[account]
+pub struct RfqQuote {
+    pub rfq: Pubkey,
+    pub maker: Pubkey,
+    pub premium_per_contract: u64,
+    pub valid_until: i64,
+    pub escrow_amount: u64,       // held in the `rfq_escrow` PDA until accepted or cancelled
     pub bump: u8,
 }
 
-#[account]
-pub struct OptionContract {
-    pub writer: Pubkey,
-    pub underlying_mint: Pubkey,
-    pub quote_mint: Pubkey,
-    pub option_type: OptionType,
-    pub strike_price: u64,
-    pub expiration_timestamp: i64,
-    pub amount: u64,
-    pub premium_per_contract: u64,
-    pub contracts_sold: u64,
-    pub is_exercised: bool,
-    pub is_expired: bool,
-    pub creation_timestamp: i64,
-    pub bump: u8,
+// Context structs
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<ProtocolState>(),
+        seeds = [b"protocol_state"],
+        bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        constraint = protocol_state.authority == authority.key() @ OptionsError::UnauthorizedAuthority
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptProtocolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        constraint = protocol_state.pending_authority != Pubkey::default() @ OptionsError::NoPendingAuthority,
+        constraint = protocol_state.pending_authority == pending_authority.key() @ OptionsError::UnauthorizedAuthority
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+
+    pub pending_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: u64)]
+pub struct CreateOptionsMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<OptionsMarket>(),
+        seeds = [b"options_market", market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    pub underlying_mint: Account<'info, Mint>,
+    pub quote_mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = protocol_state,
+        seeds = [b"fee_vault", quote_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<FeeStats>(),
+        seeds = [b"fee_stats", quote_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_stats: Account<'info, FeeStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump,
+        constraint = protocol_state.authority == authority.key() @ OptionsError::UnauthorizedAuthority
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault", fee_stats.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_stats", fee_stats.mint.as_ref()],
+        bump = fee_stats.bump
+    )]
+    pub fee_stats: Account<'info, FeeStats>,
+    
+    #[account(
+        mut,
+        constraint = treasury.mint == fee_stats.mint @ OptionsError::InvalidMint
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketStatus<'info> {
+    #[account(
+        mut,
+        constraint = options_market.authority == authority.key() @ OptionsError::UnauthorizedMarketAuthority
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(option_type: OptionType)]
+pub struct WriteOption<'info> {
+    #[account(
+        init_if_needed,
+        payer = writer,
+        space = 8 + size_of::<WriterAccount>(),
+        seeds = [b"writer_account", writer.key().as_ref()],
+        bump
+    )]
+    pub writer_account: Box<Account<'info, WriterAccount>>,
+    
+    #[account(
+        init,
+        payer = writer,
+        space = 8 + size_of::<OptionContract>(),
+        seeds = [
+            b"option_contract",
+            writer.key().as_ref(),
+            writer_account.next_nonce.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub option_contract: Box<Account<'info, OptionContract>>,
+    
+    #[account(mut)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    #[account(constraint = underlying_mint.key() == options_market.underlying_mint @ OptionsError::InvalidMint)]
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    #[account(constraint = quote_mint.key() == options_market.quote_mint @ OptionsError::InvalidMint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    /// Underlying mint for calls, quote mint for puts
+    #[account(
+        constraint = collateral_mint.key() == match option_type {
+            OptionType::Call => underlying_mint.key(),
+            OptionType::Put => quote_mint.key(),
+        } @ OptionsError::InvalidMint
+    )]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+    
+    // Only the vault for the option type's collateral is created
+    #[account(
+        init,
+        payer = writer,
+        token::mint = collateral_mint,
+        token::authority = collateral_vault,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = writer,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = option_contract,
+        seeds = [b"option_mint", option_contract.key().as_ref()],
+        bump
+    )]
+    pub option_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = writer,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = option_contract,
+        seeds = [b"writer_mint", option_contract.key().as_ref()],
+        bump
+    )]
+    pub writer_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = writer,
+        associated_token::mint = writer_mint,
+        associated_token::authority = writer
+    )]
+    pub writer_claim_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = writer_token_account.mint == underlying_mint.key() @ OptionsError::InvalidMint
+    )]
+    pub writer_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = writer_quote_account.mint == quote_mint.key() @ OptionsError::InvalidMint
+    )]
+    pub writer_quote_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub writer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyOption<'info> {
+    #[account(mut)]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + size_of::<BuyerPosition>(),
+        seeds = [
+            b"buyer_position",
+            buyer.key().as_ref(),
+            option_contract.key().as_ref()
+        ],
+        bump
+    )]
+    pub buyer_position: Account<'info, BuyerPosition>,
+    
+    #[account(
+        mut,
+        constraint = buyer_quote_account.mint == option_contract.quote_mint @ OptionsError::InvalidMint
+    )]
+    pub buyer_quote_account: Account<'info, TokenAccount>,
+    
+    /// Receives put premiums
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    /// Quote mint for calls, underlying mint for puts
+    #[account(constraint = proceeds_mint.key() == option_contract.proceeds_mint() @ OptionsError::InvalidMint)]
+    pub proceeds_mint: Box<Account<'info, Mint>>,
+    
+    /// Receives call premiums; created by the first buy or exercise
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        token::mint = proceeds_mint,
+        token::authority = proceeds_vault,
+        seeds = [b"proceeds", option_contract.key().as_ref()],
+        bump
+    )]
+    pub proceeds_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = option_mint.key() == option_contract.option_mint @ OptionsError::InvalidMint
+    )]
+    pub option_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = buyer_option_token_account.mint == option_contract.option_mint @ OptionsError::InvalidMint
+    )]
+    pub buyer_option_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault", option_contract.quote_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_stats", option_contract.quote_mint.as_ref()],
+        bump = fee_stats.bump
+    )]
+    pub fee_stats: Account<'info, FeeStats>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExerciseOption<'info> {
+    #[account(mut)]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_position",
+            buyer.key().as_ref(),
+            option_contract.key().as_ref()
+        ],
+        bump = buyer_position.bump
+    )]
+    pub buyer_position: Option<Account<'info, BuyerPosition>>,
+    
+    #[account(
+        mut,
+        constraint = option_mint.key() == option_contract.option_mint @ OptionsError::InvalidMint
+    )]
+    pub option_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = buyer_option_token_account.mint == option_contract.option_mint @ OptionsError::InvalidMint
+    )]
+    pub buyer_option_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = buyer_token_account.mint == option_contract.underlying_mint @ OptionsError::InvalidMint
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = buyer_quote_account.mint == option_contract.quote_mint @ OptionsError::InvalidMint
+    )]
+    pub buyer_quote_account: Account<'info, TokenAccount>,
+    
+    /// Quote mint for calls, underlying mint for puts
+    #[account(constraint = proceeds_mint.key() == option_contract.proceeds_mint() @ OptionsError::InvalidMint)]
+    pub proceeds_mint: Box<Account<'info, Mint>>,
+    
+    /// Receives the strike payment for calls, the underlying for puts; created on first use
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        token::mint = proceeds_mint,
+        token::authority = proceeds_vault,
+        seeds = [b"proceeds", option_contract.key().as_ref()],
+        bump
+    )]
+    pub proceeds_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault", option_contract.quote_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_stats", option_contract.quote_mint.as_ref()],
+        bump = fee_stats.bump
+    )]
+    pub fee_stats: Account<'info, FeeStats>,
+    
+    /// Required when the contract was written by the market's pool
+    #[account(
+        mut,
+        constraint = pool.key() == option_contract.writer @ OptionsError::UnauthorizedWriter
+    )]
+    pub pool: Option<Box<Account<'info, LiquidityPool>>>,
+    
+    /// Holder of the option tokens being exercised
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimExpiredOption<'info> {
+    #[account(mut)]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: the proceeds vault, which does not exist until the first buy or exercise
+    #[account(
+        mut,
+        seeds = [b"proceeds", option_contract.key().as_ref()],
+        bump
+    )]
+    pub proceeds_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = writer_mint.key() == option_contract.writer_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = writer_claim_token_account.mint == option_contract.writer_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_claim_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_token_account.mint == option_contract.underlying_mint @ OptionsError::InvalidMint,
+        constraint = writer_token_account.owner == writer.key() @ OptionsError::InvalidOwner
+    )]
+    pub writer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_quote_account.mint == option_contract.quote_mint @ OptionsError::InvalidMint,
+        constraint = writer_quote_account.owner == writer.key() @ OptionsError::InvalidOwner
+    )]
+    pub writer_quote_account: Account<'info, TokenAccount>,
+    
+    /// Holder of the writer tokens being redeemed
+    pub writer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePremium<'info> {
+    #[account(
+        mut,
+        constraint = option_contract.writer == writer.key() @ OptionsError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    pub writer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartDutchAuction<'info> {
+    #[account(
+        mut,
+        constraint = option_contract.writer == writer.key() @ OptionsError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+
+    pub writer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelUnsold<'info> {
+    #[account(
+        mut,
+        constraint = option_contract.writer == writer.key() @ OptionsError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_mint.key() == option_contract.writer_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = writer_claim_token_account.mint == option_contract.writer_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_claim_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_token_account.mint == option_contract.underlying_mint @ OptionsError::InvalidMint,
+        constraint = writer_token_account.owner == writer.key() @ OptionsError::InvalidOwner
+    )]
+    pub writer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_quote_account.mint == option_contract.quote_mint @ OptionsError::InvalidMint,
+        constraint = writer_quote_account.owner == writer.key() @ OptionsError::InvalidOwner
+    )]
+    pub writer_quote_account: Account<'info, TokenAccount>,
+    
+    pub writer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOptionContract<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = option_contract.writer == writer.key() @ OptionsError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: the proceeds vault, which does not exist until the first buy or exercise
+    #[account(
+        mut,
+        seeds = [b"proceeds", option_contract.key().as_ref()],
+        bump
+    )]
+    pub proceeds_vault: UncheckedAccount<'info>,
+    
+    pub writer: Signer<'info>,
+    
+    /// CHECK: paid the rent at write time and receives it back
+    #[account(mut, address = option_contract.rent_payer @ OptionsError::InvalidOwner)]
+    pub rent_payer: UncheckedAccount<'info>,
+    
+    /// CHECK: paid for the proceeds vault and gets its rent back; required once the vault exists
+    #[account(mut, address = option_contract.proceeds_rent_payer @ OptionsError::InvalidOwner)]
+    pub proceeds_rent_payer: Option<UncheckedAccount<'info>>,
+    
+    /// The writer's emptied writer token account, closed along with the contract when passed
+    #[account(
+        mut,
+        constraint = writer_claim_token_account.mint == option_contract.writer_mint @ OptionsError::InvalidMint,
+        constraint = writer_claim_token_account.owner == writer.key() @ OptionsError::InvalidOwner,
+        constraint = writer_claim_token_account.amount == 0 @ OptionsError::WriterTokensOutstanding
+    )]
+    pub writer_claim_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBuyerPosition<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = buyer_position.buyer == buyer.key() @ OptionsError::InvalidOwner
+    )]
+    pub buyer_position: Account<'info, BuyerPosition>,
+    
+    /// CHECK: the position's contract, only deserialized while it has not been closed
+    #[account(address = buyer_position.option_contract @ OptionsError::ContractMismatch)]
+    pub option_contract: UncheckedAccount<'info>,
+    
+    pub buyer: Signer<'info>,
+    
+    /// CHECK: paid the rent when the position was opened and receives it back
+    #[account(mut, address = buyer_position.rent_payer @ OptionsError::InvalidOwner)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleExpiredOption<'info> {
+    #[account(mut)]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: the proceeds vault, which does not exist until the first buy or exercise
+    #[account(
+        mut,
+        seeds = [b"proceeds", option_contract.key().as_ref()],
+        bump
+    )]
+    pub proceeds_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = writer_mint.key() == option_contract.writer_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_mint: Account<'info, Mint>,
+    
+    /// Writer token account being settled; must have delegated to the option contract
+    #[account(
+        mut,
+        constraint = writer_claim_token_account.mint == option_contract.writer_mint @ OptionsError::InvalidMint,
+        constraint = writer_claim_token_account.delegate == COption::Some(option_contract.key()) @ OptionsError::NothingToSettle
+    )]
+    pub writer_claim_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_token_account.mint == option_contract.underlying_mint @ OptionsError::InvalidMint,
+        constraint = writer_token_account.owner == writer_claim_token_account.owner @ OptionsError::InvalidOwner
+    )]
+    pub writer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = writer_quote_account.mint == option_contract.quote_mint @ OptionsError::InvalidMint,
+        constraint = writer_quote_account.owner == writer_claim_token_account.owner @ OptionsError::InvalidOwner
+    )]
+    pub writer_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == option_contract.underlying_mint @ OptionsError::InvalidMint
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = keeper_quote_account.mint == option_contract.quote_mint @ OptionsError::InvalidMint
+    )]
+    pub keeper_quote_account: Account<'info, TokenAccount>,
+    
+    pub keeper: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(rfq_id: u64)]
+pub struct CreateRfq<'info> {
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + size_of::<Rfq>(),
+        seeds = [b"rfq", requester.key().as_ref(), rfq_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rfq: Account<'info, Rfq>,
+    
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitRfqQuote<'info> {
+    pub rfq: Box<Account<'info, Rfq>>,
+    
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + size_of::<RfqQuote>(),
+        seeds = [b"rfq_quote", rfq.key().as_ref(), maker.key().as_ref()],
+        bump
+    )]
+    pub rfq_quote: Box<Account<'info, RfqQuote>>,
+    
+    #[account(constraint = options_market.key() == rfq.options_market @ OptionsError::MarketMismatch)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    /// Collateral mint when the requester buys, quote mint when they write
+    #[account(constraint = escrow_mint.key() == rfq.escrow_mint(&options_market) @ OptionsError::InvalidMint)]
+    pub escrow_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = maker,
+        token::mint = escrow_mint,
+        token::authority = rfq_quote,
+        seeds = [b"rfq_escrow", rfq_quote.key().as_ref()],
+        bump
+    )]
+    pub rfq_escrow: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = maker_token_account.mint == escrow_mint.key() @ OptionsError::InvalidMint
+    )]
+    pub maker_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
-#[account]
-pub struct BuyerPosition {
-    pub buyer: Pubkey,
-    pub option_contract: Pubkey,
-    pub contracts_owned: u64,
-    pub premium_paid: u64,
-    pub is_exercised: bool,
-    pub bump: u8,
+#[derive(Accounts)]
+pub struct AcceptRfqQuote<'info> {
+    #[account(
+        mut,
+        constraint = rfq.requester == requester.key() @ OptionsError::InvalidOwner,
+        constraint = !rfq.is_filled @ OptionsError::RfqFilled
+    )]
+    pub rfq: Box<Account<'info, Rfq>>,
+    
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"rfq_quote", rfq.key().as_ref(), rfq_quote.maker.as_ref()],
+        bump = rfq_quote.bump
+    )]
+    pub rfq_quote: Box<Account<'info, RfqQuote>>,
+    
+    #[account(
+        mut,
+        seeds = [b"rfq_escrow", rfq_quote.key().as_ref()],
+        bump
+    )]
+    pub rfq_escrow: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: receives the quote's rent; must be the quote's maker
+    #[account(mut, address = rfq_quote.maker @ OptionsError::InvalidOwner)]
+    pub maker: UncheckedAccount<'info>,
+    
+    /// CHECK: the maker when the requester buys, the requester when they write
+    #[account(
+        constraint = writer.key() == match rfq.side {
+            Side::Bid => rfq_quote.maker,
+            Side::Ask => rfq.requester,
+        } @ OptionsError::InvalidOwner
+    )]
+    pub writer: UncheckedAccount<'info>,
+    
+    /// CHECK: the requester when they buy, the maker when the requester writes
+    #[account(
+        constraint = buyer.key() == match rfq.side {
+            Side::Bid => rfq.requester,
+            Side::Ask => rfq_quote.maker,
+        } @ OptionsError::InvalidOwner
+    )]
+    pub buyer: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = 8 + size_of::<WriterAccount>(),
+        seeds = [b"writer_account", writer.key().as_ref()],
+        bump
+    )]
+    pub writer_account: Box<Account<'info, WriterAccount>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + size_of::<OptionContract>(),
+        seeds = [
+            b"option_contract",
+            writer.key().as_ref(),
+            writer_account.next_nonce.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub option_contract: Box<Account<'info, OptionContract>>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == rfq.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    #[account(constraint = underlying_mint.key() == options_market.underlying_mint @ OptionsError::InvalidMint)]
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    #[account(constraint = quote_mint.key() == options_market.quote_mint @ OptionsError::InvalidMint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    /// Underlying mint for calls, quote mint for puts
+    #[account(
+        constraint = collateral_mint.key() == match rfq.option_type {
+            OptionType::Call => underlying_mint.key(),
+            OptionType::Put => quote_mint.key(),
+        } @ OptionsError::InvalidMint
+    )]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        token::mint = collateral_mint,
+        token::authority = collateral_vault,
+        seeds = [b"collateral", option_contract.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = option_contract,
+        seeds = [b"option_mint", option_contract.key().as_ref()],
+        bump
+    )]
+    pub option_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = option_contract,
+        seeds = [b"writer_mint", option_contract.key().as_ref()],
+        bump
+    )]
+    pub writer_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        associated_token::mint = writer_mint,
+        associated_token::authority = writer
+    )]
+    pub writer_claim_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        associated_token::mint = option_mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_option_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + size_of::<BuyerPosition>(),
+        seeds = [
+            b"buyer_position",
+            buyer.key().as_ref(),
+            option_contract.key().as_ref()
+        ],
+        bump
+    )]
+    pub buyer_position: Box<Account<'info, BuyerPosition>>,
+    
+    /// Only needed when the requester writes
+    #[account(
+        mut,
+        constraint = requester_collateral_account.mint == collateral_mint.key() @ OptionsError::InvalidMint,
+        constraint = requester_collateral_account.owner == requester.key() @ OptionsError::InvalidOwner
+    )]
+    pub requester_collateral_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        constraint = requester_quote_account.mint == quote_mint.key() @ OptionsError::InvalidMint,
+        constraint = requester_quote_account.owner == requester.key() @ OptionsError::InvalidOwner
+    )]
+    pub requester_quote_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = maker_quote_account.mint == quote_mint.key() @ OptionsError::InvalidMint,
+        constraint = maker_quote_account.owner == rfq_quote.maker @ OptionsError::InvalidOwner
+    )]
+    pub maker_quote_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Receives what is left in the escrow; the maker's account for the escrowed mint
+    #[account(
+        mut,
+        constraint = maker_token_account.mint == rfq_escrow.mint @ OptionsError::InvalidMint,
+        constraint = maker_token_account.owner == rfq_quote.maker @ OptionsError::InvalidOwner
+    )]
+    pub maker_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault", quote_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_stats", quote_mint.key().as_ref()],
+        bump = fee_stats.bump
+    )]
+    pub fee_stats: Box<Account<'info, FeeStats>>,
+    
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRfqQuote<'info> {
+    pub rfq: Account<'info, Rfq>,
+    
+    #[account(
+        mut,
+        close = maker,
+        seeds = [b"rfq_quote", rfq.key().as_ref(), rfq_quote.maker.as_ref()],
+        bump = rfq_quote.bump
+    )]
+    pub rfq_quote: Account<'info, RfqQuote>,
+    
+    #[account(
+        mut,
+        seeds = [b"rfq_escrow", rfq_quote.key().as_ref()],
+        bump
+    )]
+    pub rfq_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = maker_token_account.mint == rfq_escrow.mint @ OptionsError::InvalidMint,
+        constraint = maker_token_account.owner == rfq_quote.maker @ OptionsError::InvalidOwner
+    )]
+    pub maker_token_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: receives the escrow and quote rent; must be the quote's maker
+    #[account(mut, address = rfq_quote.maker @ OptionsError::InvalidOwner)]
+    pub maker: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<LiquidityPool>(),
+        seeds = [b"pool", options_market.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+    
+    #[account(
+        mut,
+        constraint = options_market.authority == authority.key() @ OptionsError::UnauthorizedMarketAuthority
+    )]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(constraint = underlying_mint.key() == options_market.underlying_mint @ OptionsError::InvalidMint)]
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    #[account(constraint = quote_mint.key() == options_market.quote_mint @ OptionsError::InvalidMint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = quote_mint.decimals,
+        mint::authority = pool,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = underlying_mint,
+        token::authority = pool,
+        seeds = [b"pool_underlying", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_underlying_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = pool,
+        seeds = [b"pool_quote", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolVolatility<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", options_market.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, LiquidityPool>,
+    
+    #[account(constraint = options_market.authority == authority.key() @ OptionsError::UnauthorizedMarketAuthority)]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(
+        seeds = [b"pool", pool.options_market.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+    
+    #[account(address = pool.options_market @ OptionsError::MarketMismatch)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_underlying", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_underlying_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_quote", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = depositor_underlying_account.mint == pool_underlying_vault.mint @ OptionsError::InvalidMint
+    )]
+    pub depositor_underlying_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = depositor_quote_account.mint == pool_quote_vault.mint @ OptionsError::InvalidMint
+    )]
+    pub depositor_quote_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = lp_mint,
+        associated_token::authority = depositor
+    )]
+    pub depositor_lp_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum OptionType {
-    Call,
-    Put,
+#[derive(Accounts)]
+pub struct WithdrawLiquidity<'info> {
+    #[account(
+        seeds = [b"pool", pool.options_market.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+    
+    #[account(address = pool.options_market @ OptionsError::MarketMismatch)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_underlying", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_underlying_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_quote", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = depositor_underlying_account.mint == pool_underlying_vault.mint @ OptionsError::InvalidMint
+    )]
+    pub depositor_underlying_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = depositor_quote_account.mint == pool_quote_vault.mint @ OptionsError::InvalidMint
+    )]
+    pub depositor_quote_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = depositor_lp_account.mint == lp_mint.key() @ OptionsError::InvalidMint
+    )]
+    pub depositor_lp_account: Box<Account<'info, TokenAccount>>,
+    
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
 }
 
-// Context structs
 #[derive(Accounts)]
-pub struct InitializeProtocol<'info> {
+#[instruction(option_type: OptionType)]
+pub struct BuyFromPool<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool", options_market.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_underlying", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_underlying_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_quote", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + size_of::<WriterAccount>(),
+        seeds = [b"writer_account", pool.key().as_ref()],
+        bump
+    )]
+    pub writer_account: Box<Account<'info, WriterAccount>>,
+    
     #[account(
         init,
-        payer = authority,
-        space = 8 + size_of::<ProtocolState>(),
+        payer = buyer,
+        space = 8 + size_of::<OptionContract>(),
+        seeds = [
+            b"option_contract",
+            pool.key().as_ref(),
+            writer_account.next_nonce.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub option_contract: Box<Account<'info, OptionContract>>,
+    
+    #[account(mut)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        mut,
         seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    #[account(constraint = underlying_mint.key() == options_market.underlying_mint @ OptionsError::InvalidMint)]
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    #[account(constraint = quote_mint.key() == options_market.quote_mint @ OptionsError::InvalidMint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    /// Underlying mint for calls, quote mint for puts
+    #[account(
+        constraint = collateral_mint.key() == match option_type {
+            OptionType::Call => underlying_mint.key(),
+            OptionType::Put => quote_mint.key(),
+        } @ OptionsError::InvalidMint
+    )]
+    pub collateral_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        token::mint = collateral_mint,
+        token::authority = collateral_vault,
+        seeds = [b"collateral", option_contract.key().as_ref()],
         bump
     )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = option_contract,
+        seeds = [b"option_mint", option_contract.key().as_ref()],
+        bump
+    )]
+    pub option_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = option_contract,
+        seeds = [b"writer_mint", option_contract.key().as_ref()],
+        bump
+    )]
+    pub writer_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = writer_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_writer_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = option_mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_option_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + size_of::<BuyerPosition>(),
+        seeds = [
+            b"buyer_position",
+            buyer.key().as_ref(),
+            option_contract.key().as_ref()
+        ],
+        bump
+    )]
+    pub buyer_position: Box<Account<'info, BuyerPosition>>,
+    
+    #[account(
+        mut,
+        constraint = buyer_quote_account.mint == quote_mint.key() @ OptionsError::InvalidMint
+    )]
+    pub buyer_quote_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault", quote_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_stats", quote_mint.key().as_ref()],
+        bump = fee_stats.bump
+    )]
+    pub fee_stats: Box<Account<'info, FeeStats>>,
     
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub buyer: Signer<'info>,
     
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct CreateOptionsMarket<'info> {
+pub struct SettlePoolOption<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = 8 + size_of::<OptionsMarket>(),
-        seeds = [b"options_market", market_id.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"pool", options_market.key().as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+    
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = option_contract.writer == pool.key() @ OptionsError::UnauthorizedWriter
+    )]
+    pub option_contract: Box<Account<'info, OptionContract>>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"collateral", option_contract.key().as_ref()],
         bump
     )]
-    pub options_market: Account<'info, OptionsMarket>,
+    pub collateral_vault: Box<Account<'info, TokenAccount>>,
     
-    pub underlying_mint: Account<'info, Mint>,
-    pub quote_mint: Account<'info, Mint>,
+    /// CHECK: the proceeds vault, which does not exist until the first buy or exercise
+    #[account(
+        mut,
+        seeds = [b"proceeds", option_contract.key().as_ref()],
+        bump
+    )]
+    pub proceeds_vault: UncheckedAccount<'info>,
     
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = writer_mint.key() == option_contract.writer_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_mint: Box<Account<'info, Mint>>,
     
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        associated_token::mint = writer_mint,
+        associated_token::authority = pool
+    )]
+    pub pool_writer_token_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_underlying", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_underlying_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_quote", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: the buyer who funded the contract's accounts; receives their rent back
+    #[account(mut, address = option_contract.rent_payer @ OptionsError::InvalidOwner)]
+    pub rent_payer: UncheckedAccount<'info>,
+    
+    /// CHECK: paid for the proceeds vault and gets its rent back; required once the vault exists
+    #[account(mut, address = option_contract.proceeds_rent_payer @ OptionsError::InvalidOwner)]
+    pub proceeds_rent_payer: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-#[instruction(timestamp_seed: i64)]
-pub struct WriteOption<'info> {
+pub struct MintUnsoldOptions<'info> {
     #[account(
-        init,
-        payer = writer,
-        space = 8 + size_of::<OptionContract>(),
-        seeds = [
-            b"option_contract",
-            writer.key().as_ref(),
-            underlying_mint.key().as_ref(),
-            timestamp_seed.to_le_bytes().as_ref()
-        ],
+        mut,
+        constraint = option_contract.writer == writer.key() @ OptionsError::UnauthorizedWriter
+    )]
+    pub option_contract: Account<'info, OptionContract>,
+    
+    #[account(
+        mut,
+        constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch
+    )]
+    pub options_market: Account<'info, OptionsMarket>,
+    
+    #[account(
+        seeds = [b"protocol_state"],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        constraint = option_mint.key() == option_contract.option_mint @ OptionsError::InvalidMint
+    )]
+    pub option_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = writer_option_token_account.mint == option_contract.option_mint @ OptionsError::InvalidMint
+    )]
+    pub writer_option_token_account: Account<'info, TokenAccount>,
+    
+    pub writer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateOrderBook<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"order_book", option_contract.key().as_ref()],
         bump
     )]
-    pub option_contract: Account<'info, OptionContract>,
+    pub order_book: Box<Account<'info, OrderBook>>,
     
-    #[account(mut)]
-    pub options_market: Account<'info, OptionsMarket>,
+    pub option_contract: Box<Account<'info, OptionContract>>,
     
-    pub underlying_mint: Account<'info, Mint>,
-    pub quote_mint: Account<'info, Mint>,
+    #[account(constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
+    
+    #[account(constraint = option_mint.key() == option_contract.option_mint @ OptionsError::InvalidMint)]
+    pub option_mint: Box<Account<'info, Mint>>,
+    
+    #[account(constraint = quote_mint.key() == option_contract.quote_mint @ OptionsError::InvalidMint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
     
+    /// Escrow for asks and for option tokens owed to bid makers
     #[account(
         init,
-        payer = writer,
-        token::mint = underlying_mint,
-        token::authority = collateral_vault,
-        seeds = [b"collateral", option_contract.key().as_ref()],
+        payer = payer,
+        token::mint = option_mint,
+        token::authority = order_book,
+        seeds = [b"book_options", order_book.key().as_ref()],
         bump
     )]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    pub option_vault: Box<Account<'info, TokenAccount>>,
     
+    /// Escrow for bids and for quote owed to ask makers
     #[account(
         init,
-        payer = writer,
+        payer = payer,
         token::mint = quote_mint,
-        token::authority = quote_collateral_vault,
-        seeds = [b"quote_collateral", option_contract.key().as_ref()],
+        token::authority = order_book,
+        seeds = [b"book_quote", order_book.key().as_ref()],
         bump
     )]
-    pub quote_collateral_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub writer_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub writer_quote_account: Account<'info, TokenAccount>,
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
     
     #[account(mut)]
-    pub writer: Signer<'info>,
+    pub payer: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -577,136 +4776,499 @@ pub struct WriteOption<'info> {
 }
 
 #[derive(Accounts)]
-pub struct BuyOption<'info> {
-    #[account(mut)]
-    pub option_contract: Account<'info, OptionContract>,
+pub struct PlaceOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"order_book", option_contract.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    
+    pub option_contract: Box<Account<'info, OptionContract>>,
+    
+    #[account(constraint = options_market.key() == option_contract.options_market @ OptionsError::MarketMismatch)]
+    pub options_market: Box<Account<'info, OptionsMarket>>,
     
     #[account(
         mut,
         seeds = [b"protocol_state"],
         bump = protocol_state.bump
     )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    pub protocol_state: Box<Account<'info, ProtocolState>>,
     
     #[account(
-        init,
-        payer = buyer,
-        space = 8 + size_of::<BuyerPosition>(),
-        seeds = [
-            b"buyer_position",
-            buyer.key().as_ref(),
-            option_contract.key().as_ref()
-        ],
+        mut,
+        seeds = [b"book_options", order_book.key().as_ref()],
         bump
     )]
-    pub buyer_position: Account<'info, BuyerPosition>,
+    pub option_vault: Box<Account<'info, TokenAccount>>,
     
-    #[account(mut)]
-    pub buyer_quote_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"book_quote", order_book.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
     
-    #[account(mut)]
-    pub writer_quote_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_vault", option_contract.quote_mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
     
-    #[account(mut)]
-    pub protocol_fee_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_stats", option_contract.quote_mint.as_ref()],
+        bump = fee_stats.bump
+    )]
+    pub fee_stats: Box<Account<'info, FeeStats>>,
     
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+    // Associated token accounts, so the crank can find a maker's payout accounts
+    #[account(
+        mut,
+        associated_token::mint = order_book.option_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_option_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = order_book.quote_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_quote_account: Box<Account<'info, TokenAccount>>,
     
+    pub owner: Signer<'info>,
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ExerciseOption<'info> {
+pub struct CancelOrder<'info> {
     #[account(mut)]
-    pub option_contract: Account<'info, OptionContract>,
+    pub order_book: Box<Account<'info, OrderBook>>,
     
     #[account(
         mut,
-        seeds = [
-            b"buyer_position",
-            buyer.key().as_ref(),
-            option_contract.key().as_ref()
-        ],
-        bump = buyer_position.bump
+        seeds = [b"book_options", order_book.key().as_ref()],
+        bump
     )]
-    pub buyer_position: Account<'info, BuyerPosition>,
+    pub option_vault: Box<Account<'info, TokenAccount>>,
     
     #[account(
-        seeds = [b"protocol_state"],
-        bump = protocol_state.bump
+        mut,
+        seeds = [b"book_quote", order_book.key().as_ref()],
+        bump
     )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
-        seeds = [b"collateral", option_contract.key().as_ref()],
-        bump
+        associated_token::mint = order_book.option_mint,
+        associated_token::authority = owner
     )]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    pub owner_option_account: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
-        seeds = [b"quote_collateral", option_contract.key().as_ref()],
-        bump
+        associated_token::mint = order_book.quote_mint,
+        associated_token::authority = owner
     )]
-    pub quote_collateral_vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub buyer_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub buyer_quote_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub writer_token_account: Account<'info, TokenAccount>,
+    pub owner_quote_account: Box<Account<'info, TokenAccount>>,
     
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeEvents<'info> {
     #[account(mut)]
-    pub writer_quote_account: Account<'info, TokenAccount>,
+    pub order_book: Box<Account<'info, OrderBook>>,
     
-    #[account(mut)]
-    pub protocol_fee_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"book_options", order_book.key().as_ref()],
+        bump
+    )]
+    pub option_vault: Box<Account<'info, TokenAccount>>,
     
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"book_quote", order_book.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
     
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimExpiredOption<'info> {
+pub struct ClaimParkedFill<'info> {
     #[account(mut)]
-    pub option_contract: Account<'info, OptionContract>,
+    pub order_book: Box<Account<'info, OrderBook>>,
     
     #[account(
         mut,
-        seeds = [b"collateral", option_contract.key().as_ref()],
+        seeds = [b"book_options", order_book.key().as_ref()],
         bump
     )]
-    pub collateral_vault: Account<'info, TokenAccount>,
+    pub option_vault: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
-        seeds = [b"quote_collateral", option_contract.key().as_ref()],
+        seeds = [b"book_quote", order_book.key().as_ref()],
         bump
     )]
-    pub quote_collateral_vault: Account<'info, TokenAccount>,
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
     
-    #[account(mut)]
-    pub writer_token_account: Account<'info, TokenAccount>,
+    /// Receives parked option tokens; required when any are owed
+    #[account(
+        mut,
+        constraint = maker_option_account.mint == order_book.option_mint @ OptionsError::InvalidMint,
+        constraint = maker_option_account.owner == maker.key() @ OptionsError::InvalidOwner
+    )]
+    pub maker_option_account: Option<Box<Account<'info, TokenAccount>>>,
     
+    /// Receives parked quote; required when any is owed
     #[account(
         mut,
-        constraint = writer_quote_account.owner == writer.key() @ OptionsError::InvalidOwner
+        constraint = maker_quote_account.mint == order_book.quote_mint @ OptionsError::InvalidMint,
+        constraint = maker_quote_account.owner == maker.key() @ OptionsError::InvalidOwner
     )]
-    pub writer_quote_account: Account<'info, TokenAccount>,
+    pub maker_quote_account: Option<Box<Account<'info, TokenAccount>>>,
     
-    #[account(constraint = option_contract.writer == writer.key() @ OptionsError::UnauthorizedWriter)]
-    pub writer: Signer<'info>,
+    pub maker: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
+// Events
+
+#[event]
+pub struct ProtocolInitialized {
+    pub authority: Pubkey,
+    pub protocol_fee_rate: u64,
+    pub settlement_fee_rate: u64,
+    pub liquidation_fee_rate: u64,
+}
+
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub authority: Pubkey,
+    pub protocol_fee_rate: u64,
+    pub settlement_fee_rate: u64,
+    pub liquidation_fee_rate: u64,
+}
+
+#[event]
+pub struct ProtocolAuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct ProtocolAuthorityAccepted {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PauseFlagsSet {
+    pub authority: Pubkey,
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub total_fees_withdrawn: u64,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub options_market: Pubkey,
+    pub market_id: u64,
+    pub underlying_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub underlying_decimals: u8,
+    pub quote_decimals: u8,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MarketStatusChanged {
+    pub options_market: Pubkey,
+    pub market_id: u64,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct OptionWritten {
+    pub option_contract: Pubkey,
+    pub options_market: Pubkey,
+    pub writer: Pubkey,
+    pub nonce: u64,
+    pub option_type: OptionType,
+    pub strike_price: u64,
+    pub expiration_timestamp: i64,
+    pub amount: u64,
+    pub premium_per_contract: u64,
+    pub collateral_amount: u64,   // underlying for calls, quote for puts
+    pub option_mint: Pubkey,
+    pub writer_mint: Pubkey,
+}
+
+#[event]
+pub struct PremiumUpdated {
+    pub option_contract: Pubkey,
+    pub writer: Pubkey,
+    pub old_premium: u64,
+    pub new_premium: u64,
+}
+
+#[event]
+pub struct DutchAuctionStarted {
+    pub option_contract: Pubkey,
+    pub writer: Pubkey,
+    pub start_premium: u64,
+    pub floor_premium: u64,
+    pub start_timestamp: i64,
+    pub duration: i64,
+}
+
+#[event]
+pub struct OptionPurchased {
+    pub option_contract: Pubkey,
+    pub options_market: Pubkey,
+    pub buyer: Pubkey,
+    pub contracts: u64,
+    pub premium_per_contract: u64,
+    pub total_premium: u64,       // paid by the buyer, protocol fee included
+    pub protocol_fee: u64,
+    pub contracts_sold: u64,      // contract total after this purchase
+}
+
+#[event]
+pub struct OptionExercised {
+    pub option_contract: Pubkey,
+    pub options_market: Pubkey,
+    pub holder: Pubkey,
+    pub option_type: OptionType,
+    pub contracts: u64,           // underlying delivered (calls) or received (puts)
+    pub quote_amount: u64,        // strike paid (calls) or paid out (puts)
+    pub settlement_fee: u64,
+    pub contracts_exercised: u64, // contract total after this exercise
+}
+
+#[event]
+pub struct OptionExpiredClaimed {
+    pub option_contract: Pubkey,
+    pub options_market: Pubkey,
+    pub holder: Pubkey,
+    pub writer_tokens_burned: u64,
+    pub collateral_amount: u64,
+    pub proceeds_amount: u64,     // premium and exercise proceeds
+}
+
+#[event]
+pub struct ExpiredOptionSettled {
+    pub option_contract: Pubkey,
+    pub options_market: Pubkey,
+    pub holder: Pubkey,
+    pub keeper: Pubkey,
+    pub writer_tokens_burned: u64,
+    pub collateral_amount: u64,   // keeper fee included
+    pub proceeds_amount: u64,     // premium and exercise proceeds
+    pub keeper_fee: u64,
+}
+
+#[event]
+pub struct UnsoldCancelled {
+    pub option_contract: Pubkey,
+    pub writer: Pubkey,
+    pub contracts_cancelled: u64,
+    pub collateral_returned: u64,
+    pub remaining_amount: u64,
+}
+
+#[event]
+pub struct OptionContractClosed {
+    pub option_contract: Pubkey,
+    pub writer: Pubkey,
+}
+
+#[event]
+pub struct BuyerPositionClosed {
+    pub buyer_position: Pubkey,
+    pub option_contract: Pubkey,
+    pub buyer: Pubkey,
+}
+
+#[event]
+pub struct UnsoldOptionsMinted {
+    pub option_contract: Pubkey,
+    pub writer: Pubkey,
+    pub amount: u64,
+    pub contracts_sold: u64,
+}
+
+#[event]
+pub struct OrderBookCreated {
+    pub order_book: Pubkey,
+    pub option_contract: Pubkey,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub order_book: Pubkey,
+    pub owner: Pubkey,
+    pub side: Side,
+    pub price: u64,
+    pub quantity: u64,
+    pub filled_quantity: u64,
+    pub notional: u64,            // quote value of the fills at the makers' prices
+    pub fees: u64,
+    pub resting_order_id: Option<u64>,
+    pub resting_quantity: u64,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub order_book: Pubkey,
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub side: Side,
+    pub remaining_quantity: u64,
+    pub refund: u64,              // quote for bids, option tokens for asks
+}
+
+#[event]
+pub struct EventsConsumed {
+    pub order_book: Pubkey,
+    pub count: u8,
+    pub parked: u8,
+    pub remaining: u8,
+}
+
+#[event]
+pub struct ParkedFillClaimed {
+    pub order_book: Pubkey,
+    pub maker: Pubkey,
+    pub option_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct RfqCreated {
+    pub rfq: Pubkey,
+    pub requester: Pubkey,
+    pub options_market: Pubkey,
+    pub side: Side,
+    pub option_type: OptionType,
+    pub strike_price: u64,
+    pub expiration_timestamp: i64,
+    pub amount: u64,
+    pub quote_deadline: i64,
+}
+
+#[event]
+pub struct RfqQuoteSubmitted {
+    pub rfq: Pubkey,
+    pub rfq_quote: Pubkey,
+    pub maker: Pubkey,
+    pub premium_per_contract: u64,
+    pub valid_until: i64,
+    pub escrow_amount: u64,
+}
+
+#[event]
+pub struct RfqQuoteAccepted {
+    pub rfq: Pubkey,
+    pub rfq_quote: Pubkey,
+    pub option_contract: Pubkey,
+    pub writer: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub premium_per_contract: u64,
+    pub total_premium: u64,
+    pub protocol_fee: u64,
+    pub collateral_amount: u64,
+    pub escrow_refund: u64,
+}
+
+#[event]
+pub struct RfqQuoteCancelled {
+    pub rfq: Pubkey,
+    pub rfq_quote: Pubkey,
+    pub maker: Pubkey,
+    pub refund: u64,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub pool: Pubkey,
+    pub options_market: Pubkey,
+    pub lp_mint: Pubkey,
+    pub volatility_bps: u64,
+}
+
+#[event]
+pub struct PoolVolatilityUpdated {
+    pub pool: Pubkey,
+    pub volatility_bps: u64,
+}
+
+#[event]
+pub struct PoolReferencePriceUpdated {
+    pub pool: Pubkey,
+    pub reference_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub pool: Pubkey,
+    pub depositor: Pubkey,
+    pub underlying_amount: u64,
+    pub quote_amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub pool: Pubkey,
+    pub depositor: Pubkey,
+    pub shares: u64,
+    pub underlying_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct PoolOptionSold {
+    pub pool: Pubkey,
+    pub option_contract: Pubkey,
+    pub buyer: Pubkey,
+    pub option_type: OptionType,
+    pub strike_price: u64,
+    pub expiration_timestamp: i64,
+    pub amount: u64,
+    pub premium_per_contract: u64,
+    pub total_premium: u64,
+    pub protocol_fee: u64,
+    pub collateral_amount: u64,
+}
+
+#[event]
+pub struct PoolOptionSettled {
+    pub pool: Pubkey,
+    pub option_contract: Pubkey,
+    pub collateral_returned: u64,
+    pub proceeds_returned: u64,   // exercise payments, in the other token
+    pub underlying_released: u64, // released from the pool's locked totals
+    pub quote_released: u64,
+}
+
 // Error codes
 #[error_code]
 pub enum OptionsError {
@@ -736,4 +5298,100 @@ pub enum OptionsError {
     InvalidOwner,
     #[msg("Unauthorized writer")]
     UnauthorizedWriter,
+    #[msg("Fee rate exceeds maximum")]
+    FeeRateTooHigh,
+    #[msg("Unauthorized protocol authority")]
+    UnauthorizedAuthority,
+    #[msg("No pending authority handover")]
+    NoPendingAuthority,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+    #[msg("Writing options is paused")]
+    WritingPaused,
+    #[msg("Buying options is paused")]
+    BuyingPaused,
+    #[msg("Exercising options is paused")]
+    ExercisingPaused,
+    #[msg("Claiming expired options is paused")]
+    ClaimingPaused,
+    #[msg("Protocol is in withdraw-only mode")]
+    WithdrawOnly,
+    #[msg("Options market is inactive")]
+    MarketInactive,
+    #[msg("Unauthorized market authority")]
+    UnauthorizedMarketAuthority,
+    #[msg("Options market does not match option contract")]
+    MarketMismatch,
+    #[msg("Token mint does not match")]
+    InvalidMint,
+    #[msg("Exercise amount exceeds position")]
+    ExerciseExceedsPosition,
+    #[msg("Nothing to settle")]
+    NothingToSettle,
+    #[msg("Premium per contract is above the buyer's limit")]
+    PremiumAboveLimit,
+    #[msg("Total cost is above the buyer's limit")]
+    TotalCostAboveLimit,
+    #[msg("Transaction deadline has passed")]
+    DeadlineExceeded,
+    #[msg("Amount received is below the minimum")]
+    ReceivedBelowMinimum,
+    #[msg("Option contract is not final yet")]
+    ContractNotFinal,
+    #[msg("Collateral vault is not empty")]
+    VaultNotEmpty,
+    #[msg("Position can still be exercised")]
+    PositionStillOpen,
+    #[msg("Option contract does not match")]
+    ContractMismatch,
+    #[msg("Order book side is full")]
+    OrderBookFull,
+    #[msg("Order book event queue is full; crank it first")]
+    EventQueueFull,
+    #[msg("Order not found")]
+    OrderNotFound,
+    #[msg("No order book events to consume")]
+    NoEventsToConsume,
+    #[msg("Maker token account does not match the event")]
+    InvalidMakerAccount,
+    #[msg("Invalid deadline")]
+    InvalidDeadline,
+    #[msg("RFQ has already been filled")]
+    RfqFilled,
+    #[msg("Premium is below the requester's limit")]
+    PremiumBelowLimit,
+    #[msg("Quote is still active")]
+    QuoteStillActive,
+    #[msg("Collateral token account is required")]
+    CollateralAccountRequired,
+    #[msg("Volatility must be between 1 and MAX_VOLATILITY_BPS basis points")]
+    InvalidVolatility,
+    #[msg("Not enough free liquidity in the pool")]
+    InsufficientLiquidity,
+    #[msg("Auction start premium must be above the floor and the duration positive")]
+    InvalidAuction,
+    #[msg("Amount paid is above the limit")]
+    PaymentAboveLimit,
+    #[msg("Pool reference price is missing or stale")]
+    StaleReferencePrice,
+    #[msg("Strike is too far from the pool reference price")]
+    StrikeOutOfRange,
+    #[msg("Pool account is required for pool-written contracts")]
+    PoolRequired,
+    #[msg("Proceeds vault rent payer account is required")]
+    ProceedsRentPayerRequired,
+    #[msg("Pool has too many open contracts; settle expired ones first")]
+    TooManyOpenContracts,
+    #[msg("Every open pool contract must be passed once, sorted by address")]
+    OpenContractsMismatch,
+    #[msg("One side of the deposit would earn no LP shares")]
+    DepositEarnsNoShares,
+    #[msg("Expiry is sooner than the pool's minimum time to expiry")]
+    ExpiryTooSoon,
+    #[msg("Writer token account still holds writer tokens")]
+    WriterTokensOutstanding,
+    #[msg("Too many makers already have parked fills")]
+    ParkedFillsFull,
+    #[msg("No parked fill for this maker")]
+    NoParkedFill,
 }
@@ -0,0 +1,287 @@
+//! Checked u128 fixed-point math for premium, fee and collateral calculations.
+//!
+//! Contract amounts are in underlying base units, and prices (strike, premium) are in
+//! quote base units per whole underlying token. Converting between the two divides by
+//! the underlying scale (`10^underlying_decimals`). Products are taken in u128 so they
+//! cannot overflow before the division, and every division states which way it rounds:
+//! amounts paid into a vault round up, amounts paid out of a vault round down.
+
+use anchor_lang::prelude::*;
+
+use crate::{OptionType, OptionsError};
+
+/// Denominator for rates expressed in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Seconds in a 365-day year, used to annualize volatility
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// Direction to round a division that does not come out even
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Number of base units in one whole token with `decimals` decimals
+pub fn decimal_scale(decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .ok_or_else(|| OptionsError::MathOverflow.into())
+}
+
+/// `a * b / denominator`, computed in u128 and rounded in the given direction
+pub fn mul_div(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    require!(denominator > 0, OptionsError::MathOverflow);
+
+    let product = (a as u128) * (b as u128);
+    let denominator = denominator as u128;
+    let mut quotient = product / denominator;
+    if rounding == Rounding::Up && quotient * denominator < product {
+        quotient += 1;
+    }
+
+    u64::try_from(quotient).map_err(|_| OptionsError::MathOverflow.into())
+}
+
+/// Quote tokens owed for `contracts` at `price` per whole underlying token
+pub fn quote_amount(price: u64, contracts: u64, underlying_scale: u64, rounding: Rounding) -> Result<u64> {
+    mul_div(price, contracts, underlying_scale, rounding)
+}
+
+/// Fee of `rate` basis points on `amount`, rounded down so users are never overcharged
+pub fn fee_amount(amount: u64, rate: u64) -> Result<u64> {
+    mul_div(amount, rate, BPS_DENOMINATOR, Rounding::Down)
+}
+
+/// `total * part / whole`, rounded down so the vault keeps any dust until the last claim
+pub fn pro_rata_share(total: u64, part: u64, whole: u64) -> Result<u64> {
+    mul_div(total, part, whole, Rounding::Down)
+}
+
+/// Price moving linearly from `start` to `floor` over `duration`, after `elapsed` of it.
+/// The decline rounds down, so the price never drops below the exact line.
+pub fn linear_decay(start: u64, floor: u64, elapsed: u64, duration: u64) -> Result<u64> {
+    require!(start >= floor, OptionsError::MathOverflow);
+    if elapsed >= duration {
+        return Ok(floor);
+    }
+    let decline = mul_div(start - floor, elapsed, duration, Rounding::Down)?;
+    Ok(start - decline)
+}
+
+/// Largest integer whose square is at most `n`
+pub fn integer_sqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from an overestimate converges down to the floor
+    let mut x = n;
+    let mut y = n.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// At-the-money premium per whole underlying token, `0.4 * volatility * sqrt(t) * spot`
+/// (Brenner-Subrahmanyam), with `t` in years.
+/// Rounded up, since the seller is the one quoting it.
+pub fn atm_premium(spot_price: u64, volatility_bps: u64, seconds_to_expiry: u64) -> Result<u64> {
+    const TIME_SCALE: u128 = 1_000_000;
+
+    // sqrt(t / year) in TIME_SCALE fixed point
+    let time_factor = integer_sqrt(
+        (seconds_to_expiry as u128)
+            .checked_mul(TIME_SCALE * TIME_SCALE)
+            .ok_or(OptionsError::MathOverflow)?
+            / SECONDS_PER_YEAR as u128,
+    );
+
+    // 0.4 = 2 / 5
+    let numerator = (spot_price as u128)
+        .checked_mul(volatility_bps as u128 * 2)
+        .and_then(|n| n.checked_mul(time_factor))
+        .ok_or(OptionsError::MathOverflow)?;
+    let denominator = BPS_DENOMINATOR as u128 * 5 * TIME_SCALE;
+    let premium = numerator.div_ceil(denominator);
+
+    u64::try_from(premium).map_err(|_| OptionsError::MathOverflow.into())
+}
+
+/// Model premium per whole underlying token: the intrinsic value at `spot_price` plus the
+/// at-the-money time value, so an in-the-money option never sells below what it pays out
+pub fn model_premium(
+    option_type: OptionType,
+    spot_price: u64,
+    strike_price: u64,
+    volatility_bps: u64,
+    seconds_to_expiry: u64,
+) -> Result<u64> {
+    let intrinsic_value = match option_type {
+        OptionType::Call => spot_price.saturating_sub(strike_price),
+        OptionType::Put => strike_price.saturating_sub(spot_price),
+    };
+    intrinsic_value
+        .checked_add(atm_premium(spot_price, volatility_bps, seconds_to_expiry)?)
+        .ok_or_else(|| OptionsError::MathOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn mul_div_rejects_zero_denominator() {
+        assert!(mul_div(1, 1, 0, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn mul_div_rejects_results_above_u64() {
+        assert!(mul_div(u64::MAX, 2, 1, Rounding::Down).is_err());
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX, Rounding::Up).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn quote_amount_handles_nine_decimal_notional() {
+        // 1_000_000 SOL at a 200 USDC strike: the product overflows u64 but the result fits
+        let scale = decimal_scale(9).unwrap();
+        let contracts = 1_000_000 * scale;
+        let strike = 200_000_000;
+        assert_eq!(
+            quote_amount(strike, contracts, scale, Rounding::Down).unwrap(),
+            200_000_000_000_000
+        );
+    }
+
+    #[test]
+    fn atm_premium_matches_the_approximation() {
+        // 100 USDC strike, 80% volatility, one year: 0.4 * 0.8 * 100 = 32 USDC
+        assert_eq!(atm_premium(100_000_000, 8_000, SECONDS_PER_YEAR).unwrap(), 32_000_000);
+        // A quarter of a year halves it
+        assert_eq!(atm_premium(100_000_000, 8_000, SECONDS_PER_YEAR / 4).unwrap(), 16_000_000);
+        assert_eq!(atm_premium(100_000_000, 8_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn model_premium_charges_intrinsic_value_in_the_money() {
+        // 100 USDC spot, one year at 80%: 32 USDC of time value on top of the intrinsic value
+        let spot = 100_000_000;
+        assert_eq!(model_premium(OptionType::Call, spot, 1, 8_000, SECONDS_PER_YEAR).unwrap(), 131_999_999);
+        assert_eq!(model_premium(OptionType::Put, spot, 150_000_000, 8_000, SECONDS_PER_YEAR).unwrap(), 82_000_000);
+        // Out of the money only the time value is left
+        assert_eq!(model_premium(OptionType::Call, spot, 150_000_000, 8_000, SECONDS_PER_YEAR).unwrap(), 32_000_000);
+    }
+
+    proptest! {
+        #[test]
+        fn linear_decay_falls_from_start_to_floor(
+            floor in 0..=u64::MAX / 2,
+            spread in 0..=u64::MAX / 2,
+            duration in 1..=365 * 86_400u64,
+            elapsed in 0..=400 * 86_400u64,
+            later in 0..=86_400u64,
+        ) {
+            let start = floor + spread;
+            let price = linear_decay(start, floor, elapsed, duration).unwrap();
+            prop_assert!(floor <= price && price <= start);
+            prop_assert!(linear_decay(start, floor, elapsed + later, duration).unwrap() <= price);
+            prop_assert_eq!(linear_decay(start, floor, 0, duration).unwrap(), start);
+            prop_assert_eq!(linear_decay(start, floor, duration, duration).unwrap(), floor);
+        }
+
+        #[test]
+        fn integer_sqrt_is_the_floor(n: u128) {
+            let root = integer_sqrt(n);
+            prop_assert!(root * root <= n);
+            prop_assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > n));
+        }
+
+        #[test]
+        fn atm_premium_grows_with_time(
+            strike in 1..=1_000_000_000_000u64,
+            volatility_bps in 1..=50_000u64,
+            seconds in 0..=10 * SECONDS_PER_YEAR,
+            extra in 0..=SECONDS_PER_YEAR,
+        ) {
+            let shorter = atm_premium(strike, volatility_bps, seconds).unwrap();
+            let longer = atm_premium(strike, volatility_bps, seconds + extra).unwrap();
+            prop_assert!(shorter <= longer);
+        }
+
+        #[test]
+        fn model_premium_is_at_least_intrinsic_value(
+            spot in 1..=1_000_000_000_000u64,
+            strike in 1..=1_000_000_000_000u64,
+            volatility_bps in 1..=100_000u64,
+            seconds in 0..=10 * SECONDS_PER_YEAR,
+        ) {
+            let call = model_premium(OptionType::Call, spot, strike, volatility_bps, seconds).unwrap();
+            let put = model_premium(OptionType::Put, spot, strike, volatility_bps, seconds).unwrap();
+            prop_assert!(call >= spot.saturating_sub(strike));
+            prop_assert!(put >= strike.saturating_sub(spot));
+        }
+
+        #[test]
+        fn mul_div_brackets_the_exact_quotient(a: u64, b: u64, d in 1..=u64::MAX) {
+            let product = (a as u128) * (b as u128);
+            let d128 = d as u128;
+            if let Ok(floor) = mul_div(a, b, d, Rounding::Down) {
+                prop_assert!(floor as u128 * d128 <= product);
+                prop_assert!(product < (floor as u128 + 1) * d128);
+            } else {
+                prop_assert!(product / d128 > u64::MAX as u128);
+            }
+        }
+
+        #[test]
+        fn ceil_is_floor_plus_remainder(a: u64, b: u64, d in 1..=u64::MAX) {
+            let product = (a as u128) * (b as u128);
+            let exact = product / (d as u128) * (d as u128) == product;
+            if let (Ok(floor), Ok(ceil)) = (mul_div(a, b, d, Rounding::Down), mul_div(a, b, d, Rounding::Up)) {
+                prop_assert_eq!(ceil - floor, if exact { 0 } else { 1 });
+            }
+        }
+
+        #[test]
+        fn put_collateral_covers_every_exercise(
+            strike in 1..=1_000_000_000u64,
+            exercises in proptest::collection::vec(1..=100_000_000u64, 1..20),
+            decimals in 0..=9u8,
+        ) {
+            let scale = decimal_scale(decimals).unwrap();
+            let amount: u64 = exercises.iter().sum();
+            let collateral = quote_amount(strike, amount, scale, Rounding::Up).unwrap();
+            let paid_out: u64 = exercises
+                .iter()
+                .map(|n| quote_amount(strike, *n, scale, Rounding::Down).unwrap())
+                .sum();
+            prop_assert!(paid_out <= collateral);
+        }
+
+        #[test]
+        fn pro_rata_claims_drain_the_vault_exactly(
+            vault in 0..=u64::MAX / 2,
+            claims in proptest::collection::vec(1..=1_000_000_000u64, 1..20),
+        ) {
+            let mut remaining_vault = vault;
+            let mut remaining_supply: u64 = claims.iter().sum();
+            let mut paid_out = 0u64;
+            for claim in claims {
+                let share = pro_rata_share(remaining_vault, claim, remaining_supply).unwrap();
+                prop_assert!(share <= remaining_vault);
+                remaining_vault -= share;
+                remaining_supply -= claim;
+                paid_out += share;
+            }
+            prop_assert_eq!(remaining_vault, 0);
+            prop_assert_eq!(paid_out, vault);
+        }
+
+        #[test]
+        fn fee_never_exceeds_amount(amount: u64, rate in 0..=BPS_DENOMINATOR) {
+            prop_assert!(fee_amount(amount, rate).unwrap() <= amount);
+        }
+    }
+}
@@ -0,0 +1,392 @@
+//! Central limit order book for one option series (the option tokens of an `OptionContract`).
+//!
+//! Bids and asks are kept sorted by price-time priority: best price first, and among equal
+//! prices the earliest order first. An incoming order matches against the opposite side at
+//! the makers' prices until it stops crossing; whatever is left rests on the book. The taker
+//! settles inside the same instruction, while each maker fill is pushed onto the event queue
+//! and paid out later by the permissionless `consume_events` crank. A fill whose maker can no
+//! longer receive it (their token account was closed or frozen) is parked on the book instead,
+//! so the crank keeps moving, and the maker collects it later with `claim_parked_fill`.
+//!
+//! This module only manipulates the book; the token movements happen in the instruction
+//! handlers. Prices are premiums in quote base units per whole underlying token, quantities
+//! are contracts in underlying base units, matching `OptionContract.premium_per_contract`.
+
+use anchor_lang::prelude::*;
+
+use crate::math::{self, Rounding};
+use crate::OptionsError;
+
+/// Resting orders kept on each side of the book
+pub const MAX_ORDERS_PER_SIDE: usize = 32;
+/// Maker fills waiting for the crank
+pub const EVENT_QUEUE_LEN: usize = 32;
+/// Makers whose fills the crank could not deliver, each waiting to claim them
+pub const MAX_PARKED_MAKERS: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Side {
+    Bid, // buys option tokens for quote
+    Ask, // sells option tokens for quote
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct Order {
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub price: u64,
+    pub quantity: u64,     // contracts left to fill
+    pub locked_quote: u64, // bids only: escrowed quote not yet spent
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct FillEvent {
+    pub maker: Pubkey,
+    pub order_id: u64,
+    pub maker_side: Side,
+    pub quantity: u64,     // option tokens owed to a bid maker
+    pub quote_amount: u64, // quote owed to an ask maker, net of the protocol fee
+    pub quote_refund: u64, // escrow returned to a bid maker whose order completed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct ParkedFill {
+    pub maker: Pubkey,
+    pub option_amount: u64, // option tokens owed to the maker
+    pub quote_amount: u64,  // quote owed to the maker
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub option_contract: Pubkey,
+    pub option_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub underlying_scale: u64,
+    pub next_order_id: u64,
+    #[max_len(MAX_ORDERS_PER_SIDE)]
+    pub bids: Vec<Order>, // highest price first
+    #[max_len(MAX_ORDERS_PER_SIDE)]
+    pub asks: Vec<Order>, // lowest price first
+    #[max_len(EVENT_QUEUE_LEN)]
+    pub events: Vec<FillEvent>,
+    #[max_len(MAX_PARKED_MAKERS)]
+    pub parked: Vec<ParkedFill>, // one entry per maker, summing every undelivered fill
+    pub bump: u8,
+}
+
+/// What the taker's side of `place_order` has to settle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatchResult {
+    pub filled_quantity: u64,
+    pub notional: u64,         // quote value of the fills at the makers' prices
+    pub fees: u64,             // protocol fee, paid by the selling side
+    pub resting_order_id: Option<u64>,
+    pub resting_quantity: u64,
+    pub escrowed_quote: u64,   // quote locked for a resting bid
+}
+
+impl OrderBook {
+    /// Match an incoming order and rest any remainder on the book
+    pub fn place_order(
+        &mut self,
+        owner: Pubkey,
+        side: Side,
+        price: u64,
+        quantity: u64,
+        fee_rate: u64,
+    ) -> Result<MatchResult> {
+        require!(price > 0, OptionsError::InvalidPremium);
+        require!(quantity > 0, OptionsError::InvalidAmount);
+
+        let mut result = MatchResult::default();
+        let mut remaining = quantity;
+
+        let (makers, events) = match side {
+            Side::Bid => (&mut self.asks, &mut self.events),
+            Side::Ask => (&mut self.bids, &mut self.events),
+        };
+
+        while remaining > 0 {
+            let Some(maker) = makers.first_mut() else { break };
+            let crosses = match side {
+                Side::Bid => maker.price <= price,
+                Side::Ask => maker.price >= price,
+            };
+            if !crosses {
+                break;
+            }
+            require!(events.len() < EVENT_QUEUE_LEN, OptionsError::EventQueueFull);
+
+            // Fills happen at the maker's price; the buyer's cost rounds down
+            let fill = remaining.min(maker.quantity);
+            let notional = math::quote_amount(maker.price, fill, self.underlying_scale, Rounding::Down)?;
+            let fee = math::fee_amount(notional, fee_rate)?;
+            maker.quantity -= fill;
+
+            let mut event = FillEvent {
+                maker: maker.owner,
+                order_id: maker.order_id,
+                maker_side: Side::Bid,
+                quantity: fill,
+                quote_amount: 0,
+                quote_refund: 0,
+            };
+            match side {
+                Side::Bid => {
+                    event.maker_side = Side::Ask;
+                    event.quote_amount = notional
+                        .checked_sub(fee)
+                        .ok_or(OptionsError::MathOverflow)?;
+                }
+                Side::Ask => {
+                    maker.locked_quote = maker.locked_quote
+                        .checked_sub(notional)
+                        .ok_or(OptionsError::MathOverflow)?;
+                    if maker.quantity == 0 {
+                        event.quote_refund = maker.locked_quote;
+                    }
+                }
+            }
+            events.push(event);
+
+            if maker.quantity == 0 {
+                makers.remove(0);
+            }
+
+            remaining -= fill;
+            result.filled_quantity += fill;
+            result.notional = result.notional
+                .checked_add(notional)
+                .ok_or(OptionsError::MathOverflow)?;
+            result.fees = result.fees
+                .checked_add(fee)
+                .ok_or(OptionsError::MathOverflow)?;
+        }
+
+        if remaining > 0 {
+            let resting = match side {
+                Side::Bid => &mut self.bids,
+                Side::Ask => &mut self.asks,
+            };
+            require!(resting.len() < MAX_ORDERS_PER_SIDE, OptionsError::OrderBookFull);
+
+            // A resting bid escrows its full cost, rounded up
+            let locked_quote = match side {
+                Side::Bid => math::quote_amount(price, remaining, self.underlying_scale, Rounding::Up)?,
+                Side::Ask => 0,
+            };
+            let order_id = self.next_order_id;
+            self.next_order_id = order_id
+                .checked_add(1)
+                .ok_or(OptionsError::MathOverflow)?;
+
+            // Price-time priority: queue behind every order at the same or a better price
+            let position = resting
+                .iter()
+                .position(|order| match side {
+                    Side::Bid => order.price < price,
+                    Side::Ask => order.price > price,
+                })
+                .unwrap_or(resting.len());
+            resting.insert(position, Order {
+                order_id,
+                owner,
+                price,
+                quantity: remaining,
+                locked_quote,
+            });
+
+            result.resting_order_id = Some(order_id);
+            result.resting_quantity = remaining;
+            result.escrowed_quote = locked_quote;
+        }
+
+        Ok(result)
+    }
+
+    /// Hold a fill the crank could not deliver, adding it to anything already parked for the maker
+    pub fn park_fill(&mut self, maker: Pubkey, option_amount: u64, quote_amount: u64) -> Result<()> {
+        if let Some(parked) = self.parked.iter_mut().find(|parked| parked.maker == maker) {
+            parked.option_amount = parked.option_amount
+                .checked_add(option_amount)
+                .ok_or(OptionsError::MathOverflow)?;
+            parked.quote_amount = parked.quote_amount
+                .checked_add(quote_amount)
+                .ok_or(OptionsError::MathOverflow)?;
+            return Ok(());
+        }
+        require!(self.parked.len() < MAX_PARKED_MAKERS, OptionsError::ParkedFillsFull);
+        self.parked.push(ParkedFill {
+            maker,
+            option_amount,
+            quote_amount,
+        });
+        Ok(())
+    }
+
+    /// Remove everything parked for a maker so it can be paid out
+    pub fn take_parked_fill(&mut self, maker: Pubkey) -> Result<ParkedFill> {
+        let index = self.parked
+            .iter()
+            .position(|parked| parked.maker == maker)
+            .ok_or(OptionsError::NoParkedFill)?;
+        Ok(self.parked.remove(index))
+    }
+
+    /// Remove a resting order, returning it so its escrow can be refunded
+    pub fn cancel_order(&mut self, owner: Pubkey, order_id: u64) -> Result<(Side, Order)> {
+        for (side, orders) in [(Side::Bid, &mut self.bids), (Side::Ask, &mut self.asks)] {
+            if let Some(index) = orders.iter().position(|order| order.order_id == order_id) {
+                require_keys_eq!(orders[index].owner, owner, OptionsError::InvalidOwner);
+                return Ok((side, orders.remove(index)));
+            }
+        }
+        err!(OptionsError::OrderNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCALE: u64 = 1_000_000;
+
+    fn book() -> OrderBook {
+        OrderBook {
+            option_contract: Pubkey::default(),
+            option_mint: Pubkey::default(),
+            quote_mint: Pubkey::default(),
+            underlying_scale: SCALE,
+            next_order_id: 0,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            events: Vec::new(),
+            parked: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn matches_best_price_then_earliest_order() {
+        let mut book = book();
+        let (alice, bob, carol, taker) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        book.place_order(alice, Side::Ask, 6_000_000, 100, 0).unwrap();
+        book.place_order(bob, Side::Ask, 5_000_000, 100, 0).unwrap();
+        book.place_order(carol, Side::Ask, 5_000_000, 100, 0).unwrap();
+
+        let result = book.place_order(taker, Side::Bid, 6_000_000, 250, 0).unwrap();
+
+        assert_eq!(result.filled_quantity, 250);
+        assert_eq!(result.resting_order_id, None);
+        let makers: Vec<Pubkey> = book.events.iter().map(|e| e.maker).collect();
+        assert_eq!(makers, vec![bob, carol, alice]);
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.asks[0].owner, alice);
+        assert_eq!(book.asks[0].quantity, 50);
+    }
+
+    #[test]
+    fn rests_the_part_that_does_not_cross() {
+        let mut book = book();
+        book.place_order(Pubkey::new_unique(), Side::Ask, 5_000_000, 1_000_000, 0).unwrap();
+
+        let result = book.place_order(Pubkey::new_unique(), Side::Bid, 4_000_000, 500_000, 0).unwrap();
+
+        assert_eq!(result.filled_quantity, 0);
+        assert_eq!(result.resting_quantity, 500_000);
+        assert_eq!(result.escrowed_quote, 2_000_000);
+        assert!(book.events.is_empty());
+        assert_eq!(book.bids[0].locked_quote, 2_000_000);
+    }
+
+    #[test]
+    fn bid_escrow_covers_partial_fills_and_refunds_the_rest() {
+        let mut book = book();
+        let maker = Pubkey::new_unique();
+        // 3 contracts (base units) at 1 quote unit per whole token: escrow rounds up to 1
+        book.place_order(maker, Side::Bid, 1, 3, 0).unwrap();
+        assert_eq!(book.bids[0].locked_quote, 1);
+
+        for _ in 0..3 {
+            book.place_order(Pubkey::new_unique(), Side::Ask, 1, 1, 0).unwrap();
+        }
+
+        assert!(book.bids.is_empty());
+        let spent: u64 = book.events.iter().map(|e| e.quantity).sum();
+        assert_eq!(spent, 3);
+        assert_eq!(book.events.last().unwrap().quote_refund, 1);
+    }
+
+    #[test]
+    fn ask_maker_is_owed_the_notional_net_of_fee() {
+        let mut book = book();
+        book.place_order(Pubkey::new_unique(), Side::Ask, 5_000_000, 1_000_000, 50).unwrap();
+
+        let result = book.place_order(Pubkey::new_unique(), Side::Bid, 5_000_000, 1_000_000, 50).unwrap();
+
+        assert_eq!(result.notional, 5_000_000);
+        assert_eq!(result.fees, 25_000);
+        assert_eq!(book.events[0].quote_amount, 4_975_000);
+    }
+
+    #[test]
+    fn rejects_fills_once_the_event_queue_is_full() {
+        let mut book = book();
+        book.place_order(Pubkey::new_unique(), Side::Ask, 1, 2 * EVENT_QUEUE_LEN as u64, 0).unwrap();
+        let taker = Pubkey::new_unique();
+        for _ in 0..EVENT_QUEUE_LEN {
+            book.place_order(taker, Side::Bid, 1, 1, 0).unwrap();
+        }
+
+        assert!(book.place_order(taker, Side::Bid, 1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn only_the_owner_can_cancel() {
+        let mut book = book();
+        let owner = Pubkey::new_unique();
+        let id = book.place_order(owner, Side::Ask, 1, 1, 0).unwrap().resting_order_id.unwrap();
+
+        assert!(book.cancel_order(Pubkey::new_unique(), id).is_err());
+        let (side, order) = book.cancel_order(owner, id).unwrap();
+        assert_eq!(side, Side::Ask);
+        assert_eq!(order.quantity, 1);
+        assert!(book.asks.is_empty());
+    }
+
+    #[test]
+    fn parks_one_balance_per_maker() {
+        let mut book = book();
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        book.park_fill(alice, 100, 0).unwrap();
+        book.park_fill(bob, 0, 5_000).unwrap();
+        book.park_fill(alice, 50, 7).unwrap();
+
+        assert_eq!(book.parked.len(), 2);
+        let parked = book.take_parked_fill(alice).unwrap();
+        assert_eq!((parked.option_amount, parked.quote_amount), (150, 7));
+        assert!(book.take_parked_fill(alice).is_err());
+        assert_eq!(book.parked[0].maker, bob);
+    }
+
+    #[test]
+    fn rejects_new_makers_once_parking_is_full() {
+        let mut book = book();
+        let first = Pubkey::new_unique();
+        book.park_fill(first, 1, 0).unwrap();
+        for _ in 1..MAX_PARKED_MAKERS {
+            book.park_fill(Pubkey::new_unique(), 1, 0).unwrap();
+        }
+
+        assert!(book.park_fill(Pubkey::new_unique(), 1, 0).is_err());
+        // A maker already parked can still add to their balance
+        book.park_fill(first, 1, 0).unwrap();
+        assert_eq!(book.parked[0].option_amount, 2);
+    }
+}
@@ -175,6 +175,7 @@ async function main() {
       const oneWeek = 7 * 24 * 60 * 60;
       const expirationTime = currentTime + oneWeek;
       
+      // Each contract is seeded by the writer's next nonce, so both can be written back to back
       // Create a call option: SOL at $200 strike, expires in 1 week
       const callOptionResult = await client.writeOption(
         authority,
@@ -13,6 +13,11 @@ import {
   createAccount,
   mintTo,
   getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
+  transfer,
+  closeAccount,
 } from "@solana/spl-token";
 import { expect } from "chai";
 
@@ -45,18 +50,70 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
   let buyer2QuoteAccount: PublicKey;
   let attackerUnderlyingAccount: PublicKey;
   let attackerQuoteAccount: PublicKey;
-  let protocolFeeAccount: PublicKey;
+  let feeVault: PublicKey;
+  let feeStats: PublicKey;
+  let treasuryAccount: PublicKey;
   
   // PDAs
   let protocolState: PublicKey;
   let optionsMarket: PublicKey;
   
+  // Option token helpers
+  const getOptionMint = (optionContract: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("option_mint"), optionContract.toBuffer()],
+      program.programId
+    )[0];
+
+  const getOptionTokenAccount = async (optionContract: PublicKey, owner: Keypair): Promise<PublicKey> =>
+    (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      owner,
+      getOptionMint(optionContract),
+      owner.publicKey
+    )).address;
+  
+  const getWriterMint = (optionContract: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("writer_mint"), optionContract.toBuffer()],
+      program.programId
+    )[0];
+  
+  const getWriterAccount = (writer: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("writer_account"), writer.toBuffer()],
+      program.programId
+    )[0];
+
+  // Contracts are seeded by the writer's next nonce
+  const getNextOptionContract = async (writer: PublicKey): Promise<PublicKey> => {
+    const writerAccount = await program.account.writerAccount.fetchNullable(getWriterAccount(writer));
+    const nonce = writerAccount ? writerAccount.nextNonce : new anchor.BN(0);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("option_contract"), writer.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  };
+  
+  // Decode the Anchor events emitted by a confirmed transaction
+  const getEvents = async (signature: string) => {
+    const txInfo = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    return Array.from(parser.parseLogs(txInfo?.meta?.logMessages ?? []));
+  };
+  
   // Test constants
   const marketId = new anchor.BN(1);
   const strikePrice = new anchor.BN(100_000_000); // 100 USDC (6 decimals)
   const amount = new anchor.BN(1_000_000); // 1 SOL (6 decimals for test)
   const premiumPerContract = new anchor.BN(5_000_000); // 5 USDC per contract
   const INITIAL_BALANCE = 1000_000_000; // 1000 tokens
+  const NO_COST_LIMIT = new anchor.BN("18446744073709551615"); // u64::MAX
+  const NO_MIN_RECEIVED = new anchor.BN(0);
+  const validUntil = () => new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
   
   before(async () => {
     // Initialize keypairs
@@ -148,7 +205,7 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
     attackerUnderlyingAccount = users[4].underlying!;
     attackerQuoteAccount = users[4].quote!;
     
-    protocolFeeAccount = await createAccount(
+    treasuryAccount = await createAccount(
       provider.connection,
       authority,
       quoteMint,
@@ -165,6 +222,16 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       [Buffer.from("options_market"), marketId.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
+    
+    [feeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), quoteMint.toBuffer()],
+      program.programId
+    );
+    
+    [feeStats] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_stats"), quoteMint.toBuffer()],
+      program.programId
+    );
   });
 
   describe("Protocol Initialization", () => {
@@ -224,6 +291,146 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
     });
   });
 
+  describe("Protocol Administration", () => {
+    let newAuthority: Keypair;
+
+    before(async () => {
+      newAuthority = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(newAuthority.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+      );
+    });
+
+    it("Should allow authority to update fee rates", async () => {
+      await program.methods
+        .updateProtocolConfig(new anchor.BN(75), new anchor.BN(15), new anchor.BN(25))
+        .accountsPartial({ protocolState, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      let protocolStateAccount = await program.account.protocolState.fetch(protocolState);
+      expect(protocolStateAccount.protocolFeeRate.toNumber()).to.equal(75);
+      expect(protocolStateAccount.settlementFeeRate.toNumber()).to.equal(15);
+      expect(protocolStateAccount.liquidationFeeRate.toNumber()).to.equal(25);
+
+      // Restore the original rates for the remaining tests
+      await program.methods
+        .updateProtocolConfig(new anchor.BN(50), new anchor.BN(10), new anchor.BN(20))
+        .accountsPartial({ protocolState, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      protocolStateAccount = await program.account.protocolState.fetch(protocolState);
+      expect(protocolStateAccount.protocolFeeRate.toNumber()).to.equal(50);
+    });
+
+    it("Should fail to set fee rate above the cap", async () => {
+      try {
+        await program.methods
+          .updateProtocolConfig(new anchor.BN(1_001), new anchor.BN(10), new anchor.BN(20))
+          .accountsPartial({ protocolState, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have failed with fee rate too high");
+      } catch (error) {
+        expect(error.message).to.include("FeeRateTooHigh");
+      }
+    });
+
+    it("Should fail to update config with unauthorized signer", async () => {
+      try {
+        await program.methods
+          .updateProtocolConfig(new anchor.BN(0), new anchor.BN(0), new anchor.BN(0))
+          .accountsPartial({ protocolState, authority: attacker.publicKey })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have failed with unauthorized authority");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedAuthority");
+      }
+    });
+
+    it("Should hand authority over in two steps", async () => {
+      await program.methods
+        .proposeProtocolAuthority(newAuthority.publicKey)
+        .accountsPartial({ protocolState, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptProtocolAuthority()
+          .accountsPartial({ protocolState, pendingAuthority: attacker.publicKey })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have failed - attacker is not the pending authority");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedAuthority");
+      }
+
+      await program.methods
+        .acceptProtocolAuthority()
+        .accountsPartial({ protocolState, pendingAuthority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+
+      let protocolStateAccount = await program.account.protocolState.fetch(protocolState);
+      expect(protocolStateAccount.authority.equals(newAuthority.publicKey)).to.be.true;
+      expect(protocolStateAccount.pendingAuthority.equals(PublicKey.default)).to.be.true;
+
+      // Hand authority back so the remaining tests keep working
+      await program.methods
+        .proposeProtocolAuthority(authority.publicKey)
+        .accountsPartial({ protocolState, authority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+      await program.methods
+        .acceptProtocolAuthority()
+        .accountsPartial({ protocolState, pendingAuthority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      protocolStateAccount = await program.account.protocolState.fetch(protocolState);
+      expect(protocolStateAccount.authority.equals(authority.publicKey)).to.be.true;
+    });
+
+    it("Should allow authority to set and clear pause flags", async () => {
+      const PAUSE_WRITE = 1 << 0;
+      const PAUSE_WITHDRAW_ONLY = 1 << 4;
+
+      await program.methods
+        .setPauseFlags(PAUSE_WRITE | PAUSE_WITHDRAW_ONLY)
+        .accountsPartial({ protocolState, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      let protocolStateAccount = await program.account.protocolState.fetch(protocolState);
+      expect(protocolStateAccount.pauseFlags).to.equal(PAUSE_WRITE | PAUSE_WITHDRAW_ONLY);
+
+      await program.methods
+        .setPauseFlags(0)
+        .accountsPartial({ protocolState, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      protocolStateAccount = await program.account.protocolState.fetch(protocolState);
+      expect(protocolStateAccount.pauseFlags).to.equal(0);
+    });
+
+    it("Should fail to set unknown pause flags", async () => {
+      try {
+        await program.methods
+          .setPauseFlags(1 << 7)
+          .accountsPartial({ protocolState, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have failed with invalid pause flags");
+      } catch (error) {
+        expect(error.message).to.include("InvalidPauseFlags");
+      }
+    });
+  });
+
   describe("Options Market Creation", () => {
     it("Should create options market successfully", async () => {
       const tx = await program.methods
@@ -241,6 +448,9 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       expect(marketAccount.marketId.eq(marketId)).to.be.true;
       expect(marketAccount.underlyingMint.equals(underlyingMint)).to.be.true;
       expect(marketAccount.quoteMint.equals(quoteMint)).to.be.true;
+      expect(marketAccount.underlyingDecimals).to.equal(6);
+      expect(marketAccount.quoteDecimals).to.equal(6);
+      expect(marketAccount.underlyingScale.toNumber()).to.equal(1_000_000);
       expect(marketAccount.isActive).to.be.true;
       expect(marketAccount.totalOptionsWritten.toNumber()).to.equal(0);
       expect(marketAccount.totalVolume.toNumber()).to.equal(0);
@@ -283,12 +493,44 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
         // Adjust based on your access control requirements
       }
     });
+
+    it("Should allow market authority to deactivate and reactivate the market", async () => {
+      await program.methods
+        .setMarketStatus(false)
+        .accountsPartial({ optionsMarket, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      let marketAccount = await program.account.optionsMarket.fetch(optionsMarket);
+      expect(marketAccount.isActive).to.be.false;
+
+      await program.methods
+        .setMarketStatus(true)
+        .accountsPartial({ optionsMarket, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      marketAccount = await program.account.optionsMarket.fetch(optionsMarket);
+      expect(marketAccount.isActive).to.be.true;
+    });
+
+    it("Should fail to change market status as non-authority", async () => {
+      try {
+        await program.methods
+          .setMarketStatus(false)
+          .accountsPartial({ optionsMarket, authority: attacker.publicKey })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have failed with unauthorized market authority");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedMarketAuthority");
+      }
+    });
   });
 
   describe("Call Options - Complete Lifecycle", () => {
     let callOptionContract: PublicKey;
     let callCollateralVault: PublicKey;
-    let callQuoteCollateralVault: PublicKey;
     let buyer1Position: PublicKey;
     let buyer2Position: PublicKey;
     const currentTime = Math.floor(Date.now() / 1000);
@@ -296,31 +538,17 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
     it("Should write call option successfully", async () => {
       const expirationTimestamp = new anchor.BN(currentTime + 86400); // 24 hours
       
-      [callOptionContract] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("option_contract"),
-          writer1.publicKey.toBuffer(),
-          underlyingMint.toBuffer(),
-          new anchor.BN(currentTime).toArrayLike(Buffer, "le", 8)
-        ],
-        program.programId
-      );
+      callOptionContract = await getNextOptionContract(writer1.publicKey);
       
       [callCollateralVault] = PublicKey.findProgramAddressSync(
         [Buffer.from("collateral"), callOptionContract.toBuffer()],
         program.programId
       );
-      
-      [callQuoteCollateralVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("quote_collateral"), callOptionContract.toBuffer()],
-        program.programId
-      );
 
       const writerBalanceBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
       
       const tx = await program.methods
         .writeOption(
-          new anchor.BN(currentTime),
           { call: {} },
           strikePrice,
           expirationTimestamp,
@@ -329,8 +557,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
         )
         .accountsPartial({
           optionsMarket,
+          optionContract: callOptionContract,
           underlyingMint,
           quoteMint,
+          collateralMint: underlyingMint,
           writerTokenAccount: writer1UnderlyingAccount,
           writerQuoteAccount: writer1QuoteAccount,
           writer: writer1.publicKey,
@@ -345,6 +575,7 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       
       const contractAccount = await program.account.optionContract.fetch(callOptionContract);
       expect(contractAccount.writer.equals(writer1.publicKey)).to.be.true;
+      expect(contractAccount.optionsMarket.equals(optionsMarket)).to.be.true;
       expect(contractAccount.optionType).to.deep.equal({ call: {} });
       expect(contractAccount.strikePrice.eq(strikePrice)).to.be.true;
       expect(contractAccount.amount.eq(amount)).to.be.true;
@@ -353,11 +584,26 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       // Verify collateral was transferred
       const vaultBalance = await getAccount(provider.connection, callCollateralVault);
       expect(Number(vaultBalance.amount)).to.equal(amount.toNumber());
+
+      // Verify one writer token was minted per contract written
+      const writerTokens = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(getWriterMint(callOptionContract), writer1.publicKey)
+      );
+      expect(Number(writerTokens.amount)).to.equal(amount.toNumber());
       
       const writerBalanceAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
       expect(Number(writerBalanceAfter.amount)).to.equal(
         Number(writerBalanceBefore.amount) - amount.toNumber()
       );
+
+      const events = await getEvents(tx);
+      const written = events.find(e => e.name === "optionWritten");
+      expect(written).to.not.be.undefined;
+      expect(written.data.optionContract.equals(callOptionContract)).to.be.true;
+      expect(written.data.writer.equals(writer1.publicKey)).to.be.true;
+      expect(written.data.amount.eq(amount)).to.be.true;
+      expect(written.data.collateralAmount.eq(amount)).to.be.true;
     });
 
     it("Should fail to write option with past expiration", async () => {
@@ -366,7 +612,6 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
         
         await program.methods
           .writeOption(
-            new anchor.BN(currentTime + 1),
             { call: {} },
             strikePrice,
             pastExpiration,
@@ -375,8 +620,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
           )
           .accountsPartial({
             optionsMarket,
+            optionContract: await getNextOptionContract(writer1.publicKey),
             underlyingMint,
             quoteMint,
+            collateralMint: underlyingMint,
             writerTokenAccount: writer1UnderlyingAccount,
             writerQuoteAccount: writer1QuoteAccount,
             writer: writer1.publicKey,
@@ -398,7 +645,6 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
         
         await program.methods
           .writeOption(
-            new anchor.BN(currentTime + 2),
             { call: {} },
             strikePrice,
             expirationTimestamp,
@@ -407,8 +653,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
           )
           .accountsPartial({
             optionsMarket,
+            optionContract: await getNextOptionContract(writer1.publicKey),
             underlyingMint,
             quoteMint,
+            collateralMint: underlyingMint,
             writerTokenAccount: writer1UnderlyingAccount,
             writerQuoteAccount: writer1QuoteAccount,
             writer: writer1.publicKey,
@@ -424,6 +672,37 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       }
     });
 
+    it("Should fail to write option with mints that do not match the market", async () => {
+      try {
+        await program.methods
+          .writeOption(
+            { call: {} },
+            strikePrice,
+            new anchor.BN(currentTime + 86400),
+            amount,
+            premiumPerContract
+          )
+          .accountsPartial({
+            optionsMarket,
+            optionContract: await getNextOptionContract(writer1.publicKey),
+            underlyingMint: quoteMint, // Swapped mints
+            quoteMint: underlyingMint,
+            collateralMint: quoteMint,
+            writerTokenAccount: writer1QuoteAccount,
+            writerQuoteAccount: writer1UnderlyingAccount,
+            writer: writer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([writer1])
+          .rpc();
+        expect.fail("Should have failed with mismatched mints");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMint");
+      }
+    });
+
     it("Should allow buyer to purchase partial contracts", async () => {
       [buyer1Position] = PublicKey.findProgramAddressSync(
         [
@@ -436,18 +715,26 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
 
       const contractsToBuy = new anchor.BN(300_000); // 0.3 contracts
       const buyer1BalanceBefore = await getAccount(provider.connection, buyer1QuoteAccount);
-      const writer1BalanceBefore = await getAccount(provider.connection, writer1QuoteAccount);
-      const protocolBalanceBefore = await getAccount(provider.connection, protocolFeeAccount);
+      const callProceedsVault = PublicKey.findProgramAddressSync(
+        [Buffer.from("proceeds"), callOptionContract.toBuffer()],
+        program.programId
+      )[0];
+      // Writing does not create the proceeds vault; the first buy does
+      expect(await provider.connection.getAccountInfo(callProceedsVault)).to.be.null;
+      const protocolBalanceBefore = await getAccount(provider.connection, feeVault);
       
       const tx = await program.methods
-        .buyOption(contractsToBuy)
+        .buyOption(contractsToBuy, premiumPerContract, NO_COST_LIMIT, validUntil())
         .accountsPartial({
           optionContract: callOptionContract,
+          optionsMarket,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(callOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer1),
           protocolState,
           buyerPosition: buyer1Position,
           buyerQuoteAccount: buyer1QuoteAccount,
-          writerQuoteAccount: writer1QuoteAccount,
-          protocolFeeAccount,
+          feeVault,
           buyer: buyer1.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -466,6 +753,13 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       // Verify contract state
       const contractAccount = await program.account.optionContract.fetch(callOptionContract);
       expect(contractAccount.contractsSold.eq(contractsToBuy)).to.be.true;
+
+      // Verify option tokens were minted to the buyer
+      const buyer1OptionTokens = await getAccount(
+        provider.connection,
+        await getOptionTokenAccount(callOptionContract, buyer1)
+      );
+      expect(Number(buyer1OptionTokens.amount)).to.equal(contractsToBuy.toNumber());
       
       // Verify premium calculations and transfers
       const totalPremium = premiumPerContract.mul(contractsToBuy).div(new anchor.BN(1_000_000));
@@ -473,18 +767,28 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       const netPremium = totalPremium.sub(protocolFee);
       
       const buyer1BalanceAfter = await getAccount(provider.connection, buyer1QuoteAccount);
-      const writer1BalanceAfter = await getAccount(provider.connection, writer1QuoteAccount);
-      const protocolBalanceAfter = await getAccount(provider.connection, protocolFeeAccount);
+      const proceedsAfter = await getAccount(provider.connection, callProceedsVault);
+      const protocolBalanceAfter = await getAccount(provider.connection, feeVault);
       
       expect(Number(buyer1BalanceAfter.amount)).to.equal(
         Number(buyer1BalanceBefore.amount) - totalPremium.toNumber()
       );
-      expect(Number(writer1BalanceAfter.amount)).to.equal(
-        Number(writer1BalanceBefore.amount) + netPremium.toNumber()
-      );
+      // The net premium is held for the writer token holders
+      expect(Number(proceedsAfter.amount)).to.equal(netPremium.toNumber());
+      expect(contractAccount.proceedsRentPayer.equals(buyer1.publicKey)).to.be.true;
       expect(Number(protocolBalanceAfter.amount)).to.equal(
         Number(protocolBalanceBefore.amount) + protocolFee.toNumber()
       );
+
+      // The purchase is reported as an event with every key and amount
+      const events = await getEvents(tx);
+      const purchased = events.find(e => e.name === "optionPurchased");
+      expect(purchased).to.not.be.undefined;
+      expect(purchased.data.optionContract.equals(callOptionContract)).to.be.true;
+      expect(purchased.data.buyer.equals(buyer1.publicKey)).to.be.true;
+      expect(purchased.data.contracts.eq(contractsToBuy)).to.be.true;
+      expect(purchased.data.totalPremium.eq(totalPremium)).to.be.true;
+      expect(purchased.data.protocolFee.eq(protocolFee)).to.be.true;
     });
 
     it("Should allow second buyer to purchase remaining contracts", async () => {
@@ -500,14 +804,17 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       const contractsToBuy = new anchor.BN(700_000); // 0.7 contracts (remaining)
       
       const tx = await program.methods
-        .buyOption(contractsToBuy)
+        .buyOption(contractsToBuy, premiumPerContract, NO_COST_LIMIT, validUntil())
         .accountsPartial({
           optionContract: callOptionContract,
+          optionsMarket,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(callOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer2),
           protocolState,
           buyerPosition: buyer2Position,
           buyerQuoteAccount: buyer2QuoteAccount,
-          writerQuoteAccount: writer1QuoteAccount,
-          protocolFeeAccount,
+          feeVault,
           buyer: buyer2.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -533,14 +840,17 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
 
       try {
         await program.methods
-          .buyOption(new anchor.BN(1)) // Even 1 more contract should fail
+          .buyOption(new anchor.BN(1), premiumPerContract, NO_COST_LIMIT, validUntil()) // Even 1 more contract should fail
           .accountsPartial({
             optionContract: callOptionContract,
+            optionsMarket,
+            proceedsMint: quoteMint,
+            optionMint: getOptionMint(callOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, attacker),
             protocolState,
             buyerPosition: attackerPosition,
             buyerQuoteAccount: attackerQuoteAccount,
-            writerQuoteAccount: writer1QuoteAccount,
-            protocolFeeAccount,
+            feeVault,
             buyer: attacker.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -557,27 +867,66 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       }
     });
 
+    it("Should fail to exercise a call when the payment is above the limit", async () => {
+      const position = await program.account.buyerPosition.fetch(buyer1Position);
+      const protocolAccount = await program.account.protocolState.fetch(protocolState);
+
+      // The holder pays the strike value plus the settlement fee
+      const paymentAmount = strikePrice.mul(position.contractsOwned).div(new anchor.BN(1_000_000));
+      const settlementFee = paymentAmount.mul(protocolAccount.settlementFeeRate).div(new anchor.BN(10_000));
+
+      try {
+        await program.methods
+          .exerciseOption(position.contractsOwned, NO_MIN_RECEIVED, paymentAmount.add(settlementFee).sub(new anchor.BN(1)))
+          .accountsPartial({
+            optionContract: callOptionContract,
+            optionsMarket,
+            proceedsMint: quoteMint,
+            optionMint: getOptionMint(callOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer1),
+            buyerPosition: buyer1Position,
+            protocolState,
+            collateralVault: callCollateralVault,
+            buyerTokenAccount: buyer1UnderlyingAccount,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed - payment above limit");
+      } catch (error) {
+        expect(error.message).to.include("PaymentAboveLimit");
+      }
+    });
+
     it("Should allow buyer1 to exercise their call option", async () => {
       const buyer1UnderlyingBefore = await getAccount(provider.connection, buyer1UnderlyingAccount);
       const buyer1QuoteBefore = await getAccount(provider.connection, buyer1QuoteAccount);
-      const writer1QuoteBefore = await getAccount(provider.connection, writer1QuoteAccount);
+      const callProceedsVault = PublicKey.findProgramAddressSync(
+        [Buffer.from("proceeds"), callOptionContract.toBuffer()],
+        program.programId
+      )[0];
+      const proceedsBefore = await getAccount(provider.connection, callProceedsVault);
       
       const positionBefore = await program.account.buyerPosition.fetch(buyer1Position);
       const contractsToExercise = positionBefore.contractsOwned;
       
       const tx = await program.methods
-        .exerciseOption()
+        .exerciseOption(contractsToExercise, NO_MIN_RECEIVED, NO_COST_LIMIT)
         .accountsPartial({
           optionContract: callOptionContract,
+          optionsMarket,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(callOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer1),
           buyerPosition: buyer1Position,
           protocolState,
           collateralVault: callCollateralVault,
-          quoteCollateralVault: callQuoteCollateralVault,
           buyerTokenAccount: buyer1UnderlyingAccount,
           buyerQuoteAccount: buyer1QuoteAccount,
-          writerTokenAccount: writer1UnderlyingAccount,
-          writerQuoteAccount: writer1QuoteAccount,
-          protocolFeeAccount,
+          feeVault,
           buyer: buyer1.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -587,6 +936,8 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       // Verify exercise results
       const positionAfter = await program.account.buyerPosition.fetch(buyer1Position);
       expect(positionAfter.isExercised).to.be.true;
+      expect(positionAfter.contractsOwned.toNumber()).to.equal(0);
+      expect(positionAfter.contractsExercised.eq(contractsToExercise)).to.be.true;
       
       // Verify token transfers
       const paymentAmount = strikePrice.mul(contractsToExercise).div(new anchor.BN(1_000_000));
@@ -603,23 +954,30 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       expect(Number(buyer1QuoteAfter.amount)).to.be.lessThan(
         Number(buyer1QuoteBefore.amount)
       );
+
+      // The strike payment is held for the writer token holders
+      const proceedsAfter = await getAccount(provider.connection, callProceedsVault);
+      expect(Number(proceedsAfter.amount)).to.equal(
+        Number(proceedsBefore.amount) + paymentAmount.toNumber()
+      );
     });
 
     it("Should fail to exercise already exercised position", async () => {
       try {
         await program.methods
-          .exerciseOption()
+          .exerciseOption(new anchor.BN(1), NO_MIN_RECEIVED, NO_COST_LIMIT)
           .accountsPartial({
             optionContract: callOptionContract,
+            optionsMarket,
+            proceedsMint: quoteMint,
+            optionMint: getOptionMint(callOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer1),
             buyerPosition: buyer1Position,
             protocolState,
             collateralVault: callCollateralVault,
-            quoteCollateralVault: callQuoteCollateralVault,
             buyerTokenAccount: buyer1UnderlyingAccount,
             buyerQuoteAccount: buyer1QuoteAccount,
-            writerTokenAccount: writer1UnderlyingAccount,
-            writerQuoteAccount: writer1QuoteAccount,
-            protocolFeeAccount,
+            feeVault,
             buyer: buyer1.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
@@ -630,98 +988,259 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
         expect(error.message).to.include("PositionAlreadyExercised");
       }
     });
-  });
 
-  describe("Put Options - Complete Lifecycle", () => {
-    let putOptionContract: PublicKey;
-    let putCollateralVault: PublicKey;
-    let putQuoteCollateralVault: PublicKey;
-    let putBuyerPosition: PublicKey;
-    const putCurrentTime = Math.floor(Date.now() / 1000) + 10;
-
-    it("Should write put option successfully", async () => {
-      const expirationTimestamp = new anchor.BN(putCurrentTime + 86400);
-      
-      [putOptionContract] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("option_contract"),
-          writer2.publicKey.toBuffer(),
-          underlyingMint.toBuffer(),
-          new anchor.BN(putCurrentTime).toArrayLike(Buffer, "le", 8)
-        ],
-        program.programId
-      );
-      
-      [putCollateralVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("collateral"), putOptionContract.toBuffer()],
-        program.programId
-      );
-      
-      [putQuoteCollateralVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("quote_collateral"), putOptionContract.toBuffer()],
-        program.programId
-      );
+    it("Should allow buyer2 to exercise part of their position", async () => {
+      const positionBefore = await program.account.buyerPosition.fetch(buyer2Position);
+      const contractsToExercise = new anchor.BN(200_000);
+      const buyer2UnderlyingBefore = await getAccount(provider.connection, buyer2UnderlyingAccount);
 
-      const writer2QuoteBefore = await getAccount(provider.connection, writer2QuoteAccount);
-      
-      const tx = await program.methods
-        .writeOption(
-          new anchor.BN(putCurrentTime),
-          { put: {} },
-          strikePrice,
-          expirationTimestamp,
-          amount,
-          premiumPerContract
-        )
+      await program.methods
+        .exerciseOption(contractsToExercise, NO_MIN_RECEIVED, NO_COST_LIMIT)
         .accountsPartial({
+          optionContract: callOptionContract,
           optionsMarket,
-          underlyingMint,
-          quoteMint,
-          writerTokenAccount: writer2UnderlyingAccount,
-          writerQuoteAccount: writer2QuoteAccount,
-          writer: writer2.publicKey,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(callOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer2),
+          buyerPosition: buyer2Position,
+          protocolState,
+          collateralVault: callCollateralVault,
+          buyerTokenAccount: buyer2UnderlyingAccount,
+          buyerQuoteAccount: buyer2QuoteAccount,
+          feeVault,
+          buyer: buyer2.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-          rent: SYSVAR_RENT_PUBKEY,
         })
-        .signers([writer2])
+        .signers([buyer2])
         .rpc();
-      
-      const contractAccount = await program.account.optionContract.fetch(putOptionContract);
-      expect(contractAccount.optionType).to.deep.equal({ put: {} });
-      
-      // For puts, collateral is quote tokens (strike_price * amount)
-      const expectedCollateral = strikePrice.mul(amount).div(new anchor.BN(1_000_000));
-      const vaultBalance = await getAccount(provider.connection, putQuoteCollateralVault);
-      expect(Number(vaultBalance.amount)).to.equal(expectedCollateral.toNumber());
-      
-      const writer2QuoteAfter = await getAccount(provider.connection, writer2QuoteAccount);
-      expect(Number(writer2QuoteAfter.amount)).to.equal(
-        Number(writer2QuoteBefore.amount) - expectedCollateral.toNumber()
+
+      const positionAfter = await program.account.buyerPosition.fetch(buyer2Position);
+      expect(positionAfter.isExercised).to.be.false;
+      expect(positionAfter.contractsOwned.eq(positionBefore.contractsOwned.sub(contractsToExercise))).to.be.true;
+      expect(positionAfter.contractsExercised.eq(contractsToExercise)).to.be.true;
+
+      const buyer2UnderlyingAfter = await getAccount(provider.connection, buyer2UnderlyingAccount);
+      expect(Number(buyer2UnderlyingAfter.amount)).to.equal(
+        Number(buyer2UnderlyingBefore.amount) + contractsToExercise.toNumber()
       );
     });
 
-    it("Should allow buying put option", async () => {
-      [putBuyerPosition] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("buyer_position"),
-          buyer1.publicKey.toBuffer(),
-          putOptionContract.toBuffer()
-        ],
-        program.programId
-      );
+    it("Should fail to exercise more than the remaining position", async () => {
+      const position = await program.account.buyerPosition.fetch(buyer2Position);
 
-      const contractsToBuy = new anchor.BN(500_000); // 0.5 contracts
-      
-      const tx = await program.methods
-        .buyOption(contractsToBuy)
-        .accountsPartial({
+      try {
+        await program.methods
+          .exerciseOption(position.contractsOwned.add(new anchor.BN(1)), NO_MIN_RECEIVED, NO_COST_LIMIT)
+          .accountsPartial({
+            optionContract: callOptionContract,
+            optionsMarket,
+            proceedsMint: quoteMint,
+            optionMint: getOptionMint(callOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(callOptionContract, buyer2),
+            buyerPosition: buyer2Position,
+            protocolState,
+            collateralVault: callCollateralVault,
+            buyerTokenAccount: buyer2UnderlyingAccount,
+            buyerQuoteAccount: buyer2QuoteAccount,
+            feeVault,
+            buyer: buyer2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed - exercise exceeds position");
+      } catch (error) {
+        expect(error.message).to.include("ExerciseExceedsPosition");
+      }
+    });
+
+    it("Should allow a transferee to exercise option tokens without a position", async () => {
+      const contractsToTransfer = new anchor.BN(100_000);
+      const buyer2OptionAccount = await getOptionTokenAccount(callOptionContract, buyer2);
+      const attackerOptionAccount = await getOptionTokenAccount(callOptionContract, attacker);
+
+      await transfer(
+        provider.connection,
+        buyer2,
+        buyer2OptionAccount,
+        attackerOptionAccount,
+        buyer2,
+        contractsToTransfer.toNumber()
+      );
+
+      const holderUnderlyingBefore = await getAccount(provider.connection, attackerUnderlyingAccount);
+
+      await program.methods
+        .exerciseOption(contractsToTransfer, NO_MIN_RECEIVED, NO_COST_LIMIT)
+        .accountsPartial({
+          optionContract: callOptionContract,
+          optionsMarket,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(callOptionContract),
+          buyerOptionTokenAccount: attackerOptionAccount,
+          buyerPosition: null,
+          protocolState,
+          collateralVault: callCollateralVault,
+          buyerTokenAccount: attackerUnderlyingAccount,
+          buyerQuoteAccount: attackerQuoteAccount,
+          feeVault,
+          buyer: attacker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([attacker])
+        .rpc();
+
+      const holderUnderlyingAfter = await getAccount(provider.connection, attackerUnderlyingAccount);
+      expect(Number(holderUnderlyingAfter.amount)).to.equal(
+        Number(holderUnderlyingBefore.amount) + contractsToTransfer.toNumber()
+      );
+
+      // The exercised tokens are burned
+      const holderOptionTokens = await getAccount(provider.connection, attackerOptionAccount);
+      expect(Number(holderOptionTokens.amount)).to.equal(0);
+    });
+
+    it("Should fail to close a contract that is not final", async () => {
+      try {
+        await program.methods
+          .closeOptionContract()
+          .accountsPartial({
+            optionContract: callOptionContract,
+            optionsMarket,
+            collateralVault: callCollateralVault,
+            writer: writer1.publicKey,
+            rentPayer: writer1.publicKey,
+            proceedsRentPayer: buyer1.publicKey,
+            writerClaimTokenAccount: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([writer1])
+          .rpc();
+        expect.fail("Should have failed - contract still open");
+      } catch (error) {
+        expect(error.message).to.include("ContractNotFinal");
+      }
+    });
+
+    it("Should allow buyer1 to close their fully exercised position", async () => {
+      const buyer1LamportsBefore = await provider.connection.getBalance(buyer1.publicKey);
+
+      await program.methods
+        .closeBuyerPosition()
+        .accountsPartial({
+          buyerPosition: buyer1Position,
+          optionContract: callOptionContract,
+          buyer: buyer1.publicKey,
+          rentPayer: buyer1.publicKey,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(buyer1Position)).to.be.null;
+      const buyer1LamportsAfter = await provider.connection.getBalance(buyer1.publicKey);
+      expect(buyer1LamportsAfter).to.be.greaterThan(buyer1LamportsBefore);
+    });
+
+    it("Should fail to close a position that can still be exercised", async () => {
+      try {
+        await program.methods
+          .closeBuyerPosition()
+          .accountsPartial({
+            buyerPosition: buyer2Position,
+            optionContract: callOptionContract,
+            buyer: buyer2.publicKey,
+            rentPayer: buyer2.publicKey,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have failed - position still open");
+      } catch (error) {
+        expect(error.message).to.include("PositionStillOpen");
+      }
+    });
+  });
+
+  describe("Put Options - Complete Lifecycle", () => {
+    let putOptionContract: PublicKey;
+    let putCollateralVault: PublicKey;
+    let putBuyerPosition: PublicKey;
+    const putCurrentTime = Math.floor(Date.now() / 1000) + 10;
+
+    it("Should write put option successfully", async () => {
+      const expirationTimestamp = new anchor.BN(putCurrentTime + 86400);
+      
+      putOptionContract = await getNextOptionContract(writer2.publicKey);
+      
+      [putCollateralVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), putOptionContract.toBuffer()],
+        program.programId
+      );
+
+      const writer2QuoteBefore = await getAccount(provider.connection, writer2QuoteAccount);
+      
+      const tx = await program.methods
+        .writeOption(
+          { put: {} },
+          strikePrice,
+          expirationTimestamp,
+          amount,
+          premiumPerContract
+        )
+        .accountsPartial({
+          optionsMarket,
+          optionContract: putOptionContract,
+          underlyingMint,
+          quoteMint,
+          collateralMint: quoteMint,
+          writerTokenAccount: writer2UnderlyingAccount,
+          writerQuoteAccount: writer2QuoteAccount,
+          writer: writer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer2])
+        .rpc();
+      
+      const contractAccount = await program.account.optionContract.fetch(putOptionContract);
+      expect(contractAccount.optionType).to.deep.equal({ put: {} });
+      
+      // For puts, collateral is quote tokens (strike_price * amount)
+      const expectedCollateral = strikePrice.mul(amount).div(new anchor.BN(1_000_000));
+      const vaultBalance = await getAccount(provider.connection, putCollateralVault);
+      expect(vaultBalance.mint.equals(quoteMint)).to.be.true; // Puts only get a quote vault
+      expect(Number(vaultBalance.amount)).to.equal(expectedCollateral.toNumber());
+      
+      const writer2QuoteAfter = await getAccount(provider.connection, writer2QuoteAccount);
+      expect(Number(writer2QuoteAfter.amount)).to.equal(
+        Number(writer2QuoteBefore.amount) - expectedCollateral.toNumber()
+      );
+    });
+
+    it("Should allow buying put option", async () => {
+      [putBuyerPosition] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("buyer_position"),
+          buyer1.publicKey.toBuffer(),
+          putOptionContract.toBuffer()
+        ],
+        program.programId
+      );
+
+      const contractsToBuy = new anchor.BN(500_000); // 0.5 contracts
+      
+      const tx = await program.methods
+        .buyOption(contractsToBuy, premiumPerContract, NO_COST_LIMIT, validUntil())
+        .accountsPartial({
           optionContract: putOptionContract,
+          optionsMarket,
+          proceedsMint: underlyingMint,
+          optionMint: getOptionMint(putOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
           protocolState,
           buyerPosition: putBuyerPosition,
           buyerQuoteAccount: buyer1QuoteAccount,
-          writerQuoteAccount: writer2QuoteAccount,
-          protocolFeeAccount,
+          feeVault,
           buyer: buyer1.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -733,179 +1252,2142 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       expect(positionAccount.contractsOwned.eq(contractsToBuy)).to.be.true;
     });
 
+    it("Should fail to buy after the buyer's deadline", async () => {
+      try {
+        await program.methods
+          .buyOption(
+            new anchor.BN(100_000),
+            premiumPerContract,
+            NO_COST_LIMIT,
+            new anchor.BN(Math.floor(Date.now() / 1000) - 60)
+          )
+          .accountsPartial({
+            optionContract: putOptionContract,
+            optionsMarket,
+            proceedsMint: underlyingMint,
+            optionMint: getOptionMint(putOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
+            protocolState,
+            buyerPosition: putBuyerPosition,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed - deadline passed");
+      } catch (error) {
+        expect(error.message).to.include("DeadlineExceeded");
+      }
+    });
+
+    it("Should fail to buy when the total cost is above the buyer's limit", async () => {
+      const contractsToBuy = new anchor.BN(100_000);
+      const totalCost = premiumPerContract.mul(contractsToBuy).div(new anchor.BN(1_000_000));
+
+      try {
+        await program.methods
+          .buyOption(contractsToBuy, premiumPerContract, totalCost.sub(new anchor.BN(1)), validUntil())
+          .accountsPartial({
+            optionContract: putOptionContract,
+            optionsMarket,
+            proceedsMint: underlyingMint,
+            optionMint: getOptionMint(putOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
+            protocolState,
+            buyerPosition: putBuyerPosition,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed - total cost above limit");
+      } catch (error) {
+        expect(error.message).to.include("TotalCostAboveLimit");
+      }
+    });
+
+    it("Should allow topping up an existing put position", async () => {
+      const positionBefore = await program.account.buyerPosition.fetch(putBuyerPosition);
+      const contractsToBuy = new anchor.BN(100_000); // 0.1 contracts
+
+      await program.methods
+        .buyOption(contractsToBuy, premiumPerContract, NO_COST_LIMIT, validUntil())
+        .accountsPartial({
+          optionContract: putOptionContract,
+          optionsMarket,
+          proceedsMint: underlyingMint,
+          optionMint: getOptionMint(putOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
+          protocolState,
+          buyerPosition: putBuyerPosition,
+          buyerQuoteAccount: buyer1QuoteAccount,
+          feeVault,
+          buyer: buyer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const totalPremium = premiumPerContract.mul(contractsToBuy).div(new anchor.BN(1_000_000));
+      const positionAfter = await program.account.buyerPosition.fetch(putBuyerPosition);
+      expect(positionAfter.contractsOwned.eq(positionBefore.contractsOwned.add(contractsToBuy))).to.be.true;
+      expect(positionAfter.premiumPaid.eq(positionBefore.premiumPaid.add(totalPremium))).to.be.true;
+      expect(positionAfter.buyer.equals(buyer1.publicKey)).to.be.true;
+    });
+
+    it("Should fail to exercise a put when the payout is below the minimum", async () => {
+      const positionBefore = await program.account.buyerPosition.fetch(putBuyerPosition);
+      const protocolAccount = await program.account.protocolState.fetch(protocolState);
+      const contractAccount = await program.account.optionContract.fetch(putOptionContract);
+      const contractsToExercise = positionBefore.contractsOwned;
+
+      // Payout is the strike value net of the settlement fee
+      const settlementValue = contractAccount.strikePrice.mul(contractsToExercise).div(new anchor.BN(1_000_000));
+      const settlementFee = settlementValue.mul(protocolAccount.settlementFeeRate).div(new anchor.BN(10_000));
+      const payout = settlementValue.sub(settlementFee);
+
+      try {
+        await program.methods
+          .exerciseOption(contractsToExercise, payout.add(new anchor.BN(1)), NO_COST_LIMIT)
+          .accountsPartial({
+            optionContract: putOptionContract,
+            optionsMarket,
+            proceedsMint: underlyingMint,
+            optionMint: getOptionMint(putOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
+            buyerPosition: putBuyerPosition,
+            protocolState,
+            collateralVault: putCollateralVault,
+            buyerTokenAccount: buyer1UnderlyingAccount,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed - payout below minimum");
+      } catch (error) {
+        expect(error.message).to.include("ReceivedBelowMinimum");
+      }
+    });
+
     it("Should allow exercising put option", async () => {
       const buyer1UnderlyingBefore = await getAccount(provider.connection, buyer1UnderlyingAccount);
       const buyer1QuoteBefore = await getAccount(provider.connection, buyer1QuoteAccount);
-      const writer2UnderlyingBefore = await getAccount(provider.connection, writer2UnderlyingAccount);
+      const putProceedsVault = PublicKey.findProgramAddressSync(
+        [Buffer.from("proceeds"), putOptionContract.toBuffer()],
+        program.programId
+      )[0];
+      const proceedsBefore = await getAccount(provider.connection, putProceedsVault);
       
       const positionBefore = await program.account.buyerPosition.fetch(putBuyerPosition);
       const contractsToExercise = positionBefore.contractsOwned;
       
       const tx = await program.methods
-        .exerciseOption()
+        .exerciseOption(contractsToExercise, NO_MIN_RECEIVED, NO_COST_LIMIT)
         .accountsPartial({
           optionContract: putOptionContract,
+          optionsMarket,
+          proceedsMint: underlyingMint,
+          optionMint: getOptionMint(putOptionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
           buyerPosition: putBuyerPosition,
           protocolState,
           collateralVault: putCollateralVault,
-          quoteCollateralVault: putQuoteCollateralVault,
           buyerTokenAccount: buyer1UnderlyingAccount,
           buyerQuoteAccount: buyer1QuoteAccount,
-          writerTokenAccount: writer2UnderlyingAccount,
-          writerQuoteAccount: writer2QuoteAccount,
-          protocolFeeAccount,
+          feeVault,
           buyer: buyer1.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([buyer1])
+        .signers([buyer1])
+        .rpc();
+      
+      // For puts: buyer gives underlying tokens, receives quote tokens
+      const buyer1UnderlyingAfter = await getAccount(provider.connection, buyer1UnderlyingAccount);
+      const buyer1QuoteAfter = await getAccount(provider.connection, buyer1QuoteAccount);
+      const proceedsAfter = await getAccount(provider.connection, putProceedsVault);
+      
+      // Buyer should have fewer underlying tokens
+      expect(Number(buyer1UnderlyingAfter.amount)).to.equal(
+        Number(buyer1UnderlyingBefore.amount) - contractsToExercise.toNumber()
+      );
+      
+      // Buyer should have more quote tokens
+      expect(Number(buyer1QuoteAfter.amount)).to.be.greaterThan(
+        Number(buyer1QuoteBefore.amount)
+      );
+      
+      // The delivered underlying is held for the writer token holders
+      expect(Number(proceedsAfter.amount)).to.equal(
+        Number(proceedsBefore.amount) + contractsToExercise.toNumber()
+      );
+    });
+
+    it("Should allow the writer to reprice unsold put contracts", async () => {
+      const newPremium = premiumPerContract.mul(new anchor.BN(2));
+
+      await program.methods
+        .updatePremium(newPremium)
+        .accountsPartial({
+          optionContract: putOptionContract,
+          writer: writer2.publicKey,
+        })
+        .signers([writer2])
+        .rpc();
+
+      const contractAccount = await program.account.optionContract.fetch(putOptionContract);
+      expect(contractAccount.premiumPerContract.eq(newPremium)).to.be.true;
+    });
+
+    it("Should fail to reprice as non-writer", async () => {
+      try {
+        await program.methods
+          .updatePremium(new anchor.BN(1))
+          .accountsPartial({
+            optionContract: putOptionContract,
+            writer: attacker.publicKey,
+          })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have failed - unauthorized writer");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedWriter");
+      }
+    });
+
+    it("Should fail to buy when the premium was raised above the buyer's limit", async () => {
+      try {
+        await program.methods
+          .buyOption(new anchor.BN(100_000), premiumPerContract, NO_COST_LIMIT, validUntil()) // Limit set before the reprice
+          .accountsPartial({
+            optionContract: putOptionContract,
+            optionsMarket,
+            proceedsMint: underlyingMint,
+            optionMint: getOptionMint(putOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(putOptionContract, buyer1),
+            protocolState,
+            buyerPosition: putBuyerPosition,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed - premium above limit");
+      } catch (error) {
+        expect(error.message).to.include("PremiumAboveLimit");
+      }
+    });
+
+    it("Should fail to cancel more contracts than remain unsold", async () => {
+      const contractAccount = await program.account.optionContract.fetch(putOptionContract);
+      const unsold = contractAccount.amount.sub(contractAccount.contractsSold);
+
+      try {
+        await program.methods
+          .cancelUnsold(unsold.add(new anchor.BN(1)))
+          .accountsPartial({
+            optionContract: putOptionContract,
+            optionsMarket,
+            protocolState,
+            collateralVault: putCollateralVault,
+            writerMint: getWriterMint(putOptionContract),
+            writerClaimTokenAccount: getAssociatedTokenAddressSync(
+              getWriterMint(putOptionContract),
+              writer2.publicKey
+            ),
+            writerTokenAccount: writer2UnderlyingAccount,
+            writerQuoteAccount: writer2QuoteAccount,
+            writer: writer2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([writer2])
+          .rpc();
+        expect.fail("Should have failed - not enough unsold contracts");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientContracts");
+      }
+    });
+
+    it("Should allow the writer to cancel unsold put contracts before expiry", async () => {
+      const contractBefore = await program.account.optionContract.fetch(putOptionContract);
+      const unsold = contractBefore.amount.sub(contractBefore.contractsSold);
+      const writer2QuoteBefore = await getAccount(provider.connection, writer2QuoteAccount);
+      const marketBefore = await program.account.optionsMarket.fetch(optionsMarket);
+
+      await program.methods
+        .cancelUnsold(unsold)
+        .accountsPartial({
+          optionContract: putOptionContract,
+          optionsMarket,
+          protocolState,
+          collateralVault: putCollateralVault,
+          writerMint: getWriterMint(putOptionContract),
+          writerClaimTokenAccount: getAssociatedTokenAddressSync(
+            getWriterMint(putOptionContract),
+            writer2.publicKey
+          ),
+          writerTokenAccount: writer2UnderlyingAccount,
+          writerQuoteAccount: writer2QuoteAccount,
+          writer: writer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([writer2])
+        .rpc();
+
+      // The contract shrinks to what was sold and the unsold collateral comes back
+      const contractAfter = await program.account.optionContract.fetch(putOptionContract);
+      expect(contractAfter.amount.eq(contractBefore.contractsSold)).to.be.true;
+
+      const releasedCollateral = strikePrice.mul(unsold).div(new anchor.BN(1_000_000));
+      const writer2QuoteAfter = await getAccount(provider.connection, writer2QuoteAccount);
+      expect(Number(writer2QuoteAfter.amount)).to.equal(
+        Number(writer2QuoteBefore.amount) + releasedCollateral.toNumber()
+      );
+
+      const marketAfter = await program.account.optionsMarket.fetch(optionsMarket);
+      expect(marketAfter.openInterestWritten.eq(marketBefore.openInterestWritten.sub(unsold))).to.be.true;
+
+      const writerMintAfter = await getMint(provider.connection, getWriterMint(putOptionContract));
+      expect(Number(writerMintAfter.supply)).to.equal(contractAfter.amount.toNumber());
+    });
+  });
+
+  describe("Order Book", () => {
+    let bookContract: PublicKey;
+    let orderBook: PublicKey;
+    let bookOptionVault: PublicKey;
+    let bookQuoteVault: PublicKey;
+    let writer1OptionAta: PublicKey;
+    let writer1QuoteAta: PublicKey;
+    let buyer2OptionAta: PublicKey;
+    let buyer2QuoteAta: PublicKey;
+    const askPrice = new anchor.BN(5_000_000);
+
+    const orderAccounts = (owner: Keypair, optionAta: PublicKey, quoteAta: PublicKey) => ({
+      orderBook,
+      optionContract: bookContract,
+      optionsMarket,
+      protocolState,
+      optionVault: bookOptionVault,
+      quoteVault: bookQuoteVault,
+      feeVault,
+      feeStats,
+      ownerOptionAccount: optionAta,
+      ownerQuoteAccount: quoteAta,
+      owner: owner.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    before(async () => {
+      bookContract = await getNextOptionContract(writer1.publicKey);
+      await program.methods
+        .writeOption(
+          { call: {} },
+          strikePrice,
+          new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+          amount,
+          premiumPerContract
+        )
+        .accountsPartial({
+          optionsMarket,
+          optionContract: bookContract,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          writerTokenAccount: writer1UnderlyingAccount,
+          writerQuoteAccount: writer1QuoteAccount,
+          writer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer1])
+        .rpc();
+
+      [orderBook] = PublicKey.findProgramAddressSync(
+        [Buffer.from("order_book"), bookContract.toBuffer()],
+        program.programId
+      );
+      [bookOptionVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("book_options"), orderBook.toBuffer()],
+        program.programId
+      );
+      [bookQuoteVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("book_quote"), orderBook.toBuffer()],
+        program.programId
+      );
+
+      // Orders settle through associated token accounts
+      writer1OptionAta = await getOptionTokenAccount(bookContract, writer1);
+      buyer2OptionAta = await getOptionTokenAccount(bookContract, buyer2);
+      writer1QuoteAta = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, writer1, quoteMint, writer1.publicKey
+      )).address;
+      buyer2QuoteAta = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, buyer2, quoteMint, buyer2.publicKey
+      )).address;
+      await mintTo(provider.connection, authority, quoteMint, buyer2QuoteAta, authority, INITIAL_BALANCE);
+    });
+
+    it("Should create an order book for an option series", async () => {
+      await program.methods
+        .createOrderBook()
+        .accountsPartial({
+          orderBook,
+          optionContract: bookContract,
+          optionsMarket,
+          optionMint: getOptionMint(bookContract),
+          quoteMint,
+          optionVault: bookOptionVault,
+          quoteVault: bookQuoteVault,
+          payer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer1])
+        .rpc();
+
+      const book = await program.account.orderBook.fetch(orderBook);
+      expect(book.optionContract.equals(bookContract)).to.be.true;
+      expect(book.bids.length).to.equal(0);
+      expect(book.asks.length).to.equal(0);
+    });
+
+    it("Should let the writer mint unsold options and post an ask", async () => {
+      await program.methods
+        .mintUnsoldOptions(new anchor.BN(500_000))
+        .accountsPartial({
+          optionContract: bookContract,
+          optionsMarket,
+          protocolState,
+          optionMint: getOptionMint(bookContract),
+          writerOptionTokenAccount: writer1OptionAta,
+          writer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([writer1])
+        .rpc();
+
+      await program.methods
+        .placeOrder({ ask: {} }, askPrice, new anchor.BN(500_000))
+        .accountsPartial(orderAccounts(writer1, writer1OptionAta, writer1QuoteAta))
+        .signers([writer1])
+        .rpc();
+
+      const book = await program.account.orderBook.fetch(orderBook);
+      expect(book.asks.length).to.equal(1);
+      expect(book.asks[0].quantity.toNumber()).to.equal(500_000);
+      const vault = await getAccount(provider.connection, bookOptionVault);
+      expect(Number(vault.amount)).to.equal(500_000);
+    });
+
+    it("Should reject orders while the market is inactive", async () => {
+      await program.methods
+        .setMarketStatus(false)
+        .accountsPartial({ optionsMarket, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      try {
+        await program.methods
+          .placeOrder({ bid: {} }, new anchor.BN(6_000_000), new anchor.BN(100_000))
+          .accountsPartial(orderAccounts(buyer2, buyer2OptionAta, buyer2QuoteAta))
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("MarketInactive");
+      } finally {
+        await program.methods
+          .setMarketStatus(true)
+          .accountsPartial({ optionsMarket, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      }
+    });
+
+    it("Should fill a crossing bid at the maker's price", async () => {
+      const buyer2QuoteBefore = await getAccount(provider.connection, buyer2QuoteAta);
+
+      const tx = await program.methods
+        .placeOrder({ bid: {} }, new anchor.BN(6_000_000), new anchor.BN(300_000))
+        .accountsPartial(orderAccounts(buyer2, buyer2OptionAta, buyer2QuoteAta))
+        .signers([buyer2])
+        .rpc();
+
+      // 0.3 contracts at 5 USDC, not the 6 USDC limit
+      const notional = askPrice.mul(new anchor.BN(300_000)).div(new anchor.BN(1_000_000)).toNumber();
+      const buyer2QuoteAfter = await getAccount(provider.connection, buyer2QuoteAta);
+      expect(Number(buyer2QuoteBefore.amount) - Number(buyer2QuoteAfter.amount)).to.equal(notional);
+      const buyer2Options = await getAccount(provider.connection, buyer2OptionAta);
+      expect(Number(buyer2Options.amount)).to.equal(300_000);
+
+      const book = await program.account.orderBook.fetch(orderBook);
+      expect(book.asks[0].quantity.toNumber()).to.equal(200_000);
+      expect(book.bids.length).to.equal(0);
+      expect(book.events.length).to.equal(1);
+
+      const events = await getEvents(tx);
+      const placed = events.find((e) => e.name === "orderPlaced");
+      expect(placed.data.filledQuantity.toNumber()).to.equal(300_000);
+    });
+
+    it("Should fail to crank with a token account that is not the maker's", async () => {
+      try {
+        await program.methods
+          .consumeEvents(1)
+          .accountsPartial({
+            orderBook,
+            optionVault: bookOptionVault,
+            quoteVault: bookQuoteVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([
+            { pubkey: buyer2OptionAta, isWritable: true, isSigner: false },
+            { pubkey: buyer2QuoteAta, isWritable: true, isSigner: false },
+          ])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMakerAccount");
+      }
+    });
+
+    it("Should pay the ask maker when the event queue is cranked", async () => {
+      const book = await program.account.orderBook.fetch(orderBook);
+      const owed = book.events[0].quoteAmount.toNumber();
+      const writer1QuoteBefore = await getAccount(provider.connection, writer1QuoteAta);
+
+      await program.methods
+        .consumeEvents(8)
+        .accountsPartial({
+          orderBook,
+          optionVault: bookOptionVault,
+          quoteVault: bookQuoteVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: writer1OptionAta, isWritable: true, isSigner: false },
+          { pubkey: writer1QuoteAta, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+
+      const writer1QuoteAfter = await getAccount(provider.connection, writer1QuoteAta);
+      expect(Number(writer1QuoteAfter.amount) - Number(writer1QuoteBefore.amount)).to.equal(owed);
+      const bookAfter = await program.account.orderBook.fetch(orderBook);
+      expect(bookAfter.events.length).to.equal(0);
+    });
+
+    it("Should escrow a resting bid and refund it on cancel", async () => {
+      const buyer2QuoteBefore = await getAccount(provider.connection, buyer2QuoteAta);
+
+      await program.methods
+        .placeOrder({ bid: {} }, new anchor.BN(4_000_000), new anchor.BN(100_000))
+        .accountsPartial(orderAccounts(buyer2, buyer2OptionAta, buyer2QuoteAta))
+        .signers([buyer2])
+        .rpc();
+
+      const book = await program.account.orderBook.fetch(orderBook);
+      expect(book.bids.length).to.equal(1);
+      expect(book.bids[0].lockedQuote.toNumber()).to.equal(400_000);
+
+      await program.methods
+        .cancelOrder(book.bids[0].orderId)
+        .accountsPartial({
+          orderBook,
+          optionVault: bookOptionVault,
+          quoteVault: bookQuoteVault,
+          ownerOptionAccount: buyer2OptionAta,
+          ownerQuoteAccount: buyer2QuoteAta,
+          owner: buyer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const buyer2QuoteAfter = await getAccount(provider.connection, buyer2QuoteAta);
+      expect(Number(buyer2QuoteAfter.amount)).to.equal(Number(buyer2QuoteBefore.amount));
+      const bookAfter = await program.account.orderBook.fetch(orderBook);
+      expect(bookAfter.bids.length).to.equal(0);
+    });
+
+    it("Should fail to cancel someone else's order", async () => {
+      const book = await program.account.orderBook.fetch(orderBook);
+      try {
+        await program.methods
+          .cancelOrder(book.asks[0].orderId)
+          .accountsPartial({
+            orderBook,
+            optionVault: bookOptionVault,
+            quoteVault: bookQuoteVault,
+            ownerOptionAccount: buyer2OptionAta,
+            ownerQuoteAccount: buyer2QuoteAta,
+            owner: buyer2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOwner");
+      }
+    });
+
+    it("Should park a fill whose maker closed their token account and let the maker claim it", async () => {
+      // A fresh maker sells some of buyer2's options, then closes the quote account it would be paid to
+      const maker = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(maker.publicKey, anchor.web3.LAMPORTS_PER_SOL)
+      );
+      const makerOptionAta = await getOptionTokenAccount(bookContract, maker);
+      const makerQuoteAta = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, maker, quoteMint, maker.publicKey
+      )).address;
+      const quantity = new anchor.BN(100_000);
+      await transfer(provider.connection, buyer2, buyer2OptionAta, makerOptionAta, buyer2, BigInt(quantity.toString()));
+
+      const makerPrice = new anchor.BN(4_000_000);
+      await program.methods
+        .placeOrder({ ask: {} }, makerPrice, quantity)
+        .accountsPartial(orderAccounts(maker, makerOptionAta, makerQuoteAta))
+        .signers([maker])
+        .rpc();
+      await closeAccount(provider.connection, maker, makerQuoteAta, maker.publicKey, maker);
+
+      const buyer1OptionAta = await getOptionTokenAccount(bookContract, buyer1);
+      const buyer1QuoteAta = getAssociatedTokenAddressSync(quoteMint, buyer1.publicKey);
+      await program.methods
+        .placeOrder({ bid: {} }, makerPrice, quantity)
+        .accountsPartial(orderAccounts(buyer1, buyer1OptionAta, buyer1QuoteAta))
+        .signers([buyer1])
+        .rpc();
+
+      const book = await program.account.orderBook.fetch(orderBook);
+      expect(book.events.length).to.equal(1);
+      const owed = book.events[0].quoteAmount;
+
+      // The crank moves past the undeliverable fill instead of failing on it
+      const tx = await program.methods
+        .consumeEvents(8)
+        .accountsPartial({
+          orderBook,
+          optionVault: bookOptionVault,
+          quoteVault: bookQuoteVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: makerOptionAta, isWritable: true, isSigner: false },
+          { pubkey: makerQuoteAta, isWritable: true, isSigner: false },
+        ])
+        .rpc();
+
+      const consumed = (await getEvents(tx)).find((e) => e.name === "eventsConsumed");
+      expect(consumed.data.count).to.equal(1);
+      expect(consumed.data.parked).to.equal(1);
+      const bookAfterCrank = await program.account.orderBook.fetch(orderBook);
+      expect(bookAfterCrank.events.length).to.equal(0);
+      expect(bookAfterCrank.parked.length).to.equal(1);
+      expect(bookAfterCrank.parked[0].maker.equals(maker.publicKey)).to.be.true;
+      expect(bookAfterCrank.parked[0].quoteAmount.eq(owed)).to.be.true;
+
+      // Only the maker can collect it, into a quote account they own again
+      const makerQuoteAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, maker, quoteMint, maker.publicKey
+      )).address;
+      try {
+        await program.methods
+          .claimParkedFill()
+          .accountsPartial({
+            orderBook,
+            optionVault: bookOptionVault,
+            quoteVault: bookQuoteVault,
+            makerOptionAccount: null,
+            makerQuoteAccount: buyer2QuoteAta,
+            maker: buyer2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("NoParkedFill");
+      }
+
+      await program.methods
+        .claimParkedFill()
+        .accountsPartial({
+          orderBook,
+          optionVault: bookOptionVault,
+          quoteVault: bookQuoteVault,
+          makerOptionAccount: null,
+          makerQuoteAccount,
+          maker: maker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([maker])
+        .rpc();
+
+      const makerQuote = await getAccount(provider.connection, makerQuoteAccount);
+      expect(Number(makerQuote.amount)).to.equal(owed.toNumber());
+      const bookAfterClaim = await program.account.orderBook.fetch(orderBook);
+      expect(bookAfterClaim.parked.length).to.equal(0);
+    });
+  });
+
+  describe("Request for Quote", () => {
+    const getRfq = (requester: PublicKey, rfqId: anchor.BN): PublicKey =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("rfq"), requester.toBuffer(), rfqId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const getRfqQuote = (rfq: PublicKey, maker: PublicKey): PublicKey =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("rfq_quote"), rfq.toBuffer(), maker.toBuffer()],
+        program.programId
+      )[0];
+
+    const getRfqEscrow = (rfqQuote: PublicKey): PublicKey =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("rfq_escrow"), rfqQuote.toBuffer()],
+        program.programId
+      )[0];
+
+    const rfqAmount = new anchor.BN(2_000_000); // 2 contracts
+    const rfqExpiration = () => new anchor.BN(Math.floor(Date.now() / 1000) + 86400);
+    const quoteDeadline = () => new anchor.BN(Math.floor(Date.now() / 1000) + 600);
+
+    const createRfq = async (requester: Keypair, rfqId: anchor.BN, side: any, optionType: any) => {
+      await program.methods
+        .createRfq(rfqId, side, optionType, strikePrice, rfqExpiration(), rfqAmount, quoteDeadline())
+        .accountsPartial({
+          rfq: getRfq(requester.publicKey, rfqId),
+          optionsMarket,
+          protocolState,
+          requester: requester.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([requester])
+        .rpc();
+      return getRfq(requester.publicKey, rfqId);
+    };
+
+    const submitQuote = async (rfq: PublicKey, maker: Keypair, escrowMint: PublicKey, makerTokenAccount: PublicKey, premium: anchor.BN) => {
+      const rfqQuote = getRfqQuote(rfq, maker.publicKey);
+      await program.methods
+        .submitRfqQuote(premium, validUntil())
+        .accountsPartial({
+          rfq,
+          rfqQuote,
+          optionsMarket,
+          protocolState,
+          escrowMint,
+          rfqEscrow: getRfqEscrow(rfqQuote),
+          makerTokenAccount,
+          maker: maker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([maker])
+        .rpc();
+      return rfqQuote;
+    };
+
+    const acceptQuote = async (
+      rfq: PublicKey,
+      rfqQuote: PublicKey,
+      requester: Keypair,
+      maker: Keypair,
+      writer: PublicKey,
+      buyer: PublicKey,
+      collateralMint: PublicKey,
+      requesterCollateralAccount: PublicKey | null,
+      requesterQuoteAccount: PublicKey,
+      makerQuoteAccount: PublicKey,
+      makerTokenAccount: PublicKey,
+      limitPremium: anchor.BN
+    ) => {
+      const optionContract = await getNextOptionContract(writer);
+      await program.methods
+        .acceptRfqQuote(limitPremium)
+        .accountsPartial({
+          rfq,
+          rfqQuote,
+          rfqEscrow: getRfqEscrow(rfqQuote),
+          maker: maker.publicKey,
+          writer,
+          buyer,
+          optionContract,
+          optionsMarket,
+          protocolState,
+          underlyingMint,
+          quoteMint,
+          collateralMint,
+          requesterCollateralAccount,
+          requesterQuoteAccount,
+          makerQuoteAccount,
+          makerTokenAccount,
+          feeVault,
+          feeStats,
+          requester: requester.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([requester])
+        .rpc();
+      return optionContract;
+    };
+
+    let buyRfq: PublicKey;
+    let writer1Quote: PublicKey;
+    let writer2Quote: PublicKey;
+
+    it("Should let a requester broadcast an RFQ and collect escrowed quotes", async () => {
+      buyRfq = await createRfq(buyer1, new anchor.BN(1), { bid: {} }, { call: {} });
+
+      const writer1UnderlyingBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
+      writer1Quote = await submitQuote(buyRfq, writer1, underlyingMint, writer1UnderlyingAccount, new anchor.BN(6_000_000));
+      writer2Quote = await submitQuote(buyRfq, writer2, underlyingMint, writer2UnderlyingAccount, new anchor.BN(5_500_000));
+
+      // Call writers escrow the underlying up front
+      const escrow = await getAccount(provider.connection, getRfqEscrow(writer1Quote));
+      expect(Number(escrow.amount)).to.equal(rfqAmount.toNumber());
+      const writer1UnderlyingAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
+      expect(Number(writer1UnderlyingBefore.amount) - Number(writer1UnderlyingAfter.amount)).to.equal(rfqAmount.toNumber());
+    });
+
+    it("Should fail to accept a quote above the requester's limit", async () => {
+      try {
+        await acceptQuote(
+          buyRfq, writer1Quote, buyer1, writer1, writer1.publicKey, buyer1.publicKey,
+          underlyingMint, null, buyer1QuoteAccount, writer1QuoteAccount, writer1UnderlyingAccount, new anchor.BN(5_500_000)
+        );
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("PremiumAboveLimit");
+      }
+    });
+
+    it("Should settle the winning quote into a new option contract", async () => {
+      const writer2QuoteBefore = await getAccount(provider.connection, writer2QuoteAccount);
+
+      const optionContract = await acceptQuote(
+        buyRfq, writer2Quote, buyer1, writer2, writer2.publicKey, buyer1.publicKey,
+        underlyingMint, null, buyer1QuoteAccount, writer2QuoteAccount, writer2UnderlyingAccount, new anchor.BN(5_500_000)
+      );
+
+      const contract = await program.account.optionContract.fetch(optionContract);
+      expect(contract.writer.equals(writer2.publicKey)).to.be.true;
+      // The requester funded the contract's accounts, so closing it refunds them
+      expect(contract.rentPayer.equals(buyer1.publicKey)).to.be.true;
+      expect(contract.amount.eq(rfqAmount)).to.be.true;
+      expect(contract.contractsSold.eq(rfqAmount)).to.be.true;
+
+      const vault = await getAccount(provider.connection, PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), optionContract.toBuffer()],
+        program.programId
+      )[0]);
+      expect(Number(vault.amount)).to.equal(rfqAmount.toNumber());
+
+      const buyerOptions = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(getOptionMint(optionContract), buyer1.publicKey)
+      );
+      expect(Number(buyerOptions.amount)).to.equal(rfqAmount.toNumber());
+
+      // The maker is paid the premium net of the protocol fee
+      const protocol = await program.account.protocolState.fetch(protocolState);
+      const totalPremium = 11_000_000; // 5.5 USDC x 2 contracts
+      const fee = Math.floor(totalPremium * protocol.protocolFeeRate.toNumber() / 10_000);
+      const writer2QuoteAfter = await getAccount(provider.connection, writer2QuoteAccount);
+      expect(Number(writer2QuoteAfter.amount) - Number(writer2QuoteBefore.amount)).to.equal(totalPremium - fee);
+
+      const rfq = await program.account.rfq.fetch(buyRfq);
+      expect(rfq.isFilled).to.be.true;
+      expect(rfq.optionContract.equals(optionContract)).to.be.true;
+      expect(await provider.connection.getAccountInfo(getRfqEscrow(writer2Quote))).to.be.null;
+    });
+
+    it("Should let anyone refund a losing quote once the RFQ is filled", async () => {
+      const writer1UnderlyingBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
+
+      await program.methods
+        .cancelRfqQuote()
+        .accountsPartial({
+          rfq: buyRfq,
+          rfqQuote: writer1Quote,
+          rfqEscrow: getRfqEscrow(writer1Quote),
+          makerTokenAccount: writer1UnderlyingAccount,
+          maker: writer1.publicKey,
+          authority: attacker.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([attacker])
+        .rpc();
+
+      const writer1UnderlyingAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
+      expect(Number(writer1UnderlyingAfter.amount) - Number(writer1UnderlyingBefore.amount)).to.equal(rfqAmount.toNumber());
+      expect(await provider.connection.getAccountInfo(writer1Quote)).to.be.null;
+    });
+
+    it("Should fail to cancel someone else's live quote", async () => {
+      const rfq = await createRfq(buyer1, new anchor.BN(2), { bid: {} }, { call: {} });
+      const rfqQuote = await submitQuote(rfq, writer1, underlyingMint, writer1UnderlyingAccount, premiumPerContract);
+
+      try {
+        await program.methods
+          .cancelRfqQuote()
+          .accountsPartial({
+            rfq,
+            rfqQuote,
+            rfqEscrow: getRfqEscrow(rfqQuote),
+            makerTokenAccount: writer1UnderlyingAccount,
+            maker: writer1.publicKey,
+            authority: attacker.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("QuoteStillActive");
+      }
+    });
+
+    it("Should let a requester write puts against an escrowed premium", async () => {
+      const rfq = await createRfq(writer2, new anchor.BN(1), { ask: {} }, { put: {} });
+      const rfqQuote = await submitQuote(rfq, buyer2, quoteMint, buyer2QuoteAccount, premiumPerContract);
+
+      const escrow = await getAccount(provider.connection, getRfqEscrow(rfqQuote));
+      expect(Number(escrow.amount)).to.equal(10_000_000); // 5 USDC x 2 contracts
+
+      const optionContract = await acceptQuote(
+        rfq, rfqQuote, writer2, buyer2, writer2.publicKey, buyer2.publicKey,
+        quoteMint, writer2QuoteAccount, writer2QuoteAccount, buyer2QuoteAccount, buyer2QuoteAccount, premiumPerContract
+      );
+
+      const contract = await program.account.optionContract.fetch(optionContract);
+      expect(contract.optionType).to.deep.equal({ put: {} });
+      const position = await program.account.buyerPosition.fetch(
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("buyer_position"), buyer2.publicKey.toBuffer(), optionContract.toBuffer()],
+          program.programId
+        )[0]
+      );
+      expect(position.contractsOwned.eq(rfqAmount)).to.be.true;
+      expect(position.rentPayer.equals(writer2.publicKey)).to.be.true;
+
+      // A signing writer delegates their writer tokens for keeper settlement
+      const writerClaim = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(getWriterMint(optionContract), writer2.publicKey)
+      );
+      expect(Number(writerClaim.delegatedAmount)).to.equal(rfqAmount.toNumber());
+    });
+
+    it("Should return tokens donated to an escrow to the maker on accept", async () => {
+      const rfq = await createRfq(buyer1, new anchor.BN(3), { bid: {} }, { call: {} });
+      const rfqQuote = await submitQuote(rfq, writer1, underlyingMint, writer1UnderlyingAccount, premiumPerContract);
+
+      // A stray transfer into the escrow must not block the fill or be stranded
+      const donation = 250_000;
+      await transfer(
+        provider.connection,
+        writer2,
+        writer2UnderlyingAccount,
+        getRfqEscrow(rfqQuote),
+        writer2,
+        donation
+      );
+
+      const writer1UnderlyingBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
+      const optionContract = await acceptQuote(
+        rfq, rfqQuote, buyer1, writer1, writer1.publicKey, buyer1.publicKey,
+        underlyingMint, null, buyer1QuoteAccount, writer1QuoteAccount, writer1UnderlyingAccount, premiumPerContract
+      );
+
+      const vault = await getAccount(provider.connection, PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), optionContract.toBuffer()],
+        program.programId
+      )[0]);
+      expect(Number(vault.amount)).to.equal(rfqAmount.toNumber());
+      const writer1UnderlyingAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
+      expect(Number(writer1UnderlyingAfter.amount) - Number(writer1UnderlyingBefore.amount)).to.equal(donation);
+      expect(await provider.connection.getAccountInfo(getRfqEscrow(rfqQuote))).to.be.null;
+    });
+  });
+
+  describe("Liquidity Pool", () => {
+    let pool: PublicKey;
+    let lpMint: PublicKey;
+    let poolUnderlyingVault: PublicKey;
+    let poolQuoteVault: PublicKey;
+    const volatilityBps = new anchor.BN(8_000); // 80%
+
+    const liquidityAccounts = (depositor: Keypair, underlyingAccount: PublicKey, quoteAccount: PublicKey) => ({
+      pool,
+      optionsMarket,
+      protocolState,
+      lpMint,
+      poolUnderlyingVault,
+      poolQuoteVault,
+      depositorUnderlyingAccount: underlyingAccount,
+      depositorQuoteAccount: quoteAccount,
+      depositorLpAccount: getAssociatedTokenAddressSync(lpMint, depositor.publicKey),
+      depositor: depositor.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    // Deposits and withdrawals take every open pool contract, sorted by address
+    const openPoolContracts = async () =>
+      (await program.account.optionContract.all([{ memcmp: { offset: 8, bytes: pool.toBase58() } }]))
+        .map((contract) => contract.publicKey)
+        .sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()))
+        .map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }));
+
+    const poolCallBuyAccounts = (optionContract: PublicKey) => ({
+      pool,
+      poolUnderlyingVault,
+      poolQuoteVault,
+      optionContract,
+      optionsMarket,
+      protocolState,
+      underlyingMint,
+      quoteMint,
+      collateralMint: underlyingMint,
+      buyerQuoteAccount: buyer1QuoteAccount,
+      feeVault,
+      feeStats,
+      buyer: buyer1.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    });
+
+    before(() => {
+      [pool] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), optionsMarket.toBuffer()],
+        program.programId
+      );
+      [lpMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lp_mint"), pool.toBuffer()],
+        program.programId
+      );
+      [poolUnderlyingVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_underlying"), pool.toBuffer()],
+        program.programId
+      );
+      [poolQuoteVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_quote"), pool.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Should let the market authority create a pool", async () => {
+      await program.methods
+        .createPool(volatilityBps)
+        .accountsPartial({
+          pool,
+          optionsMarket,
+          underlyingMint,
+          quoteMint,
+          lpMint,
+          poolUnderlyingVault,
+          poolQuoteVault,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([authority])
+        .rpc();
+
+      const poolAccount = await program.account.liquidityPool.fetch(pool);
+      expect(poolAccount.volatilityBps.eq(volatilityBps)).to.be.true;
+      expect(poolAccount.optionsMarket.equals(optionsMarket)).to.be.true;
+    });
+
+    it("Should fail to set the pool volatility as non-authority", async () => {
+      try {
+        await program.methods
+          .setPoolVolatility(new anchor.BN(1))
+          .accountsPartial({ pool, optionsMarket, authority: attacker.publicKey })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedMarketAuthority");
+      }
+    });
+
+    it("Should refuse to value a first deposit of underlying before the reference price is set", async () => {
+      try {
+        await program.methods
+          .depositLiquidity(new anchor.BN(10_000_000), new anchor.BN(100_000_000), new anchor.BN(0))
+          .accountsPartial(liquidityAccounts(writer1, writer1UnderlyingAccount, writer1QuoteAccount))
+          .remainingAccounts(await openPoolContracts())
+          .signers([writer1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("StaleReferencePrice");
+      }
+    });
+
+    it("Should refuse to quote before the reference price is set", async () => {
+      const optionContract = await getNextOptionContract(pool);
+      try {
+        await program.methods
+          .buyFromPool(
+            { call: {} },
+            strikePrice,
+            new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+            new anchor.BN(1_000_000),
+            NO_COST_LIMIT,
+            validUntil()
+          )
+          .accountsPartial(poolCallBuyAccounts(optionContract))
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("StaleReferencePrice");
+      }
+    });
+
+    it("Should let only the market authority set the reference price", async () => {
+      try {
+        await program.methods
+          .setPoolReferencePrice(new anchor.BN(1))
+          .accountsPartial({ pool, optionsMarket, authority: attacker.publicKey })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedMarketAuthority");
+      }
+
+      await program.methods
+        .setPoolReferencePrice(strikePrice)
+        .accountsPartial({ pool, optionsMarket, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      const poolAccount = await program.account.liquidityPool.fetch(pool);
+      expect(poolAccount.referencePrice.eq(strikePrice)).to.be.true;
+      expect(poolAccount.referencePriceUpdatedAt.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Should mint LP shares for deposits in the pool's ratio", async () => {
+      await program.methods
+        .depositLiquidity(new anchor.BN(10_000_000), new anchor.BN(100_000_000), new anchor.BN(0))
+        .accountsPartial(liquidityAccounts(writer1, writer1UnderlyingAccount, writer1QuoteAccount))
+        .remainingAccounts(await openPoolContracts())
+        .signers([writer1])
+        .rpc();
+
+      // The first deposit sets the share scale at its value in quote tokens:
+      // 10 underlying at the 100 USDC reference price plus 100 USDC
+      const writer1Lp = await getAccount(provider.connection, getAssociatedTokenAddressSync(lpMint, writer1.publicKey));
+      expect(Number(writer1Lp.amount)).to.equal(1_100_000_000);
+
+      await program.methods
+        .depositLiquidity(new anchor.BN(5_000_000), new anchor.BN(50_000_000), new anchor.BN(550_000_000))
+        .accountsPartial(liquidityAccounts(writer2, writer2UnderlyingAccount, writer2QuoteAccount))
+        .remainingAccounts(await openPoolContracts())
+        .signers([writer2])
+        .rpc();
+
+      const writer2Lp = await getAccount(provider.connection, getAssociatedTokenAddressSync(lpMint, writer2.publicKey));
+      expect(Number(writer2Lp.amount)).to.equal(550_000_000);
+    });
+
+    it("Should fail to sell a deep in-the-money call below its intrinsic value", async () => {
+      // A strike at a tenth of spot is worth 90 USDC per contract on exercise
+      const optionContract = await getNextOptionContract(pool);
+      try {
+        await program.methods
+          .buyFromPool(
+            { call: {} },
+            strikePrice.div(new anchor.BN(10)),
+            new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+            new anchor.BN(1_000_000),
+            NO_COST_LIMIT,
+            validUntil()
+          )
+          .accountsPartial(poolCallBuyAccounts(optionContract))
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("StrikeOutOfRange");
+      }
+    });
+
+    it("Should sell a call at the model price, writing it out of pool collateral", async () => {
+      const expirationTimestamp = new anchor.BN(Math.floor(Date.now() / 1000) + 30 * 86400);
+      const writerAccount = await program.account.writerAccount.fetchNullable(getWriterAccount(pool));
+      const nonce = writerAccount ? writerAccount.nextNonce : new anchor.BN(0);
+      const [optionContract] = PublicKey.findProgramAddressSync(
+        [Buffer.from("option_contract"), pool.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const contracts = new anchor.BN(2_000_000);
+      const poolQuoteBefore = await getAccount(provider.connection, poolQuoteVault);
+
+      const tx = await program.methods
+        .buyFromPool({ call: {} }, strikePrice, expirationTimestamp, contracts, NO_COST_LIMIT, validUntil())
+        .accountsPartial({
+          pool,
+          poolUnderlyingVault,
+          poolQuoteVault,
+          optionContract,
+          optionsMarket,
+          protocolState,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          buyerQuoteAccount: buyer1QuoteAccount,
+          feeVault,
+          feeStats,
+          buyer: buyer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      const contract = await program.account.optionContract.fetch(optionContract);
+      expect(contract.writer.equals(pool)).to.be.true;
+      expect(contract.contractsSold.eq(contracts)).to.be.true;
+      // 0.4 * 80% * sqrt(30 / 365) * 100 USDC is about 9.17 USDC
+      expect(contract.premiumPerContract.toNumber()).to.be.closeTo(9_174_000, 10_000);
+
+      const poolAccount = await program.account.liquidityPool.fetch(pool);
+      expect(poolAccount.lockedUnderlying.eq(contracts)).to.be.true;
+      const poolUnderlying = await getAccount(provider.connection, poolUnderlyingVault);
+      expect(Number(poolUnderlying.amount)).to.equal(15_000_000 - contracts.toNumber());
+
+      const events = await getEvents(tx);
+      const sold = events.find((e) => e.name === "poolOptionSold");
+      const poolQuoteAfter = await getAccount(provider.connection, poolQuoteVault);
+      expect(Number(poolQuoteAfter.amount) - Number(poolQuoteBefore.amount)).to.equal(
+        sold.data.totalPremium.sub(sold.data.protocolFee).toNumber()
+      );
+
+      const buyerOptions = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(getOptionMint(optionContract), buyer1.publicKey)
+      );
+      expect(Number(buyerOptions.amount)).to.equal(contracts.toNumber());
+    });
+
+    it("Should refuse to sell an option expiring within the minimum time to expiry", async () => {
+      const optionContract = await getNextOptionContract(pool);
+      try {
+        await program.methods
+          .buyFromPool(
+            { call: {} },
+            strikePrice,
+            new anchor.BN(Math.floor(Date.now() / 1000) + 60),
+            new anchor.BN(1_000_000),
+            NO_COST_LIMIT,
+            validUntil()
+          )
+          .accountsPartial(poolCallBuyAccounts(optionContract))
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("ExpiryTooSoon");
+      }
+    });
+
+    it("Should fail to sell more than the pool's free collateral", async () => {
+      const writerAccount = await program.account.writerAccount.fetch(getWriterAccount(pool));
+      const [optionContract] = PublicKey.findProgramAddressSync(
+        [Buffer.from("option_contract"), pool.toBuffer(), writerAccount.nextNonce.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      try {
+        await program.methods
+          .buyFromPool(
+            { call: {} },
+            strikePrice,
+            new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+            new anchor.BN(100_000_000),
+            NO_COST_LIMIT,
+            validUntil()
+          )
+          .accountsPartial({
+            pool,
+            poolUnderlyingVault,
+            poolQuoteVault,
+            optionContract,
+            optionsMarket,
+            protocolState,
+            underlyingMint,
+            quoteMint,
+            collateralMint: underlyingMint,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            feeStats,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientLiquidity");
+      }
+    });
+
+    it("Should pay withdrawals pro rata, counting locked collateral", async () => {
+      const poolAccount = await program.account.liquidityPool.fetch(pool);
+      const underlyingBefore = await getAccount(provider.connection, poolUnderlyingVault);
+      const quoteBefore = await getAccount(provider.connection, poolQuoteVault);
+      const lpSupply = Number((await getMint(provider.connection, lpMint)).supply);
+      const shares = 110_000_000; // a tenth of writer1's shares
+
+      const writer1UnderlyingBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
+      await program.methods
+        .withdrawLiquidity(new anchor.BN(shares), new anchor.BN(0), new anchor.BN(0))
+        .accountsPartial(liquidityAccounts(writer1, writer1UnderlyingAccount, writer1QuoteAccount))
+        .remainingAccounts(await openPoolContracts())
+        .signers([writer1])
+        .rpc();
+
+      const totalUnderlying = Number(underlyingBefore.amount) + poolAccount.lockedUnderlying.toNumber();
+      const expectedUnderlying = Math.floor(totalUnderlying * shares / lpSupply);
+      const writer1UnderlyingAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
+      expect(Number(writer1UnderlyingAfter.amount) - Number(writer1UnderlyingBefore.amount)).to.equal(expectedUnderlying);
+
+      const totalQuote = Number(quoteBefore.amount) + poolAccount.lockedQuote.toNumber();
+      const quoteAfter = await getAccount(provider.connection, poolQuoteVault);
+      expect(Number(quoteBefore.amount) - Number(quoteAfter.amount)).to.equal(
+        Math.floor(totalQuote * shares / lpSupply)
+      );
+    });
+
+    it("Should hold back what open pool contracts owe from withdrawals", async () => {
+      const shares = new anchor.BN(50_000_000);
+
+      // Leaving out an open contract would hide its liability
+      try {
+        await program.methods
+          .withdrawLiquidity(shares, new anchor.BN(0), new anchor.BN(0))
+          .accountsPartial(liquidityAccounts(writer2, writer2UnderlyingAccount, writer2QuoteAccount))
+          .remainingAccounts([])
+          .signers([writer2])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("OpenContractsMismatch");
+      }
+
+      // Spot 10% above the strike puts the open calls in the money
+      const spot = strikePrice.muln(11).divn(10);
+      await program.methods
+        .setPoolReferencePrice(spot)
+        .accountsPartial({ pool, optionsMarket, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      // Each open call owes (spot - strike) / spot underlying per contract, rounded up
+      const openContracts = await openPoolContracts();
+      expect(openContracts.length).to.be.greaterThan(0);
+      let underlyingOwed = new anchor.BN(0);
+      for (const { pubkey } of openContracts) {
+        const contract = await program.account.optionContract.fetch(pubkey);
+        expect(contract.optionType).to.deep.equal({ call: {} });
+        const outstanding = contract.amount.sub(contract.contractsExercised);
+        underlyingOwed = underlyingOwed.add(
+          outstanding.mul(spot.sub(contract.strikePrice)).add(spot.subn(1)).div(spot)
+        );
+      }
+
+      const poolAccount = await program.account.liquidityPool.fetch(pool);
+      expect(poolAccount.openContracts.toNumber()).to.equal(openContracts.length);
+      const underlyingBefore = await getAccount(provider.connection, poolUnderlyingVault);
+      const lpSupply = new anchor.BN((await getMint(provider.connection, lpMint)).supply.toString());
+      const writer2UnderlyingBefore = await getAccount(provider.connection, writer2UnderlyingAccount);
+
+      await program.methods
+        .withdrawLiquidity(shares, new anchor.BN(0), new anchor.BN(0))
+        .accountsPartial(liquidityAccounts(writer2, writer2UnderlyingAccount, writer2QuoteAccount))
+        .remainingAccounts(openContracts)
+        .signers([writer2])
+        .rpc();
+
+      const netUnderlying = new anchor.BN(underlyingBefore.amount.toString())
+        .add(poolAccount.lockedUnderlying)
+        .sub(underlyingOwed);
+      const writer2UnderlyingAfter = await getAccount(provider.connection, writer2UnderlyingAccount);
+      expect(Number(writer2UnderlyingAfter.amount) - Number(writer2UnderlyingBefore.amount)).to.equal(
+        netUnderlying.mul(shares).div(lpSupply).toNumber()
+      );
+
+      // Back to the strike for the tests that follow
+      await program.methods
+        .setPoolReferencePrice(strikePrice)
+        .accountsPartial({ pool, optionsMarket, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Should track exercises of pool contracts and release them on settlement", async () => {
+      const optionContract = await getNextOptionContract(pool);
+      const [collateralVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), optionContract.toBuffer()],
+        program.programId
+      );
+      const [proceedsVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("proceeds"), optionContract.toBuffer()],
+        program.programId
+      );
+      const contracts = new anchor.BN(1_000_000);
+      // Just past the pool's two-minute minimum time to expiry
+      const expiration = Math.floor(Date.now() / 1000) + 130;
+      const poolBeforeBuy = await program.account.liquidityPool.fetch(pool);
+
+      await program.methods
+        .buyFromPool({ call: {} }, strikePrice, new anchor.BN(expiration), contracts, NO_COST_LIMIT, validUntil())
+        .accountsPartial({
+          pool,
+          poolUnderlyingVault,
+          poolQuoteVault,
+          optionContract,
+          optionsMarket,
+          protocolState,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          buyerQuoteAccount: buyer2QuoteAccount,
+          feeVault,
+          feeStats,
+          buyer: buyer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const exerciseAccounts = {
+        optionContract,
+        optionsMarket,
+        proceedsMint: quoteMint,
+        optionMint: getOptionMint(optionContract),
+        buyerOptionTokenAccount: getAssociatedTokenAddressSync(getOptionMint(optionContract), buyer2.publicKey),
+        buyerPosition: null,
+        protocolState,
+        collateralVault,
+        buyerTokenAccount: buyer2UnderlyingAccount,
+        buyerQuoteAccount: buyer2QuoteAccount,
+        feeVault,
+        buyer: buyer2.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      };
+      const exercised = new anchor.BN(400_000);
+
+      try {
+        await program.methods
+          .exerciseOption(exercised, NO_MIN_RECEIVED, NO_COST_LIMIT)
+          .accountsPartial({ ...exerciseAccounts, pool: null })
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("PoolRequired");
+      }
+
+      const exerciseTx = await program.methods
+        .exerciseOption(exercised, NO_MIN_RECEIVED, NO_COST_LIMIT)
+        .accountsPartial({ ...exerciseAccounts, pool })
+        .signers([buyer2])
+        .rpc();
+
+      // The paid-out underlying leaves the locked totals and the strike payment joins them
+      const exercise = (await getEvents(exerciseTx)).find((e) => e.name === "optionExercised");
+      const poolAfterExercise = await program.account.liquidityPool.fetch(pool);
+      expect(poolAfterExercise.lockedUnderlying.eq(poolBeforeBuy.lockedUnderlying.add(contracts).sub(exercised))).to.be.true;
+      expect(poolAfterExercise.lockedQuote.eq(poolBeforeBuy.lockedQuote.add(exercise.data.quoteAmount))).to.be.true;
+
+      while (Math.floor(Date.now() / 1000) <= expiration + 1) {
+        await new Promise(resolve => setTimeout(resolve, 1000));
+      }
+
+      const buyer2LamportsBefore = await provider.connection.getBalance(buyer2.publicKey);
+
+      // Any keeper can settle; the rent goes to the buyer who funded the accounts
+      await program.methods
+        .settlePoolOption()
+        .accountsPartial({
+          pool,
+          optionContract,
+          optionsMarket,
+          protocolState,
+          collateralVault,
+          proceedsVault,
+          writerMint: getWriterMint(optionContract),
+          poolWriterTokenAccount: getAssociatedTokenAddressSync(getWriterMint(optionContract), pool, true),
+          poolUnderlyingVault,
+          poolQuoteVault,
+          rentPayer: buyer2.publicKey,
+          proceedsRentPayer: buyer2.publicKey, // created the proceeds vault by exercising
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      // Both vaults went back to the pool, so nothing of this contract stays locked
+      const poolAfter = await program.account.liquidityPool.fetch(pool);
+      expect(poolAfter.lockedUnderlying.eq(poolBeforeBuy.lockedUnderlying)).to.be.true;
+      expect(poolAfter.lockedQuote.eq(poolBeforeBuy.lockedQuote)).to.be.true;
+
+      for (const account of [optionContract, collateralVault, proceedsVault]) {
+        expect(await provider.connection.getAccountInfo(account)).to.be.null;
+      }
+      const buyer2LamportsAfter = await provider.connection.getBalance(buyer2.publicKey);
+      expect(buyer2LamportsAfter).to.be.greaterThan(buyer2LamportsBefore);
+    });
+  });
+
+  describe("Dutch Auction", () => {
+    let auctionContract: PublicKey;
+    const startPremium = new anchor.BN(20_000_000); // 20 USDC
+    const floorPremium = new anchor.BN(5_000_000); // 5 USDC
+
+    const buyAccounts = async (buyer: Keypair, buyerQuoteAccount: PublicKey) => ({
+      optionContract: auctionContract,
+      optionsMarket,
+      proceedsMint: quoteMint,
+      optionMint: getOptionMint(auctionContract),
+      buyerOptionTokenAccount: await getOptionTokenAccount(auctionContract, buyer),
+      protocolState,
+      buyerPosition: PublicKey.findProgramAddressSync(
+        [Buffer.from("buyer_position"), buyer.publicKey.toBuffer(), auctionContract.toBuffer()],
+        program.programId
+      )[0],
+      buyerQuoteAccount,
+      feeVault,
+      buyer: buyer.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      auctionContract = await getNextOptionContract(writer1.publicKey);
+      await program.methods
+        .writeOption(
+          { call: {} },
+          strikePrice,
+          new anchor.BN(Math.floor(Date.now() / 1000) + 7 * 86400),
+          amount,
+          premiumPerContract
+        )
+        .accountsPartial({
+          optionsMarket,
+          optionContract: auctionContract,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          writerTokenAccount: writer1UnderlyingAccount,
+          writerQuoteAccount: writer1QuoteAccount,
+          writer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer1])
+        .rpc();
+    });
+
+    it("Should fail to start an auction whose start is not above the floor", async () => {
+      try {
+        await program.methods
+          .startDutchAuction(floorPremium, floorPremium, new anchor.BN(3600))
+          .accountsPartial({ optionContract: auctionContract, writer: writer1.publicKey })
+          .signers([writer1])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAuction");
+      }
+    });
+
+    it("Should fail to start an auction as non-writer", async () => {
+      try {
+        await program.methods
+          .startDutchAuction(startPremium, floorPremium, new anchor.BN(3600))
+          .accountsPartial({ optionContract: auctionContract, writer: attacker.publicKey })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedWriter");
+      }
+    });
+
+    it("Should let the writer list the contract in a Dutch auction", async () => {
+      await program.methods
+        .startDutchAuction(startPremium, floorPremium, new anchor.BN(3600))
+        .accountsPartial({ optionContract: auctionContract, writer: writer1.publicKey })
+        .signers([writer1])
+        .rpc();
+
+      const contract = await program.account.optionContract.fetch(auctionContract);
+      expect(contract.premiumPerContract.eq(floorPremium)).to.be.true;
+      expect(contract.auctionStartPremium.eq(startPremium)).to.be.true;
+      expect(contract.auctionDuration.toNumber()).to.equal(3600);
+    });
+
+    it("Should fail to buy at the floor before the price has fallen", async () => {
+      try {
+        await program.methods
+          .buyOption(new anchor.BN(100_000), floorPremium, NO_COST_LIMIT, validUntil())
+          .accountsPartial(await buyAccounts(buyer2, buyer2QuoteAccount))
+          .signers([buyer2])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.message).to.include("PremiumAboveLimit");
+      }
+    });
+
+    it("Should charge the current auction price", async () => {
+      const contracts = new anchor.BN(100_000); // 0.1 contracts
+      const tx = await program.methods
+        .buyOption(contracts, startPremium, NO_COST_LIMIT, validUntil())
+        .accountsPartial(await buyAccounts(buyer2, buyer2QuoteAccount))
+        .signers([buyer2])
+        .rpc();
+
+      // A few seconds into a one-hour auction the price is still close to the start
+      const events = await getEvents(tx);
+      const purchased = events.find((e) => e.name === "optionPurchased");
+      const price = purchased.data.premiumPerContract.toNumber();
+      expect(price).to.be.at.most(startPremium.toNumber());
+      expect(price).to.be.above(19_000_000);
+      expect(purchased.data.totalPremium.toNumber()).to.equal(Math.ceil(price * contracts.toNumber() / 1_000_000));
+    });
+
+    it("Should return to a flat price when the writer reprices", async () => {
+      await program.methods
+        .updatePremium(premiumPerContract)
+        .accountsPartial({ optionContract: auctionContract, writer: writer1.publicKey })
+        .signers([writer1])
+        .rpc();
+
+      const contract = await program.account.optionContract.fetch(auctionContract);
+      expect(contract.auctionDuration.toNumber()).to.equal(0);
+
+      const tx = await program.methods
+        .buyOption(new anchor.BN(100_000), premiumPerContract, NO_COST_LIMIT, validUntil())
+        .accountsPartial(await buyAccounts(buyer2, buyer2QuoteAccount))
+        .signers([buyer2])
+        .rpc();
+      const purchased = (await getEvents(tx)).find((e) => e.name === "optionPurchased");
+      expect(purchased.data.premiumPerContract.eq(premiumPerContract)).to.be.true;
+    });
+  });
+
+  describe("Option Expiration and Liquidation", () => {
+    let expiredOptionContract: PublicKey;
+    let expiredCollateralVault: PublicKey;
+    let keeperOptionContract: PublicKey;
+    const expiredTime = Math.floor(Date.now() / 1000) + 20;
+
+    it("Should write option that will expire", async () => {
+      const shortExpiration = new anchor.BN(expiredTime + 1); // Expires in 1 second
+      
+      expiredOptionContract = await getNextOptionContract(writer1.publicKey);
+      
+      [expiredCollateralVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), expiredOptionContract.toBuffer()],
+        program.programId
+      );
+      
+      const tx = await program.methods
+        .writeOption(
+          { call: {} },
+          strikePrice,
+          shortExpiration,
+          amount,
+          premiumPerContract
+        )
+        .accountsPartial({
+          optionsMarket,
+          optionContract: expiredOptionContract,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          writerTokenAccount: writer1UnderlyingAccount,
+          writerQuoteAccount: writer1QuoteAccount,
+          writer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer1])
+        .rpc();
+      
+      console.log("Created option that will expire soon");
+    });
+
+    it("Should write a second expiring option for keeper settlement", async () => {
+      keeperOptionContract = await getNextOptionContract(writer1.publicKey);
+
+      await program.methods
+        .writeOption(
+          { call: {} },
+          strikePrice,
+          new anchor.BN(expiredTime + 1),
+          amount,
+          premiumPerContract
+        )
+        .accountsPartial({
+          optionsMarket,
+          optionContract: keeperOptionContract,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          writerTokenAccount: writer1UnderlyingAccount,
+          writerQuoteAccount: writer1QuoteAccount,
+          writer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer1])
+        .rpc();
+
+      // Consecutive writes take consecutive nonces from the writer account
+      const expiredContract = await program.account.optionContract.fetch(expiredOptionContract);
+      const keeperContract = await program.account.optionContract.fetch(keeperOptionContract);
+      const writerAccount = await program.account.writerAccount.fetch(getWriterAccount(writer1.publicKey));
+      expect(keeperContract.nonce.eq(expiredContract.nonce.add(new anchor.BN(1)))).to.be.true;
+      expect(writerAccount.nextNonce.eq(keeperContract.nonce.add(new anchor.BN(1)))).to.be.true;
+
+      // Writing delegates the writer tokens to the contract so keepers can settle them
+      const writerTokens = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(getWriterMint(keeperOptionContract), writer1.publicKey)
+      );
+      expect(writerTokens.delegate.toString()).to.equal(keeperOptionContract.toString());
+      expect(Number(writerTokens.delegatedAmount)).to.equal(amount.toNumber());
+    });
+
+    it("Should fail to exercise expired option", async () => {
+      // Wait for option to expire
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      
+      // Try to buy the expired option (should fail)
+      try {
+        // Create a dummy buyer position PDA for this expired option
+        const [expiredBuyerPosition] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("buyer_position"),
+            buyer1.publicKey.toBuffer(),
+            expiredOptionContract.toBuffer()
+          ],
+          program.programId
+        );
+
+        await program.methods
+          .buyOption(new anchor.BN(100_000), premiumPerContract, NO_COST_LIMIT, validUntil())
+          .accountsPartial({
+            optionContract: expiredOptionContract,
+            optionsMarket,
+            proceedsMint: quoteMint,
+            optionMint: getOptionMint(expiredOptionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(expiredOptionContract, buyer1),
+            protocolState,
+            buyerPosition: expiredBuyerPosition,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+        expect.fail("Should have failed - option expired");
+      } catch (error) {
+        // Check for the specific error or insufficient funds due to premium calculation
+        const errorMessage = error.message || error.toString();
+        const hasExpiredError = errorMessage.includes("OptionExpired");
+        const hasInsufficientFunds = errorMessage.includes("insufficient");
+        expect(hasExpiredError || hasInsufficientFunds).to.be.true;
+      }
+    });
+
+    it("Should allow claiming expired option collateral", async () => {
+      // Wait for the option to definitely expire
+      await new Promise(resolve => setTimeout(resolve, 2000));
+      
+      const writer1BalanceBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
+      
+      const tx = await program.methods
+        .claimExpiredOption(amount) // Redeem every writer token
+        .accountsPartial({
+          optionContract: expiredOptionContract,
+          optionsMarket,
+          collateralVault: expiredCollateralVault,
+          writerMint: getWriterMint(expiredOptionContract),
+          writerClaimTokenAccount: getAssociatedTokenAddressSync(
+            getWriterMint(expiredOptionContract),
+            writer1.publicKey
+          ),
+          writerTokenAccount: writer1UnderlyingAccount,
+          writerQuoteAccount: writer1QuoteAccount,
+          writer: writer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([writer1])
+        .rpc();
+      
+      const contractAccount = await program.account.optionContract.fetch(expiredOptionContract);
+      expect(contractAccount.isExpired).to.be.true;
+      
+      // Writer should get collateral back
+      const writer1BalanceAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
+      expect(Number(writer1BalanceAfter.amount)).to.equal(
+        Number(writer1BalanceBefore.amount) + amount.toNumber()
+      );
+
+      // Every unexercised contract releases its collateral, so the vault is empty
+      const vaultAfter = await getAccount(provider.connection, expiredCollateralVault);
+      expect(Number(vaultAfter.amount)).to.equal(0);
+
+      // The redeemed writer tokens are burned
+      const writerTokensAfter = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(getWriterMint(expiredOptionContract), writer1.publicKey)
+      );
+      expect(Number(writerTokensAfter.amount)).to.equal(0);
+    });
+
+    it("Should allow the writer to close the claimed contract and reclaim rent", async () => {
+      const writer1LamportsBefore = await provider.connection.getBalance(writer1.publicKey);
+      const writerClaimTokenAccount = getAssociatedTokenAddressSync(
+        getWriterMint(expiredOptionContract),
+        writer1.publicKey
+      );
+
+      await program.methods
+        .closeOptionContract()
+        .accountsPartial({
+          optionContract: expiredOptionContract,
+          optionsMarket,
+          collateralVault: expiredCollateralVault,
+          writer: writer1.publicKey,
+          rentPayer: writer1.publicKey,
+          proceedsRentPayer: null, // never bought, so no proceeds vault was created
+          writerClaimTokenAccount: writerClaimTokenAccount, // emptied by the claim
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([writer1])
+        .rpc();
+
+      // The contract, its vault and the writer's empty writer token account are gone, with their rent back to the writer
+      expect(await provider.connection.getAccountInfo(expiredOptionContract)).to.be.null;
+      expect(await provider.connection.getAccountInfo(expiredCollateralVault)).to.be.null;
+      expect(await provider.connection.getAccountInfo(writerClaimTokenAccount)).to.be.null;
+      // Mints cannot be closed, so the writer mint outlives the contract
+      expect(await provider.connection.getAccountInfo(getWriterMint(expiredOptionContract))).to.not.be.null;
+      expect(await provider.connection.getAccountInfo(
+        PublicKey.findProgramAddressSync([Buffer.from("proceeds"), expiredOptionContract.toBuffer()], program.programId)[0]
+      )).to.be.null;
+      const writer1LamportsAfter = await provider.connection.getBalance(writer1.publicKey);
+      expect(writer1LamportsAfter).to.be.greaterThan(writer1LamportsBefore);
+    });
+
+    it("Should allow a keeper to settle an expired option for the liquidation fee", async () => {
+      const keeperCollateralVault = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), keeperOptionContract.toBuffer()],
+        program.programId
+      )[0];
+      const writerClaimTokenAccount = getAssociatedTokenAddressSync(
+        getWriterMint(keeperOptionContract),
+        writer1.publicKey
+      );
+
+      const protocolAccount = await program.account.protocolState.fetch(protocolState);
+      const writer1BalanceBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
+      const keeperBalanceBefore = await getAccount(provider.connection, buyer2UnderlyingAccount);
+
+      await program.methods
+        .settleExpiredOption()
+        .accountsPartial({
+          optionContract: keeperOptionContract,
+          optionsMarket,
+          protocolState,
+          collateralVault: keeperCollateralVault,
+          writerMint: getWriterMint(keeperOptionContract),
+          writerClaimTokenAccount,
+          writerTokenAccount: writer1UnderlyingAccount,
+          writerQuoteAccount: writer1QuoteAccount,
+          keeperTokenAccount: buyer2UnderlyingAccount,
+          keeperQuoteAccount: buyer2QuoteAccount,
+          keeper: buyer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer2])
+        .rpc();
+
+      const keeperFee = Math.floor(
+        amount.toNumber() * protocolAccount.liquidationFeeRate.toNumber() / 10_000
+      );
+
+      const writer1BalanceAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
+      const keeperBalanceAfter = await getAccount(provider.connection, buyer2UnderlyingAccount);
+      expect(Number(writer1BalanceAfter.amount)).to.equal(
+        Number(writer1BalanceBefore.amount) + amount.toNumber() - keeperFee
+      );
+      expect(Number(keeperBalanceAfter.amount)).to.equal(
+        Number(keeperBalanceBefore.amount) + keeperFee
+      );
+
+      const contractAccount = await program.account.optionContract.fetch(keeperOptionContract);
+      expect(contractAccount.isExpired).to.be.true;
+
+      const writerTokensAfter = await getAccount(provider.connection, writerClaimTokenAccount);
+      expect(Number(writerTokensAfter.amount)).to.equal(0);
+    });
+
+    it("Should pay premium and exercise proceeds to a writer token transferee", async () => {
+      const optionContract = await getNextOptionContract(writer2.publicKey);
+      const [collateralVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), optionContract.toBuffer()],
+        program.programId
+      );
+      const [proceedsVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("proceeds"), optionContract.toBuffer()],
+        program.programId
+      );
+      const expiration = Math.floor(Date.now() / 1000) + 15;
+
+      await program.methods
+        .writeOption({ call: {} }, strikePrice, new anchor.BN(expiration), amount, premiumPerContract)
+        .accountsPartial({
+          optionsMarket,
+          optionContract,
+          underlyingMint,
+          quoteMint,
+          collateralMint: underlyingMint,
+          writerTokenAccount: writer2UnderlyingAccount,
+          writerQuoteAccount: writer2QuoteAccount,
+          writer: writer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([writer2])
+        .rpc();
+
+      // buyer1 buys and exercises part of the contract
+      const contracts = new anchor.BN(400_000);
+      const buyer1OptionAccount = await getOptionTokenAccount(optionContract, buyer1);
+      await program.methods
+        .buyOption(contracts, premiumPerContract, NO_COST_LIMIT, validUntil())
+        .accountsPartial({
+          optionContract,
+          optionsMarket,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(optionContract),
+          buyerOptionTokenAccount: buyer1OptionAccount,
+          protocolState,
+          buyerQuoteAccount: buyer1QuoteAccount,
+          feeVault,
+          buyer: buyer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
+      await program.methods
+        .exerciseOption(contracts, NO_MIN_RECEIVED, NO_COST_LIMIT)
+        .accountsPartial({
+          optionContract,
+          optionsMarket,
+          proceedsMint: quoteMint,
+          optionMint: getOptionMint(optionContract),
+          buyerOptionTokenAccount: buyer1OptionAccount,
+          protocolState,
+          collateralVault,
+          buyerTokenAccount: buyer1UnderlyingAccount,
+          buyerQuoteAccount: buyer1QuoteAccount,
+          feeVault,
+          buyer: buyer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer1])
+        .rpc();
+
+      // The writer sells half of the short side to buyer2
+      const writerTokens = amount.divn(2);
+      const buyer2WriterAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        buyer2,
+        getWriterMint(optionContract),
+        buyer2.publicKey
+      )).address;
+      await transfer(
+        provider.connection,
+        writer2,
+        getAssociatedTokenAddressSync(getWriterMint(optionContract), writer2.publicKey),
+        buyer2WriterAccount,
+        writer2,
+        writerTokens.toNumber()
+      );
+
+      while (Math.floor(Date.now() / 1000) <= expiration + 1) {
+        await new Promise(resolve => setTimeout(resolve, 1000));
+      }
+
+      const collateralBefore = Number((await getAccount(provider.connection, collateralVault)).amount);
+      const proceedsBefore = Number((await getAccount(provider.connection, proceedsVault)).amount);
+      const buyer2UnderlyingBefore = await getAccount(provider.connection, buyer2UnderlyingAccount);
+      const buyer2QuoteBefore = await getAccount(provider.connection, buyer2QuoteAccount);
+
+      await program.methods
+        .claimExpiredOption(writerTokens)
+        .accountsPartial({
+          optionContract,
+          optionsMarket,
+          collateralVault,
+          proceedsVault,
+          writerMint: getWriterMint(optionContract),
+          writerClaimTokenAccount: buyer2WriterAccount,
+          writerTokenAccount: buyer2UnderlyingAccount,
+          writerQuoteAccount: buyer2QuoteAccount,
+          writer: buyer2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([buyer2])
         .rpc();
-      
-      // For puts: buyer gives underlying tokens, receives quote tokens
-      const buyer1UnderlyingAfter = await getAccount(provider.connection, buyer1UnderlyingAccount);
-      const buyer1QuoteAfter = await getAccount(provider.connection, buyer1QuoteAccount);
-      const writer2UnderlyingAfter = await getAccount(provider.connection, writer2UnderlyingAccount);
-      
-      // Buyer should have fewer underlying tokens
-      expect(Number(buyer1UnderlyingAfter.amount)).to.equal(
-        Number(buyer1UnderlyingBefore.amount) - contractsToExercise.toNumber()
-      );
-      
-      // Buyer should have more quote tokens
-      expect(Number(buyer1QuoteAfter.amount)).to.be.greaterThan(
-        Number(buyer1QuoteBefore.amount)
+
+      // Half the writer tokens earn half the remaining collateral and half the premium and strike payments
+      expect(collateralBefore).to.equal(amount.sub(contracts).toNumber());
+      const buyer2UnderlyingAfter = await getAccount(provider.connection, buyer2UnderlyingAccount);
+      const buyer2QuoteAfter = await getAccount(provider.connection, buyer2QuoteAccount);
+      expect(Number(buyer2UnderlyingAfter.amount) - Number(buyer2UnderlyingBefore.amount)).to.equal(
+        Math.floor(collateralBefore / 2)
       );
-      
-      // Writer should have more underlying tokens
-      expect(Number(writer2UnderlyingAfter.amount)).to.equal(
-        Number(writer2UnderlyingBefore.amount) + contractsToExercise.toNumber()
+      expect(Number(buyer2QuoteAfter.amount) - Number(buyer2QuoteBefore.amount)).to.equal(
+        Math.floor(proceedsBefore / 2)
       );
     });
-  });
-
-  describe("Option Expiration and Liquidation", () => {
-    let expiredOptionContract: PublicKey;
-    let expiredCollateralVault: PublicKey;
-    let expiredQuoteCollateralVault: PublicKey;
-    const expiredTime = Math.floor(Date.now() / 1000) + 20;
 
-    it("Should write option that will expire", async () => {
-      const shortExpiration = new anchor.BN(expiredTime + 1); // Expires in 1 second
-      
-      [expiredOptionContract] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("option_contract"),
-          writer1.publicKey.toBuffer(),
-          underlyingMint.toBuffer(),
-          new anchor.BN(expiredTime).toArrayLike(Buffer, "le", 8)
-        ],
-        program.programId
-      );
-      
-      [expiredCollateralVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("collateral"), expiredOptionContract.toBuffer()],
-        program.programId
-      );
-      
-      [expiredQuoteCollateralVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("quote_collateral"), expiredOptionContract.toBuffer()],
+    it("Should pass a sold put's premium to the holder without the keeper fee", async () => {
+      const optionContract = await getNextOptionContract(writer2.publicKey);
+      const [collateralVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("collateral"), optionContract.toBuffer()],
         program.programId
       );
-      
-      const tx = await program.methods
-        .writeOption(
-          new anchor.BN(expiredTime),
-          { call: {} },
-          strikePrice,
-          shortExpiration,
-          amount,
-          premiumPerContract
-        )
+      const expiration = Math.floor(Date.now() / 1000) + 15;
+
+      await program.methods
+        .writeOption({ put: {} }, strikePrice, new anchor.BN(expiration), amount, premiumPerContract)
         .accountsPartial({
           optionsMarket,
+          optionContract,
           underlyingMint,
           quoteMint,
-          writerTokenAccount: writer1UnderlyingAccount,
-          writerQuoteAccount: writer1QuoteAccount,
-          writer: writer1.publicKey,
+          collateralMint: quoteMint,
+          writerTokenAccount: writer2UnderlyingAccount,
+          writerQuoteAccount: writer2QuoteAccount,
+          writer: writer2.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
         })
-        .signers([writer1])
+        .signers([writer2])
         .rpc();
-      
-      console.log("Created option that will expire soon");
-    });
+      const collateral = Number((await getAccount(provider.connection, collateralVault)).amount);
 
-    it("Should fail to exercise expired option", async () => {
-      // Wait for option to expire
-      await new Promise(resolve => setTimeout(resolve, 3000));
-      
-      // Try to buy the expired option (should fail)
-      try {
-        // Create a dummy buyer position PDA for this expired option
-        const [expiredBuyerPosition] = PublicKey.findProgramAddressSync(
-          [
-            Buffer.from("buyer_position"),
-            buyer1.publicKey.toBuffer(),
-            expiredOptionContract.toBuffer()
-          ],
-          program.programId
-        );
+      await program.methods
+        .buyOption(amount, premiumPerContract, NO_COST_LIMIT, validUntil())
+        .accountsPartial({
+          optionContract,
+          optionsMarket,
+          proceedsMint: underlyingMint,
+          optionMint: getOptionMint(optionContract),
+          buyerOptionTokenAccount: await getOptionTokenAccount(optionContract, buyer1),
+          protocolState,
+          buyerQuoteAccount: buyer1QuoteAccount,
+          feeVault,
+          buyer: buyer1.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer1])
+        .rpc();
 
-        await program.methods
-          .buyOption(new anchor.BN(100_000))
-          .accountsPartial({
-            optionContract: expiredOptionContract,
-            protocolState,
-            buyerPosition: expiredBuyerPosition,
-            buyerQuoteAccount: buyer1QuoteAccount,
-            writerQuoteAccount: writer1QuoteAccount,
-            protocolFeeAccount,
-            buyer: buyer1.publicKey,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([buyer1])
-          .rpc();
-        expect.fail("Should have failed - option expired");
-      } catch (error) {
-        // Check for the specific error or insufficient funds due to premium calculation
-        const errorMessage = error.message || error.toString();
-        const hasExpiredError = errorMessage.includes("OptionExpired");
-        const hasInsufficientFunds = errorMessage.includes("insufficient");
-        expect(hasExpiredError || hasInsufficientFunds).to.be.true;
+      // A put's net premium sits in the collateral vault next to the collateral
+      const netPremium = Number((await getAccount(provider.connection, collateralVault)).amount) - collateral;
+      expect(netPremium).to.be.greaterThan(0);
+      const contractAfterBuy = await program.account.optionContract.fetch(optionContract);
+      expect(contractAfterBuy.heldPremium.toNumber()).to.equal(netPremium);
+
+      while (Math.floor(Date.now() / 1000) <= expiration + 1) {
+        await new Promise(resolve => setTimeout(resolve, 1000));
       }
-    });
 
-    it("Should allow claiming expired option collateral", async () => {
-      // Wait for the option to definitely expire
-      await new Promise(resolve => setTimeout(resolve, 2000));
-      
-      const writer1BalanceBefore = await getAccount(provider.connection, writer1UnderlyingAccount);
-      
-      const tx = await program.methods
-        .claimExpiredOption()
+      const protocolAccount = await program.account.protocolState.fetch(protocolState);
+      const writer2QuoteBefore = await getAccount(provider.connection, writer2QuoteAccount);
+      const keeperQuoteBefore = await getAccount(provider.connection, buyer2QuoteAccount);
+
+      await program.methods
+        .settleExpiredOption()
         .accountsPartial({
-          optionContract: expiredOptionContract,
-          collateralVault: expiredCollateralVault,
-          quoteCollateralVault: expiredQuoteCollateralVault,
-          writerTokenAccount: writer1UnderlyingAccount,
-          writerQuoteAccount: writer1QuoteAccount,
-          writer: writer1.publicKey,
+          optionContract,
+          optionsMarket,
+          protocolState,
+          collateralVault,
+          writerMint: getWriterMint(optionContract),
+          writerClaimTokenAccount: getAssociatedTokenAddressSync(getWriterMint(optionContract), writer2.publicKey),
+          writerTokenAccount: writer2UnderlyingAccount,
+          writerQuoteAccount: writer2QuoteAccount,
+          keeperTokenAccount: buyer2UnderlyingAccount,
+          keeperQuoteAccount: buyer2QuoteAccount,
+          keeper: buyer2.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([writer1])
+        .signers([buyer2])
         .rpc();
-      
-      const contractAccount = await program.account.optionContract.fetch(expiredOptionContract);
-      expect(contractAccount.isExpired).to.be.true;
-      
-      // Writer should get collateral back
-      const writer1BalanceAfter = await getAccount(provider.connection, writer1UnderlyingAccount);
-      expect(Number(writer1BalanceAfter.amount)).to.equal(
-        Number(writer1BalanceBefore.amount) + amount.toNumber()
+
+      // The keeper fee is taken on the collateral only; the premium reaches the holder in full
+      const keeperFee = Math.floor(collateral * protocolAccount.liquidationFeeRate.toNumber() / 10_000);
+      const writer2QuoteAfter = await getAccount(provider.connection, writer2QuoteAccount);
+      const keeperQuoteAfter = await getAccount(provider.connection, buyer2QuoteAccount);
+      expect(Number(writer2QuoteAfter.amount) - Number(writer2QuoteBefore.amount)).to.equal(
+        collateral - keeperFee + netPremium
       );
+      expect(Number(keeperQuoteAfter.amount) - Number(keeperQuoteBefore.amount)).to.equal(keeperFee);
+
+      const contractAccount = await program.account.optionContract.fetch(optionContract);
+      expect(contractAccount.heldPremium.toNumber()).to.equal(0);
+      expect(Number((await getAccount(provider.connection, collateralVault)).amount)).to.equal(0);
     });
   });
 
@@ -915,13 +3397,11 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       // For brevity, testing the concept with a basic unauthorized access attempt
       try {
         await program.methods
-          .exerciseOption()
+          .exerciseOption(new anchor.BN(1), NO_MIN_RECEIVED, NO_COST_LIMIT)
           .accountsPartial({
             buyerTokenAccount: attackerUnderlyingAccount,
             buyerQuoteAccount: attackerQuoteAccount,
-            writerTokenAccount: writer1UnderlyingAccount,
-            writerQuoteAccount: writer1QuoteAccount,
-            protocolFeeAccount,
+            feeVault,
             buyer: attacker.publicKey, // Wrong buyer
             tokenProgram: TOKEN_PROGRAM_ID,
           })
@@ -936,7 +3416,7 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
     it("Should fail when trying to claim expired option as wrong writer", async () => {
       try {
         await program.methods
-          .claimExpiredOption()
+          .claimExpiredOption(new anchor.BN(1))
           .accountsPartial({
             writerTokenAccount: attackerUnderlyingAccount,
             writerQuoteAccount: attackerQuoteAccount,
@@ -960,7 +3440,6 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       
       const tx = await program.methods
         .writeOption(
-          new anchor.BN(testTime),
           { call: {} },
           strikePrice,
           new anchor.BN(testTime + 86400),
@@ -969,8 +3448,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
         )
         .accountsPartial({
           optionsMarket,
+          optionContract: await getNextOptionContract(writer1.publicKey),
           underlyingMint,
           quoteMint,
+          collateralMint: underlyingMint,
           writerTokenAccount: writer1UnderlyingAccount,
           writerQuoteAccount: writer1QuoteAccount,
           writer: writer1.publicKey,
@@ -992,7 +3473,6 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       try {
         const tx = await program.methods
           .writeOption(
-            new anchor.BN(testTime),
             { call: {} },
             strikePrice,
             new anchor.BN(testTime + 86400),
@@ -1001,8 +3481,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
           )
           .accountsPartial({
             optionsMarket,
+            optionContract: await getNextOptionContract(writer1.publicKey),
             underlyingMint,
             quoteMint,
+            collateralMint: underlyingMint,
             writerTokenAccount: writer1UnderlyingAccount,
             writerQuoteAccount: writer1QuoteAccount,
             writer: writer1.publicKey,
@@ -1033,6 +3515,64 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       console.log("Total protocol fees:", protocolStateAccount.totalFeesCollected.toString());
     });
 
+    it("Should track fees per mint in the fee vault", async () => {
+      const feeStatsAccount = await program.account.feeStats.fetch(feeStats);
+      const vaultBalance = await getAccount(provider.connection, feeVault);
+
+      expect(feeStatsAccount.mint.equals(quoteMint)).to.be.true;
+      expect(feeStatsAccount.totalFeesCollected.toNumber()).to.be.greaterThan(0);
+      expect(Number(vaultBalance.amount)).to.equal(
+        feeStatsAccount.totalFeesCollected.sub(feeStatsAccount.totalFeesWithdrawn).toNumber()
+      );
+    });
+
+    it("Should fail to withdraw protocol fees as non-authority", async () => {
+      try {
+        await program.methods
+          .withdrawProtocolFees(new anchor.BN(1))
+          .accountsPartial({
+            feeVault,
+            feeStats,
+            treasury: attackerQuoteAccount,
+            authority: attacker.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([attacker])
+          .rpc();
+        expect.fail("Should have failed with unauthorized authority");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedAuthority");
+      }
+    });
+
+    it("Should allow authority to withdraw protocol fees to a treasury", async () => {
+      const vaultBefore = await getAccount(provider.connection, feeVault);
+      const treasuryBefore = await getAccount(provider.connection, treasuryAccount);
+      const withdrawAmount = new anchor.BN(Number(vaultBefore.amount));
+
+      await program.methods
+        .withdrawProtocolFees(withdrawAmount)
+        .accountsPartial({
+          feeVault,
+          feeStats,
+          treasury: treasuryAccount,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+
+      const vaultAfter = await getAccount(provider.connection, feeVault);
+      const treasuryAfter = await getAccount(provider.connection, treasuryAccount);
+      expect(Number(vaultAfter.amount)).to.equal(0);
+      expect(Number(treasuryAfter.amount)).to.equal(
+        Number(treasuryBefore.amount) + withdrawAmount.toNumber()
+      );
+
+      const feeStatsAccount = await program.account.feeStats.fetch(feeStats);
+      expect(feeStatsAccount.totalFeesWithdrawn.eq(withdrawAmount)).to.be.true;
+    });
+
     it("Should verify fee calculations are consistent", async () => {
       const protocolStateAccount = await program.account.protocolState.fetch(protocolState);
       const expectedFeeRate = 50; // 0.5% as set in initialization
@@ -1052,7 +3592,6 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       for (let i = 0; i < 3; i++) {
         const promise = program.methods
           .writeOption(
-            new anchor.BN(baseTime + i),
             { call: {} },
             new anchor.BN(50_000_000 + i * 1000000), // Slightly different strike prices
             new anchor.BN(baseTime + 86400),
@@ -1061,8 +3600,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
           )
           .accountsPartial({
             optionsMarket,
+            optionContract: await getNextOptionContract(writer1.publicKey),
             underlyingMint,
             quoteMint,
+            collateralMint: underlyingMint,
             writerTokenAccount: writer1UnderlyingAccount,
             writerQuoteAccount: writer1QuoteAccount,
             writer: writer1.publicKey,
@@ -1123,9 +3664,19 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       // Should have recorded options written
       expect(marketAccount.totalOptionsWritten.toNumber()).to.be.greaterThan(0);
       expect(marketAccount.isActive).to.be.true;
+
+      // Should have recorded premium volume and settled contracts
+      expect(marketAccount.totalVolume.toNumber()).to.be.greaterThan(0);
+      expect(marketAccount.totalContractsExercised.toNumber()).to.be.greaterThan(0);
+      expect(marketAccount.totalContractsExpired.toNumber()).to.be.greaterThan(0);
+      expect(marketAccount.openInterestSold.lte(marketAccount.openInterestWritten)).to.be.true;
       
       console.log("Market options written:", marketAccount.totalOptionsWritten.toString());
       console.log("Market volume:", marketAccount.totalVolume.toString());
+      console.log("Market open interest (written/sold):",
+        marketAccount.openInterestWritten.toString(),
+        marketAccount.openInterestSold.toString()
+      );
     });
   });
 
@@ -1140,7 +3691,6 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
           try {
             const tx = await program.methods
               .writeOption(
-                new anchor.BN(baseTime + index),
                 { call: {} },
                 new anchor.BN(50_000_000 + index * 1000000), // Different strike prices
                 new anchor.BN(baseTime + 86400),
@@ -1149,8 +3699,10 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
               )
               .accountsPartial({
                 optionsMarket,
+                optionContract: await getNextOptionContract(writer1.publicKey),
                 underlyingMint,
                 quoteMint,
+                collateralMint: underlyingMint,
                 writerTokenAccount: writer1UnderlyingAccount,
                 writerQuoteAccount: writer1QuoteAccount,
                 writer: writer1.publicKey,
@@ -1206,4 +3758,237 @@ describe("Solana Options DEX - Comprehensive Tests", () => {
       console.log("\n✅ All systems operational - Protocol ready for deployment!");
     });
   });
+
+  describe("Non-6-Decimal Underlyings", () => {
+    // Prices stay in quote base units per whole underlying token, so each market divides by its own scale
+    const oddStrike = new anchor.BN(123_456_789); // 123.456789 USDC
+    const oddPremium = new anchor.BN(7_654_321); // 7.654321 USDC per contract
+    const markets = [
+      { decimals: 9, marketId: new anchor.BN(9), amount: new anchor.BN(1_234_567_891), contractsToBuy: new anchor.BN(987_654_321) },
+      { decimals: 8, marketId: new anchor.BN(8), amount: new anchor.BN(123_456_789), contractsToBuy: new anchor.BN(98_765_431) },
+    ];
+    const setups: Record<number, { mint: PublicKey; market: PublicKey; writerAccount: PublicKey; buyerAccount: PublicKey }> = {};
+
+    const ceilDiv = (a: anchor.BN, b: anchor.BN): anchor.BN => a.add(b).sub(new anchor.BN(1)).div(b);
+    const balanceOf = async (account: PublicKey): Promise<anchor.BN> =>
+      new anchor.BN((await getAccount(provider.connection, account)).amount.toString());
+
+    before(async () => {
+      // Top up quote so the earlier tests' spending cannot starve these writes and buys
+      await mintTo(provider.connection, authority, quoteMint, writer1QuoteAccount, authority, INITIAL_BALANCE);
+      await mintTo(provider.connection, authority, quoteMint, buyer1QuoteAccount, authority, INITIAL_BALANCE);
+
+      for (const { decimals, marketId: id } of markets) {
+        const mint = await createMint(provider.connection, authority, authority.publicKey, authority.publicKey, decimals);
+        const writerAccount = await createAccount(provider.connection, writer1, mint, writer1.publicKey);
+        const buyerAccount = await createAccount(provider.connection, buyer1, mint, buyer1.publicKey);
+        const wholeTokens = BigInt(`1000${"0".repeat(decimals)}`); // 1000 whole tokens
+        await mintTo(provider.connection, authority, mint, writerAccount, authority, wholeTokens);
+        await mintTo(provider.connection, authority, mint, buyerAccount, authority, wholeTokens);
+
+        const [market] = PublicKey.findProgramAddressSync(
+          [Buffer.from("options_market"), id.toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        await program.methods
+          .createOptionsMarket(id)
+          .accountsPartial({
+            underlyingMint: mint,
+            quoteMint,
+            authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        const marketAccount = await program.account.optionsMarket.fetch(market);
+        expect(marketAccount.underlyingDecimals).to.equal(decimals);
+        expect(marketAccount.underlyingScale.eq(new anchor.BN(10).pow(new anchor.BN(decimals)))).to.be.true;
+
+        setups[decimals] = { mint, market, writerAccount, buyerAccount };
+      }
+    });
+
+    for (const { decimals, amount: written, contractsToBuy } of markets) {
+      const scale = new anchor.BN(10).pow(new anchor.BN(decimals));
+
+      // Writes an option of the given type, then has buyer1 buy part of it at the listed premium
+      const writeAndBuy = async (optionType: { call: {} } | { put: {} }) => {
+        const { mint, market, writerAccount } = setups[decimals];
+        const isCall = "call" in optionType;
+        const optionContract = await getNextOptionContract(writer1.publicKey);
+        const [collateralVault] = PublicKey.findProgramAddressSync(
+          [Buffer.from("collateral"), optionContract.toBuffer()],
+          program.programId
+        );
+        const [buyerPosition] = PublicKey.findProgramAddressSync(
+          [Buffer.from("buyer_position"), buyer1.publicKey.toBuffer(), optionContract.toBuffer()],
+          program.programId
+        );
+        const [proceedsVault] = PublicKey.findProgramAddressSync(
+          [Buffer.from("proceeds"), optionContract.toBuffer()],
+          program.programId
+        );
+
+        // Calls lock one underlying base unit per contract; puts lock the strike value, rounded up
+        const collateral = isCall ? written : ceilDiv(oddStrike.mul(written), scale);
+        const writerCollateralAccount = isCall ? writerAccount : writer1QuoteAccount;
+        const writerCollateralBefore = await balanceOf(writerCollateralAccount);
+
+        await program.methods
+          .writeOption(
+            optionType,
+            oddStrike,
+            new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+            written,
+            oddPremium
+          )
+          .accountsPartial({
+            optionsMarket: market,
+            optionContract,
+            underlyingMint: mint,
+            quoteMint,
+            collateralMint: isCall ? mint : quoteMint,
+            writerTokenAccount: writerAccount,
+            writerQuoteAccount: writer1QuoteAccount,
+            writer: writer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([writer1])
+          .rpc();
+
+        expect((await balanceOf(collateralVault)).eq(collateral)).to.be.true;
+        expect((await balanceOf(writerCollateralAccount)).eq(writerCollateralBefore.sub(collateral))).to.be.true;
+
+        // The buyer pays the premium rounded up; the fee is taken from it rounded down
+        const protocolAccount = await program.account.protocolState.fetch(protocolState);
+        const totalPremium = ceilDiv(oddPremium.mul(contractsToBuy), scale);
+        const protocolFee = totalPremium.mul(protocolAccount.protocolFeeRate).div(new anchor.BN(10_000));
+        const netPremium = totalPremium.sub(protocolFee);
+        const buyerQuoteBefore = await balanceOf(buyer1QuoteAccount);
+        const feeVaultBefore = await balanceOf(feeVault);
+
+        await program.methods
+          .buyOption(contractsToBuy, oddPremium, totalPremium, validUntil())
+          .accountsPartial({
+            optionContract,
+            optionsMarket: market,
+            proceedsMint: isCall ? quoteMint : mint,
+            optionMint: getOptionMint(optionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(optionContract, buyer1),
+            protocolState,
+            buyerPosition,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect((await balanceOf(buyer1QuoteAccount)).eq(buyerQuoteBefore.sub(totalPremium))).to.be.true;
+        expect((await balanceOf(feeVault)).eq(feeVaultBefore.add(protocolFee))).to.be.true;
+        const position = await program.account.buyerPosition.fetch(buyerPosition);
+        expect(position.contractsOwned.eq(contractsToBuy)).to.be.true;
+        expect(position.premiumPaid.eq(totalPremium)).to.be.true;
+        // A call's net premium waits in the quote proceeds vault; a put's stays with its collateral
+        if (isCall) {
+          expect((await balanceOf(proceedsVault)).eq(netPremium)).to.be.true;
+          expect((await balanceOf(collateralVault)).eq(collateral)).to.be.true;
+        } else {
+          expect((await balanceOf(proceedsVault)).toNumber()).to.equal(0);
+          expect((await balanceOf(collateralVault)).eq(collateral.add(netPremium))).to.be.true;
+          const contractAccount = await program.account.optionContract.fetch(optionContract);
+          expect(contractAccount.heldPremium.eq(netPremium)).to.be.true;
+        }
+
+        return { optionContract, collateralVault, buyerPosition, proceedsVault, collateral, netPremium };
+      };
+
+      it(`Should write, buy and exercise a call on a ${decimals}-decimal underlying at exact amounts`, async () => {
+        const { market, buyerAccount } = setups[decimals];
+        const { optionContract, collateralVault, buyerPosition, proceedsVault, collateral, netPremium } =
+          await writeAndBuy({ call: {} });
+
+        // The holder pays the strike rounded up plus a fee on the strike value rounded down
+        const protocolAccount = await program.account.protocolState.fetch(protocolState);
+        const payment = ceilDiv(oddStrike.mul(contractsToBuy), scale);
+        const settlementValue = oddStrike.mul(contractsToBuy).div(scale);
+        const settlementFee = settlementValue.mul(protocolAccount.settlementFeeRate).div(new anchor.BN(10_000));
+        const buyerQuoteBefore = await balanceOf(buyer1QuoteAccount);
+        const buyerUnderlyingBefore = await balanceOf(buyerAccount);
+        const feeVaultBefore = await balanceOf(feeVault);
+
+        await program.methods
+          .exerciseOption(contractsToBuy, contractsToBuy, payment.add(settlementFee))
+          .accountsPartial({
+            optionContract,
+            optionsMarket: market,
+            proceedsMint: quoteMint,
+            optionMint: getOptionMint(optionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(optionContract, buyer1),
+            buyerPosition,
+            protocolState,
+            collateralVault,
+            buyerTokenAccount: buyerAccount,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect((await balanceOf(buyer1QuoteAccount)).eq(buyerQuoteBefore.sub(payment).sub(settlementFee))).to.be.true;
+        expect((await balanceOf(buyerAccount)).eq(buyerUnderlyingBefore.add(contractsToBuy))).to.be.true;
+        expect((await balanceOf(feeVault)).eq(feeVaultBefore.add(settlementFee))).to.be.true;
+        expect((await balanceOf(proceedsVault)).eq(netPremium.add(payment))).to.be.true;
+        expect((await balanceOf(collateralVault)).eq(collateral.sub(contractsToBuy))).to.be.true;
+      });
+
+      it(`Should write, buy and exercise a put on a ${decimals}-decimal underlying at exact amounts`, async () => {
+        const { mint, market, buyerAccount } = setups[decimals];
+        const { optionContract, collateralVault, buyerPosition, proceedsVault, collateral, netPremium } =
+          await writeAndBuy({ put: {} });
+
+        // The holder delivers the underlying and receives the strike value rounded down, net of the fee
+        const protocolAccount = await program.account.protocolState.fetch(protocolState);
+        const settlementValue = oddStrike.mul(contractsToBuy).div(scale);
+        const settlementFee = settlementValue.mul(protocolAccount.settlementFeeRate).div(new anchor.BN(10_000));
+        const payout = settlementValue.sub(settlementFee);
+        const buyerQuoteBefore = await balanceOf(buyer1QuoteAccount);
+        const buyerUnderlyingBefore = await balanceOf(buyerAccount);
+        const feeVaultBefore = await balanceOf(feeVault);
+
+        await program.methods
+          .exerciseOption(contractsToBuy, payout, NO_COST_LIMIT)
+          .accountsPartial({
+            optionContract,
+            optionsMarket: market,
+            proceedsMint: mint,
+            optionMint: getOptionMint(optionContract),
+            buyerOptionTokenAccount: await getOptionTokenAccount(optionContract, buyer1),
+            buyerPosition,
+            protocolState,
+            collateralVault,
+            buyerTokenAccount: buyerAccount,
+            buyerQuoteAccount: buyer1QuoteAccount,
+            feeVault,
+            buyer: buyer1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([buyer1])
+          .rpc();
+
+        expect((await balanceOf(buyer1QuoteAccount)).eq(buyerQuoteBefore.add(payout))).to.be.true;
+        expect((await balanceOf(buyerAccount)).eq(buyerUnderlyingBefore.sub(contractsToBuy))).to.be.true;
+        expect((await balanceOf(feeVault)).eq(feeVaultBefore.add(settlementFee))).to.be.true;
+        expect((await balanceOf(proceedsVault)).eq(contractsToBuy)).to.be.true;
+        expect((await balanceOf(collateralVault)).eq(collateral.add(netPremium).sub(settlementValue))).to.be.true;
+      });
+    }
+  });
 }); 
\ No newline at end of file